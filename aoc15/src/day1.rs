@@ -1,30 +1,46 @@
-pub fn solution() {
-    let input = include_str!("../data/day1.txt");
-    println!(
-        "The instructions takes santa to floor: {}",
-        input.chars().fold(0, |acc, c| {
-            match c {
-                '(' => acc + 1,
-                ')' => acc - 1,
-                _ => acc,
-            }
-        })
-    );
+use aoc_utils::prelude::*;
+
+/// The floors Santa moves by, up for `(` and down for `)`.
+pub fn generator(input: &str) -> Result<Vec<i32>, ParseError> {
+    let step = |_, c| match c {
+        '(' => Some(1),
+        ')' => Some(-1),
+        _ => None,
+    };
+    Ok(Source::new(input)
+        .grid(input.trim(), step, "( or )")?
+        .concat())
+}
+
+pub fn part_1(input: &[i32]) -> i32 {
+    input.iter().sum()
+}
 
+pub fn part_2(input: &[i32]) -> usize {
     let mut floor = 0;
-    println!(
-        "The {}th character makes santa enter the basement",
-        input
-            .chars()
-            .position(|c| {
-                match c {
-                    '(' => floor += 1,
-                    ')' => floor -= 1,
-                    _ => (),
-                };
-                floor == -1
-            })
-            .unwrap()
-            + 1
-    );
+    input
+        .iter()
+        .position(|step| {
+            floor += step;
+            floor == -1
+        })
+        .expect("Santa never enters the basement")
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        balanced: ("(())", part_1 = 0),
+        alternating: ("()()", part_1 = 0),
+        up_three: ("(((", part_1 = 3),
+        up_three_late: ("))(((((", part_1 = 3),
+        basement: ("())", part_1 = -1),
+        deep_basement: (")())())", part_1 = -3),
+        first_step: (")", part_2 = 1),
+        fifth_step: ("()())", part_2 = 5),
+    }
 }
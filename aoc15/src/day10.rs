@@ -1,8 +1,9 @@
-pub fn solution() {
-    let input = include_str!("../data/day10.txt");
-    let mut chars = input.trim().chars().collect::<Vec<_>>();
+use aoc_utils::prelude::*;
+
+fn look_and_say(input: &[char], iterations: usize) -> usize {
+    let mut chars = input.to_vec();
     let mut res = String::new();
-    (0..50).for_each(|i| {
+    (0..iterations).for_each(|_| {
         let mut count = 1;
         res.clear();
         (0..chars.len()).for_each(|i| {
@@ -15,10 +16,23 @@ pub fn solution() {
             }
             count += 1;
         });
-        if i == 39 {
-            println!("Length of string after 40 iterations: {}", res.len());
-        }
         chars = res.chars().collect();
     });
-    println!("Length of string after 50 iterations: {}", res.len());
+    res.len()
+}
+
+/// The digits of the starting sequence.
+pub fn generator(input: &str) -> Result<Vec<char>, ParseError> {
+    let digit = |_, c: char| c.is_ascii_digit().then_some(c);
+    Ok(Source::new(input)
+        .grid(input.trim(), digit, "a digit")?
+        .concat())
+}
+
+pub fn part_1(input: &[char]) -> usize {
+    look_and_say(input, 40)
+}
+
+pub fn part_2(input: &[char]) -> usize {
+    look_and_say(input, 50)
 }
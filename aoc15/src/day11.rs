@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_utils::prelude::*;
+
 fn is_valid_password(s: &str) -> bool {
     let chars = s.chars().collect::<Vec<_>>();
     let contains_iol = s.contains(|c| c == 'i' || c == 'o' || c == 'l');
@@ -26,18 +28,43 @@ fn increment_password(s: &str) -> String {
     chars.into_iter().collect()
 }
 
-pub fn solution() {
-    let mut curr_password = include_str!("../data/day11.txt")
-        .trim()
-        .to_string();
-    for i in 0..2 {
-        while !is_valid_password(&curr_password) {
-            curr_password = increment_password(&curr_password);
-        }
-        if i == 0 {
-            println!("Santas new password is (part 1): {curr_password}");
-            curr_password = increment_password(&curr_password);
-        }
+fn next_password(password: &str) -> String {
+    let mut curr_password = increment_password(password);
+    while !is_valid_password(&curr_password) {
+        curr_password = increment_password(&curr_password);
+    }
+    curr_password
+}
+
+/// Santa's current password, which must be lowercase letters.
+pub fn generator(input: &str) -> Result<String, ParseError> {
+    let source = Source::new(input);
+    let password = input.trim();
+    if password.is_empty() {
+        return Err(source.error_after(password, "a password"));
+    }
+    let letter = |_, c: char| c.is_ascii_lowercase().then_some(c);
+    Ok(source
+        .grid(password, letter, "a lowercase letter")?
+        .concat()
+        .into_iter()
+        .collect())
+}
+
+pub fn part_1(input: &str) -> String {
+    next_password(input)
+}
+
+pub fn part_2(input: &str) -> String {
+    next_password(&part_1(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: ("abcdefgh", part_1 = "abcdffaa"),
     }
-    println!("Santas new password is (part 2): {curr_password}");
 }
@@ -1,10 +1,25 @@
 use serde_json::Value;
 
-pub fn solution() {
-    let input = include_str!("../data/day12.txt");
-    let v = serde_json::from_str::<serde_json::Value>(input).unwrap();
-    println!("Sum of all numbers: {}", sum_numbers(&v, ""));
-    println!("Sum of all numbers without red objects: {}", sum_numbers(&v, "red"));
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Value, ParseError> {
+    serde_json::from_str::<serde_json::Value>(input).map_err(|err| {
+        // serde_json reports 1-based positions, pointing at the invalid byte
+        let line = input
+            .lines()
+            .nth(err.line().saturating_sub(1))
+            .unwrap_or("");
+        let column = err.column().saturating_sub(1).min(line.len());
+        Source::new(input).error_after(&line[..column], "valid JSON")
+    })
+}
+
+pub fn part_1(input: &Value) -> i64 {
+    sum_numbers(input, "")
+}
+
+pub fn part_2(input: &Value) -> i64 {
+    sum_numbers(input, "red")
 }
 
 fn sum_numbers(v: &Value, filter: &str) -> i64 {
@@ -21,3 +36,21 @@ fn sum_numbers(v: &Value, filter: &str) -> i64 {
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        array: ("[1,2,3]", part_1 = 6),
+        object: (r#"{"a":2,"b":4}"#, part_1 = 6),
+        nested_array: ("[[[3]]]", part_1 = 3),
+        nested_object: (r#"{"a":{"b":4},"c":-1}"#, part_1 = 3),
+        cancelling: (r#"[-1,{"a":1}]"#, part_1 = 0),
+        empty: ("[]", part_1 = 0, part_2 = 0),
+        red_in_object: (r#"[1,{"c":"red","b":2},3]"#, part_1 = 6, part_2 = 4),
+        red_at_top: (r#"{"d":"red","e":[1,2,3,4],"f":5}"#, part_1 = 15, part_2 = 0),
+        red_in_array: (r#"[1,"red",5]"#, part_2 = 6),
+    }
+}
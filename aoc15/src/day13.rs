@@ -1,7 +1,9 @@
 use itertools::Itertools;
 use std::collections::HashMap;
 
-fn get_max_happiness(graph: &HashMap<String, HashMap<String, isize>>) -> isize {
+use aoc_utils::prelude::*;
+
+fn get_max_happiness(graph: &Happiness) -> isize {
     let mut max_happiness = isize::min_value();
     let possible_seating_arrangements = graph.keys().permutations(graph.len());
     for seating_arrangement in possible_seating_arrangements {
@@ -18,38 +20,69 @@ fn get_max_happiness(graph: &HashMap<String, HashMap<String, isize>>) -> isize {
     max_happiness
 }
 
-pub fn solution() {
+type Happiness = HashMap<String, HashMap<String, isize>>;
+
+pub fn generator(input: &str) -> Result<Happiness, ParseError> {
+    let source = Source::new(input);
     let mut happiness = HashMap::new();
-    let input = include_str!("../data/day13.txt");
-    input.lines().for_each(|line| {
+    for line in input.lines() {
         let parts = line.split_whitespace().collect::<Vec<_>>();
-        let name = parts[0].to_string();
-        let other_name = parts[10].trim_end_matches('.').to_string();
-        let happiness_score = if parts[2] == "gain" {
-            parts[3].parse().unwrap()
-        } else {
-            -parts[3].parse::<isize>().unwrap()
+        let [name, _, sign, score, .., other_name] = parts[..] else {
+            return Err(source.error(line, "a change of happiness"));
+        };
+        let score = source.parse::<isize>(score, "a happiness score")?;
+        let happiness_score = match sign {
+            "gain" => score,
+            "lose" => -score,
+            _ => return Err(source.error(sign, "gain or lose")),
         };
         happiness
-            .entry(name)
+            .entry(name.to_string())
             .or_insert_with(HashMap::new)
-            .insert(other_name, happiness_score);
-    });
-    println!(
-        "Total change of happiness: {}",
-        get_max_happiness(&happiness)
-    );
-
-    for name in happiness.clone().keys() {
+            .insert(
+                other_name.trim_end_matches('.').to_string(),
+                happiness_score,
+            );
+    }
+    Ok(happiness)
+}
+
+pub fn part_1(input: &Happiness) -> isize {
+    get_max_happiness(input)
+}
+
+pub fn part_2(input: &Happiness) -> isize {
+    let mut happiness = input.clone();
+    for name in input.keys() {
         happiness
             .entry("me".to_string())
-            .or_insert_with(HashMap::new)
+            .or_default()
             .insert(name.clone(), 0);
         happiness.get_mut(name).unwrap().insert("me".to_string(), 0);
     }
+    get_max_happiness(&happiness)
+}
 
-    println!(
-        "Total change of happiness with me included: {}",
-        get_max_happiness(&happiness)
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"Alice would gain 54 happiness units by sitting next to Bob.
+Alice would lose 79 happiness units by sitting next to Carol.
+Alice would lose 2 happiness units by sitting next to David.
+Bob would gain 83 happiness units by sitting next to Alice.
+Bob would lose 7 happiness units by sitting next to Carol.
+Bob would lose 63 happiness units by sitting next to David.
+Carol would lose 62 happiness units by sitting next to Alice.
+Carol would gain 60 happiness units by sitting next to Bob.
+Carol would gain 55 happiness units by sitting next to David.
+David would gain 46 happiness units by sitting next to Alice.
+David would lose 7 happiness units by sitting next to Bob.
+David would gain 41 happiness units by sitting next to Carol.
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 330),
+    }
 }
@@ -1,19 +1,18 @@
 use std::str::FromStr;
 
-struct Reindeer {
+use aoc_utils::prelude::*;
+
+pub struct Reindeer {
     speed: usize,
     fly_time: usize,
     rest_time: usize,
 }
 
 impl FromStr for Reindeer {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
-        let speed = parts.nth(3).unwrap().parse().unwrap();
-        let fly_time = parts.nth(2).unwrap().parse().unwrap();
-        let rest_time = parts.nth(6).unwrap().parse().unwrap();
+        let [speed, fly_time, rest_time] = Source::new(s).integers(s)?;
         Ok(Reindeer {
             speed,
             fly_time,
@@ -45,29 +44,19 @@ fn get_points(reindeers: &[Reindeer], time: usize) -> Vec<usize> {
     points
 }
 
-pub fn solution() {
-    let input = include_str!("../data/day14.txt");
-    println!(
-        "Distance after 2503 seconds: {}",
-        input
-            .lines()
-            .map(|line| line.parse::<Reindeer>().unwrap())
-            .map(|reindeer| reindeer.distance(2503))
-            .max()
-            .unwrap()
-    );
+pub fn generator(input: &str) -> Result<Vec<Reindeer>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|line| source.parse_as(line)).collect()
+}
 
-    println!(
-        "Points after 2503 seconds: {}",
-        get_points(
-            &input
-                .lines()
-                .map(|line| line.parse::<Reindeer>().unwrap())
-                .collect::<Vec<_>>(),
-            2503
-        )
+pub fn part_1(input: &[Reindeer]) -> usize {
+    input
         .iter()
+        .map(|reindeer| reindeer.distance(2503))
         .max()
         .unwrap()
-    );
+}
+
+pub fn part_2(input: &[Reindeer]) -> usize {
+    *get_points(input, 2503).iter().max().unwrap()
 }
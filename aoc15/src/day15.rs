@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug)]
-struct Ingridient {
+pub struct Ingridient {
     capacity: i32,
     durability: i32,
     flavor: i32,
@@ -10,15 +12,10 @@ struct Ingridient {
 }
 
 impl FromStr for Ingridient {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
-        let capacity = parts.nth(2).unwrap().trim_end_matches(',').parse().unwrap();
-        let durability = parts.nth(1).unwrap().trim_end_matches(',').parse().unwrap();
-        let flavor = parts.nth(1).unwrap().trim_end_matches(',').parse().unwrap();
-        let texture = parts.nth(1).unwrap().trim_end_matches(',').parse().unwrap();
-        let calories = parts.nth(1).unwrap().parse().unwrap();
+        let [capacity, durability, flavor, texture, calories] = Source::new(s).integers(s)?;
         Ok(Ingridient {
             capacity,
             durability,
@@ -73,17 +70,29 @@ fn get_best_total_score(
     best_score
 }
 
-pub fn solution() {
-    let ingridients = include_str!("../data/day15.txt")
-        .lines()
-        .map(|line| line.parse::<Ingridient>().unwrap())
-        .collect::<Vec<_>>();
-    println!(
-        "Total score of highest-scoring cookie: {}",
-        get_best_total_score(&ingridients, 100, None)
-    );
-    println!(
-        "Total score of the highest-scoring cookie with 500 calories: {}",
-        get_best_total_score(&ingridients, 100, Some(500))
-    );
+pub fn generator(input: &str) -> Result<Vec<Ingridient>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|line| source.parse_as(line)).collect()
+}
+
+pub fn part_1(input: &[Ingridient]) -> u32 {
+    get_best_total_score(input, 100, None)
+}
+
+pub fn part_2(input: &[Ingridient]) -> u32 {
+    get_best_total_score(input, 100, Some(500))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 62842880, part_2 = 57600000),
+    }
 }
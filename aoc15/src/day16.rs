@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug)]
-struct Sue {
+pub struct Sue {
     children: Option<u32>,
     cats: Option<u32>,
     samoyeds: Option<u32>,
@@ -14,38 +16,30 @@ struct Sue {
     perfumes: Option<u32>,
 }
 
-fn get_number_from(item: &str, s: &str) -> Option<u32> {
-    if s.contains(item) {
-        let mut parts = s.split(item);
-        parts.next();
-        let number = parts
-            .next()
-            .unwrap()
-            .trim_start_matches(": ")
-            .split(',')
-            .next()
-            .unwrap()
-            .parse()
-            .unwrap();
-        Some(number)
-    } else {
-        None
+fn get_number_from(source: &Source, item: &str, s: &str) -> Result<Option<u32>, ParseError> {
+    match s.split_once(item) {
+        Some((_, rest)) => {
+            let number = rest.trim_start_matches(": ").split(',').next().unwrap();
+            source.parse(number, "a number").map(Some)
+        }
+        None => Ok(None),
     }
 }
 impl FromStr for Sue {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let children = get_number_from("children", s);
-        let cats = get_number_from("cats", s);
-        let samoyeds = get_number_from("samoyeds", s);
-        let pomeranians = get_number_from("pomeranians", s);
-        let akitas = get_number_from("akitas", s);
-        let vizslas = get_number_from("vizslas", s);
-        let goldfish = get_number_from("goldfish", s);
-        let trees = get_number_from("trees", s);
-        let cars = get_number_from("cars", s);
-        let perfumes = get_number_from("perfumes", s);
+        let source = Source::new(s);
+        let children = get_number_from(&source, "children", s)?;
+        let cats = get_number_from(&source, "cats", s)?;
+        let samoyeds = get_number_from(&source, "samoyeds", s)?;
+        let pomeranians = get_number_from(&source, "pomeranians", s)?;
+        let akitas = get_number_from(&source, "akitas", s)?;
+        let vizslas = get_number_from(&source, "vizslas", s)?;
+        let goldfish = get_number_from(&source, "goldfish", s)?;
+        let trees = get_number_from(&source, "trees", s)?;
+        let cars = get_number_from(&source, "cars", s)?;
+        let perfumes = get_number_from(&source, "perfumes", s)?;
         Ok(Sue {
             children,
             cats,
@@ -107,24 +101,25 @@ impl Default for Sue {
     }
 }
 
-pub fn solution() {
-    let sues = include_str!("../data/day16.txt")
-        .lines()
-        .map(|line| line.parse::<Sue>().unwrap())
-        .collect::<Vec<_>>();
+pub fn generator(input: &str) -> Result<Vec<Sue>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|line| source.parse_as(line)).collect()
+}
+
+pub fn part_1(input: &[Sue]) -> usize {
+    let the_sue = Sue::default();
+    input
+        .iter()
+        .position(|sue| is_the_real_sue(&the_sue, sue))
+        .unwrap()
+        + 1
+}
+
+pub fn part_2(input: &[Sue]) -> usize {
     let the_sue = Sue::default();
-    println!(
-        "It was Sue number {} that got me the gift",
-        sues.iter()
-            .position(|sue| is_the_real_sue(&the_sue, sue))
-            .unwrap()
-            + 1
-    );
-    println!(
-        "It was really Sue number {} that got me the gift",
-        sues.iter()
-            .position(|sue| is_the_real_real_sue(&the_sue, sue))
-            .unwrap()
-            + 1
-    );
+    input
+        .iter()
+        .position(|sue| is_the_real_real_sue(&the_sue, sue))
+        .unwrap()
+        + 1
 }
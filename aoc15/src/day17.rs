@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+use aoc_utils::prelude::*;
+
 fn get_combinations(tot_eggnod: u16, containers: &[u16]) -> Vec<Vec<u16>> {
     let mut combinations = Vec::new();
     for i in 0..containers.len() {
@@ -19,20 +21,24 @@ fn get_combinations(tot_eggnod: u16, containers: &[u16]) -> Vec<Vec<u16>> {
     combinations
 }
 
-pub fn solution() {
-    let containers = include_str!("../data/day17.txt")
+pub fn generator(input: &str) -> Result<Vec<u16>, ParseError> {
+    let source = Source::new(input);
+    input
         .trim()
         .lines()
-        .map(|line| line.parse().unwrap())
-        .collect::<Vec<_>>();
-    let combinations = get_combinations(150, &containers);
-    println!("There are {} different combinations", combinations.len());
+        .map(|line| source.parse(line, "a container size"))
+        .collect()
+}
+
+pub fn part_1(input: &[u16]) -> usize {
+    get_combinations(150, input).len()
+}
+
+pub fn part_2(input: &[u16]) -> usize {
+    let combinations = get_combinations(150, input);
     let min_containers = combinations.iter().map(|v| v.len()).min().unwrap();
-    println!(
-        "We can fill the minimum amount of containers in {} ways",
-        combinations
-            .iter()
-            .filter(|v| v.len() == min_containers)
-            .count()
-    );
+    combinations
+        .iter()
+        .filter(|v| v.len() == min_containers)
+        .count()
 }
@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 fn neighbours_on(lights: &Vec<Vec<bool>>, (i, j): (usize, usize)) -> u8 {
     let mut count = 0;
     (-1..=1).for_each(|di| {
@@ -34,23 +36,30 @@ fn update_lights(lights: &mut [Vec<bool>], corners_on: bool) {
     });
 }
 
-pub fn solution() {
-    let mut lights = include_str!("../data/day18.txt")
-        .lines()
-        .map(|line| line.chars().map(|c| matches!(c, '#')).collect())
-        .collect::<Vec<Vec<_>>>();
-    let mut lights2 = lights.clone();
+pub fn generator(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "'#' or '.'",
+    )
+}
+
+pub fn part_1(input: &[Vec<bool>]) -> usize {
+    let mut lights = input.to_vec();
     (0..100).for_each(|_| update_lights(&mut lights, false));
-    println!("After 100 steps {} lights are on", lights.iter().flatten().filter(|x| **x).count());
+    lights.iter().flatten().filter(|x| **x).count()
+}
 
-    // Part 2
-    let (w, h) = (lights2[0].len(), lights2.len());
+pub fn part_2(input: &[Vec<bool>]) -> usize {
+    let mut lights = input.to_vec();
+    let (w, h) = (lights[0].len(), lights.len());
     for (i, j) in [(0, 0), (0, w - 1), (h - 1, 0), (h - 1, w - 1)].iter() {
-        lights2[*i][*j] = true
+        lights[*i][*j] = true
     }
-    (0..100).for_each(|_| update_lights(&mut lights2, true));
-    println!(
-        "With all corners always on, {} lights are on after 100 steps",
-        lights2.iter().flatten().filter(|x| **x).count()
-    );
+    (0..100).for_each(|_| update_lights(&mut lights, true));
+    lights.iter().flatten().filter(|x| **x).count()
 }
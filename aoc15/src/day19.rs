@@ -1,15 +1,8 @@
 use std::collections::HashSet;
 
-fn parse_input(input: &str) -> (Vec<(&str, &str)>, &str) {
-    let (part, molecule) = input.split_once("\n\n").unwrap();
-    let replacement_rules = part
-        .lines()
-        .map(|line| {
-            let (from, to) = line.split_once(" => ").unwrap();
-            (from, to)
-        }).collect();
-    (replacement_rules, molecule.trim_end())
-}
+use aoc_utils::prelude::*;
+
+type Rules<'a> = (Vec<(&'a str, &'a str)>, &'a str);
 
 fn get_all_possible_molecules(
     molecule: &str,
@@ -42,16 +35,49 @@ fn backtrack(molecule: &str, replacement_rules: &[(&str, &str)], target: &str) -
     None
 }
 
-pub fn solution() {
-    let input = include_str!("../data/day19.txt");
-    let (replacement_rules, molecule) = parse_input(input);
-
-    println!(
-        "We can create {} distinct molecules",
-        get_all_possible_molecules(molecule, &replacement_rules).len()
-    );
-    println!(
-        "The fewest numbers of steps we can take are {}",
-        backtrack(molecule, &replacement_rules, "e").unwrap()
-    );
+pub fn generator(input: &str) -> Result<Rules<'_>, ParseError> {
+    let source = Source::new(input);
+    let (part, molecule) = source.split_once(input, "\n\n")?;
+    let replacement_rules = part
+        .lines()
+        .map(|line| source.split_once(line, " => "))
+        .collect::<Result<_, _>>()?;
+    Ok((replacement_rules, molecule.trim_end()))
+}
+
+pub fn part_1((replacement_rules, molecule): &Rules) -> usize {
+    get_all_possible_molecules(molecule, replacement_rules).len()
+}
+
+pub fn part_2((replacement_rules, molecule): &Rules) -> usize {
+    backtrack(molecule, replacement_rules, "e").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"e => H
+e => O
+H => HO
+H => OH
+O => HH
+
+HOH
+"#;
+
+    const INPUT_2: &str = r#"e => H
+e => O
+H => HO
+H => OH
+O => HH
+
+HOHOHO
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 4, part_2 = 3),
+        example_2: (INPUT_2, part_1 = 7, part_2 = 6),
+    }
 }
@@ -1,22 +1,39 @@
-pub fn solution() {
-    let input = include_str!("../data/day2.txt");
-    let (part1, part2) = input.lines().fold((0, 0), |(acc_paper, acc_string), line| {
-        let mut v = line
-            .split('x')
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+use aoc_utils::prelude::*;
 
-        v.sort_unstable();
+pub fn generator(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            let mut v: [usize; 3] = source.integers(line)?;
+            v.sort_unstable();
+            Ok(v.to_vec())
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[Vec<usize>]) -> usize {
+    input.iter().fold(0, |acc_paper, v| {
         let sides = [v[0] * v[1], v[1] * v[2], v[2] * v[0]];
-        (
-            acc_paper
-                + sides.iter().fold(0, |acc, &side| acc + 2 * side)
-                + sides.iter().min().unwrap(),
-            acc_string
-                + v.iter().take(2).fold(0, |acc, &side| acc + 2 * side)
-                + v.iter().product::<usize>(),
-        )
-    });
-    println!("The elves should order {part1} square feet of wrapping paper");
-    println!("The elves should order {part2} feet of ribbon");
+        acc_paper + sides.iter().fold(0, |acc, &side| acc + 2 * side) + sides.iter().min().unwrap()
+    })
+}
+
+pub fn part_2(input: &[Vec<usize>]) -> usize {
+    input.iter().fold(0, |acc_string, v| {
+        acc_string
+            + v.iter().take(2).fold(0, |acc, &side| acc + 2 * side)
+            + v.iter().product::<usize>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        cube: ("2x3x4", part_1 = 58, part_2 = 34),
+        long_box: ("1x1x10", part_1 = 43, part_2 = 14),
+    }
 }
@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 fn n_presents_slow(house_number: u32, start: u32, factor: u32) -> u32 {
     (1..=(house_number as f32).sqrt() as u32)
         .filter(|&i| house_number % i == 0)
@@ -28,36 +30,38 @@ fn get_min_house_fast(min_num: u32, max_visits: Option<u32>, factor: u32) -> Opt
     v.iter().position(|num| *num >= min_num)
 }
 
-pub fn solution() {
-    let num = include_str!("../data/day20.txt")
-        .trim()
-        .parse::<u32>()
-        .unwrap();
+fn get_min_house_slow(min_num: u32, start: impl Fn(u32) -> u32, factor: u32) -> u32 {
+    (1..)
+        .find(|&i| n_presents_slow(i, start(i), factor) >= min_num)
+        .unwrap()
+}
 
-    let mut i = 1;
-    loop {
-        if n_presents_slow(i, 1, 10) >= num {
-            println!("Slow: Lowest house number (part 1): {i}");
-            break;
-        }
-        i += 1;
-    }
+pub fn generator(input: &str) -> Result<u32, ParseError> {
+    Source::new(input).parse(input.trim(), "a number of presents")
+}
 
-    i = 1;
-    loop {
-        if n_presents_slow(i, i / 50 + 1, 11) >= num {
-            println!("Slow: Lowest house number (part 2): {i}");
-            break;
-        }
-        i += 1;
-    }
+pub fn part_1(input: &u32) -> usize {
+    get_min_house_fast(*input, None, 10).unwrap()
+}
 
-    println!(
-        "Fast: Lowest house number (part 1): {}",
-        get_min_house_fast(num, None, 10).unwrap()
-    );
-    println!(
-        "Fast: Lowest house number (part 2): {}",
-        get_min_house_fast(num, Some(50), 11).unwrap()
-    );
+pub fn part_1_slow(input: &u32) -> u32 {
+    get_min_house_slow(*input, |_| 1, 10)
+}
+
+pub fn part_2(input: &u32) -> usize {
+    get_min_house_fast(*input, Some(50), 11).unwrap()
+}
+
+pub fn part_2_slow(input: &u32) -> u32 {
+    get_min_house_slow(*input, |i| i / 50 + 1, 11)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: ("70", part_1 = 4, part_1_slow = 4),
+    }
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug)]
 struct ShopItem {
     cost: u16,
@@ -18,38 +20,29 @@ impl ShopItem {
 }
 
 #[derive(Debug)]
-struct Player {
+pub struct Player {
     hit_points: u16,
     damage: u16,
     armor: u16,
 }
 
 impl FromStr for Player {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
         let mut v = s.lines();
-        let hit_points = v
-            .next()
-            .unwrap()
-            .strip_prefix("Hit Points: ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let damage = v
-            .next()
-            .unwrap()
-            .strip_prefix("Damage: ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let armor = v
-            .next()
-            .unwrap()
-            .strip_prefix("Armor: ")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let mut stat = |name: &str| {
+            let expected = format!("{name:?}");
+            let line = v.next().ok_or_else(|| source.error_after(s, &expected))?;
+            let value = line
+                .strip_prefix(name)
+                .ok_or_else(|| source.error(line, &expected))?;
+            source.parse(value, "a number")
+        };
+        let hit_points = stat("Hit Points: ")?;
+        let damage = stat("Damage: ")?;
+        let armor = stat("Armor: ")?;
         Ok(Player {
             hit_points,
             armor,
@@ -83,7 +76,8 @@ fn simulate_fight(player: &mut Player, boss: &mut Player) -> bool {
         }
     }
 }
-pub fn solution() {
+
+fn possible_purchases() -> Vec<ShopItem> {
     let weapons = vec![
         ShopItem::new(8, 4, 0),
         ShopItem::new(10, 5, 0),
@@ -125,45 +119,32 @@ pub fn solution() {
         });
     });
     possible_purchase.sort_by(|a, b| a.cost.cmp(&b.cost));
+    possible_purchase
+}
 
-    let mut player_stats = possible_purchase.iter();
-    let mut player = Player::new(100, 0, 0);
-    let mut boss = include_str!("../data/day21.txt")
-        .parse::<Player>()
-        .unwrap();
-    let boss_hit_points = boss.hit_points;
-    let cost = loop {
-        let ShopItem {
-            cost,
-            damage,
-            armor,
-        } = player_stats.next().unwrap();
-        player.hit_points = 100;
-        boss.hit_points = boss_hit_points;
-        player.damage = *damage;
-        player.armor = *armor;
-        let won = simulate_fight(&mut player, &mut boss);
-        if won {
-            break cost;
-        }
-    };
-    println!("I need to spend at least {cost} gold to win the fight");
+fn wins_with(purchase: &ShopItem, boss: &Player) -> bool {
+    let mut player = Player::new(100, purchase.damage, purchase.armor);
+    let mut boss = Player::new(boss.hit_points, boss.damage, boss.armor);
+    simulate_fight(&mut player, &mut boss)
+}
 
-    let mut player_stats = possible_purchase.iter().rev();
-    let cost = loop {
-        let ShopItem {
-            cost,
-            damage,
-            armor,
-        } = player_stats.next().unwrap();
-        player.hit_points = 100;
-        boss.hit_points = boss_hit_points;
-        player.damage = *damage;
-        player.armor = *armor;
-        let won = simulate_fight(&mut player, &mut boss);
-        if !won {
-            break cost;
-        }
-    };
-    println!("I could spend as much as {cost} gold and still lose the fight");
+pub fn generator(input: &str) -> Result<Player, ParseError> {
+    input.parse::<Player>()
+}
+
+pub fn part_1(input: &Player) -> u16 {
+    possible_purchases()
+        .iter()
+        .find(|purchase| wins_with(purchase, input))
+        .unwrap()
+        .cost
+}
+
+pub fn part_2(input: &Player) -> u16 {
+    possible_purchases()
+        .iter()
+        .rev()
+        .find(|purchase| !wins_with(purchase, input))
+        .unwrap()
+        .cost
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Clone, Copy)]
 struct LastingEffect {
     name: &'static str,
@@ -109,30 +111,29 @@ struct Player {
 }
 
 #[derive(Debug, Clone)]
-struct Boss {
+pub struct Boss {
     hp: u16,
     damage: u16,
 }
 
 impl FromStr for Boss {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
         let mut v = s.lines();
-        let hp = v
-            .next()
-            .unwrap()
-            .strip_prefix("Hit Points: ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let damage = v
-            .next()
-            .unwrap()
-            .strip_prefix("Damage: ")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let mut stat = |name: &str| {
+            let expected = format!("{name:?}");
+            let line = v
+                .next()
+                .ok_or_else(|| source.error_after(s, &expected))?;
+            let value = line
+                .strip_prefix(name)
+                .ok_or_else(|| source.error(line, &expected))?;
+            source.parse(value, "a number")
+        };
+        let hp = stat("Hit Points: ")?;
+        let damage = stat("Damage: ")?;
         Ok(Boss {
             hp,
             damage,
@@ -304,12 +305,14 @@ fn dijkstras(boss: &Boss, player: &Player, spell_book: &SpellBook, hard_mode: bo
     None
 }
 
-pub fn solution() {
-    let boss = include_str!("../data/day22.txt")
-        .parse::<Boss>()
-        .unwrap();
-    let player = Player::default();
-    let spells = SpellBook::default();
-    println!("I need to spend at least {} mana to win", dijkstras(&boss, &player, &spells, false).unwrap());
-    println!("I need to spend at least {} mana in hard mode to win", dijkstras(&boss, &player, &spells, true).unwrap());
+pub fn generator(input: &str) -> Result<Boss, ParseError> {
+    input.parse::<Boss>()
+}
+
+pub fn part_1(input: &Boss) -> u16 {
+    dijkstras(input, &Player::default(), &SpellBook::default(), false).unwrap()
+}
+
+pub fn part_2(input: &Boss) -> u16 {
+    dijkstras(input, &Player::default(), &SpellBook::default(), true).unwrap()
 }
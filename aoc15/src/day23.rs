@@ -1,113 +1,255 @@
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
-enum Instruction {
-    Hlf(Reg),
-    Tpl(Reg),
-    Inc(Reg),
-    Jmp(i32),
-    Jie(Reg, i32),
-    Jio(Reg, i32),
+use aoc_utils::prelude::*;
+use aoc_utils::vm::{Flow, InstructionSet, Machine, Register, Stop};
+
+/// Prints every executed instruction to stderr.
+static TRACE: Param<bool> = Param::new("trace", false);
+/// Steps after which a program is assumed never to halt.
+const STEP_LIMIT: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Hlf(Register),
+    Tpl(Register),
+    Inc(Register),
+    Jmp(isize),
+    Jie(Register, isize),
+    Jio(Register, isize),
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Reg {
-    A,
-    B,
+impl InstructionSet for Instruction {
+    type Value = u32;
+
+    const REGISTERS: &'static [&'static str] = &["a", "b"];
+
+    fn execute(&self, registers: &mut [u32]) -> Flow {
+        let jump_if = |condition: bool, offset| {
+            if condition {
+                Flow::Jump(offset)
+            } else {
+                Flow::Next
+            }
+        };
+        match *self {
+            Instruction::Hlf(r) => registers[r.0] /= 2,
+            Instruction::Tpl(r) => registers[r.0] *= 3,
+            Instruction::Inc(r) => registers[r.0] += 1,
+            Instruction::Jmp(offset) => return Flow::Jump(offset),
+            Instruction::Jie(r, offset) => return jump_if(registers[r.0] % 2 == 0, offset),
+            Instruction::Jio(r, offset) => return jump_if(registers[r.0] == 1, offset),
+        }
+        Flow::Next
+    }
 }
 
 impl FromStr for Instruction {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (inst, rest) = s.split_once(' ').unwrap();
-        let reg = if rest.contains('a') { Reg::A } else { Reg::B };
+        let source = Source::new(s);
+        let (inst, rest) = source.split_once(s, " ")?;
+        let reg = |reg: &str| Register::parse::<Instruction>(&source, reg.trim());
+        let offset = |offset: &str| source.parse::<isize>(offset.trim(), "an offset");
 
         match inst {
-            "hlf" => Ok(Instruction::Hlf(reg)),
-            "tpl" => Ok(Instruction::Tpl(reg)),
-            "inc" => Ok(Instruction::Inc(reg)),
-            "jmp" => Ok(Instruction::Jmp(rest.trim().parse().unwrap())),
+            "hlf" => Ok(Instruction::Hlf(reg(rest)?)),
+            "tpl" => Ok(Instruction::Tpl(reg(rest)?)),
+            "inc" => Ok(Instruction::Inc(reg(rest)?)),
+            "jmp" => Ok(Instruction::Jmp(offset(rest)?)),
             "jie" => {
-                let (_, offset) = rest.split_once(", ").unwrap();
-                Ok(Instruction::Jie(reg, offset.trim().parse().unwrap()))
+                let (r, o) = source.split_once(rest, ", ")?;
+                Ok(Instruction::Jie(reg(r)?, offset(o)?))
             }
             "jio" => {
-                let (_, offset) = rest.split_once(", ").unwrap();
-                Ok(Instruction::Jio(reg, offset.trim().parse().unwrap()))
+                let (r, o) = source.split_once(rest, ", ")?;
+                Ok(Instruction::Jio(reg(r)?, offset(o)?))
             }
-            _ => unreachable!(),
+            _ => Err(source.error(inst, "an instruction")),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |r: &Register| Instruction::REGISTERS[r.0];
+        match self {
+            Instruction::Hlf(r) => write!(f, "hlf {}", name(r)),
+            Instruction::Tpl(r) => write!(f, "tpl {}", name(r)),
+            Instruction::Inc(r) => write!(f, "inc {}", name(r)),
+            Instruction::Jmp(o) => write!(f, "jmp {o:+}"),
+            Instruction::Jie(r, o) => write!(f, "jie {}, {o:+}", name(r)),
+            Instruction::Jio(r, o) => write!(f, "jio {}, {o:+}", name(r)),
         }
     }
 }
 
 fn run(instructions: &[Instruction], start: u32) -> u32 {
-    let mut a = start;
-    let mut b = 0;
-    let mut idx = 0i32;
-    while (idx as usize) < instructions.len() {
-        match instructions[idx as usize] {
-            Instruction::Hlf(reg) => match reg {
-                Reg::A => a /= 2,
-                Reg::B => b /= 2,
-            },
-            Instruction::Tpl(reg) => match reg {
-                Reg::A => a *= 3,
-                Reg::B => b *= 3,
-            },
-            Instruction::Inc(reg) => match reg {
-                Reg::A => a += 1,
-                Reg::B => b += 1,
-            },
-            Instruction::Jmp(i) => {
-                idx += i;
-                continue;
-            }
-            Instruction::Jie(reg, i) => match reg {
-                Reg::A => {
-                    if a % 2 == 0 {
-                        idx += i;
-                        continue;
-                    };
-                }
-                Reg::B => {
-                    if b % 2 == 0 {
-                        idx += i;
-                        continue;
-                    };
+    let mut machine = Machine::new(instructions.to_vec())
+        .with_loop_detection()
+        .with_step_limit(STEP_LIMIT);
+    if TRACE.get() {
+        machine = machine.with_trace();
+    }
+    machine.set("a", start);
+    let stop = machine.run();
+    for step in machine.trace() {
+        eprintln!("{step}");
+    }
+    let (pc, steps) = (machine.pc().unwrap_or_default(), machine.steps());
+    match stop {
+        Stop::Halted => machine.get("b"),
+        Stop::Loop { first_seen } => panic!(
+            "The program loops at pc {pc} after {steps} steps, back in the state after {first_seen} steps"
+        ),
+        _ => panic!("The program did not halt, stopping at pc {pc} after {steps} steps"),
+    }
+}
+
+pub fn generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|l| source.parse_as(l)).collect()
+}
+
+pub fn part_1(input: &[Instruction]) -> u32 {
+    run(input, 0)
+}
+
+pub fn part_2(input: &[Instruction]) -> u32 {
+    run(input, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::testing::{check, Rng};
+
+    const INPUT: &str = r#"inc b
+tpl b
+jio b, +2
+inc b
+jio b, +2
+tpl b
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 12),
+    }
+
+    /// Runs a single instruction on the registers `a` and `b`.
+    fn execute(line: &str, a: u32, b: u32) -> (Flow, [u32; 2]) {
+        let mut registers = [a, b];
+        let flow = line.parse::<Instruction>().unwrap().execute(&mut registers);
+        (flow, registers)
+    }
+
+    #[test]
+    fn opcodes() {
+        assert_eq!(execute("hlf a", 7, 4), (Flow::Next, [3, 4]));
+        assert_eq!(execute("hlf b", 7, 4), (Flow::Next, [7, 2]));
+        assert_eq!(execute("tpl a", 7, 4), (Flow::Next, [21, 4]));
+        assert_eq!(execute("tpl b", 7, 4), (Flow::Next, [7, 12]));
+        assert_eq!(execute("inc a", 7, 4), (Flow::Next, [8, 4]));
+        assert_eq!(execute("inc b", 7, 4), (Flow::Next, [7, 5]));
+        assert_eq!(execute("jmp -3", 7, 4), (Flow::Jump(-3), [7, 4]));
+        assert_eq!(execute("jmp +2", 7, 4), (Flow::Jump(2), [7, 4]));
+        assert_eq!(execute("jie a, +2", 7, 4), (Flow::Next, [7, 4]));
+        assert_eq!(execute("jie b, +2", 7, 4), (Flow::Jump(2), [7, 4]));
+        assert_eq!(execute("jio a, -1", 1, 3), (Flow::Jump(-1), [1, 3]));
+        assert_eq!(execute("jio b, -1", 1, 3), (Flow::Next, [1, 3]));
+        assert_eq!(execute("jio b, -1", 0, 1), (Flow::Jump(-1), [0, 1]));
+    }
+
+    #[test]
+    fn displays_instructions() {
+        for line in INPUT.lines().chain(["hlf a", "jmp -7", "jie a, +4"]) {
+            assert_eq!(line.parse::<Instruction>().unwrap().to_string(), line);
+        }
+        assert!("inc c".parse::<Instruction>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "loops at pc 1 after 2 steps")]
+    fn detects_loops() {
+        run(&generator("inc a\njmp +0\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn traces_the_example() {
+        let program = generator(INPUT).unwrap();
+        let mut machine = Machine::new(program).with_trace();
+        assert_eq!(machine.run(), Stop::Halted);
+        let pcs = machine.trace().iter().map(|s| s.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            machine.trace()[2].to_string(),
+            "     3     2  jio b, +2        a=0 b=3"
+        );
+    }
+
+    /// Runs the program with the registers in an array, jumping with the
+    /// offset of every instruction.
+    fn interpret(instructions: &[Instruction], start: u32) -> u32 {
+        let mut registers = [start, 0];
+        let mut pc = 0;
+        while let Some(&instruction) = instructions.get(pc as usize) {
+            let (reg, offset) = match instruction {
+                Instruction::Hlf(reg) | Instruction::Tpl(reg) | Instruction::Inc(reg) => (reg, 1),
+                Instruction::Jmp(offset) => (Register(0), offset),
+                Instruction::Jie(reg, offset) | Instruction::Jio(reg, offset) => (reg, offset),
+            };
+            let value = &mut registers[reg.0];
+            let jumps = match instruction {
+                Instruction::Hlf(_) => {
+                    *value /= 2;
+                    false
                 }
-            },
-            Instruction::Jio(reg, i) => match reg {
-                Reg::A => {
-                    if a == 1 {
-                        idx += i;
-                        continue;
-                    };
+                Instruction::Tpl(_) => {
+                    *value *= 3;
+                    false
                 }
-                Reg::B => {
-                    if b % 2 == 1 {
-                        idx += i;
-                        continue;
-                    };
+                Instruction::Inc(_) => {
+                    *value += 1;
+                    false
                 }
-            },
+                Instruction::Jmp(_) => true,
+                Instruction::Jie(..) => value.is_multiple_of(2),
+                Instruction::Jio(..) => *value == 1,
+            };
+            pc += if jumps { offset } else { 1 };
         }
-        idx += 1;
+        registers[1]
     }
-    b
-}
 
-pub fn solution() {
-    let instructions = include_str!("../data/day23.txt")
-        .lines()
-        .map(|l| l.parse().unwrap())
-        .collect::<Vec<Instruction>>();
-    println!(
-        "After the instructions, register b has the value {}",
-        run(&instructions, 0)
-    );
-    println!(
-        "After the instructions, when register a starts at 1, register b has the value {}",
-        run(&instructions, 1)
-    );
+    /// A program that stops, only ever jumping forwards.
+    fn program(rng: &mut Rng) -> Vec<Instruction> {
+        rng.vec(1..=12, |rng| {
+            let reg = *rng.choose(&[Register(0), Register(1)]);
+            let offset = rng.range(1..=4) as isize;
+            match rng.range(0..=5) {
+                0 => Instruction::Hlf(reg),
+                1 => Instruction::Tpl(reg),
+                2 => Instruction::Inc(reg),
+                3 => Instruction::Jmp(offset),
+                4 => Instruction::Jie(reg, offset),
+                _ => Instruction::Jio(reg, offset),
+            }
+        })
+    }
+
+    #[test]
+    fn runs_like_an_interpreter() {
+        check(2000, |rng| {
+            let instructions = program(rng);
+            for start in [0, 1] {
+                assert_eq!(
+                    run(&instructions, start),
+                    interpret(&instructions, start),
+                    "{instructions:?} starting with a = {start}"
+                );
+            }
+        });
+    }
 }
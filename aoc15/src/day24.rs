@@ -1,6 +1,8 @@
 use itertools::Itertools;
 
-fn get_lowest_qe(nums: &Vec<u32>, parts: u32) -> Option<u64> {
+use aoc_utils::prelude::*;
+
+fn get_lowest_qe(nums: &[u32], parts: u32) -> Option<u64> {
     let part_sum = nums.iter().sum::<u32>() / parts;
     for i in 1..nums.len() {
         for combination in nums.iter().combinations(i) {
@@ -11,17 +13,31 @@ fn get_lowest_qe(nums: &Vec<u32>, parts: u32) -> Option<u64> {
     }
     None
 }
-pub fn solution() {
-    let input = include_str!("../data/day24.txt")
+
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(input);
+    input
         .lines()
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
-    println!(
-        "The quantum entanglement for the ideal configuration with 3 parts is {}",
-        get_lowest_qe(&input, 3).unwrap()
-    );
-    println!(
-        "The quantum entanglement for the ideal configuration with 4 parts is {}",
-        get_lowest_qe(&input, 4).unwrap()
-    );
+        .map(|x| source.parse(x, "a package weight"))
+        .collect()
+}
+
+pub fn part_1(input: &[u32]) -> u64 {
+    get_lowest_qe(input, 3).unwrap()
+}
+
+pub fn part_2(input: &[u32]) -> u64 {
+    get_lowest_qe(input, 4).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1\n2\n3\n4\n5\n7\n8\n9\n10\n11";
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 99, part_2 = 44),
+    }
 }
@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 fn find_num(row: u64, col: u64) -> u64 {
     // The nth number is given by finding the triangle number for the row and
     // adding the column number. (The triangle number is the sum of the first
@@ -8,29 +10,23 @@ fn find_num(row: u64, col: u64) -> u64 {
     }
     num
 }
-pub fn solution() {
-    let input = include_str!("../data/day25.txt");
-    let row = input
-        .split("row ")
-        .nth(1)
-        .unwrap()
-        .split(',')
-        .next()
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-    let column = input
-        .trim()
-        .split("column ")
-        .nth(1)
-        .unwrap()
-        .split('.')
-        .next()
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-    println!(
-        "The code at row {row}, column {column} is {}",
-        find_num(row, column)
-    );
+
+pub fn generator(input: &str) -> Result<(u64, u64), ParseError> {
+    let [row, column] = Source::new(input).integers(input)?;
+    Ok((row, column))
+}
+
+pub fn part_1(&(row, column): &(u64, u64)) -> u64 {
+    find_num(row, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        first_column: ("Enter the code at row 2, column 1.", part_1 = 31916031),
+        first_row: ("Enter the code at row 1, column 2.", part_1 = 18749137),
+    }
 }
@@ -1,44 +1,60 @@
 use std::collections::HashSet;
 
-pub fn solution() {
-    let input = include_str!("../data/day3.txt");
+use aoc_utils::prelude::*;
+
+/// The moves of the directions, as `(dx, dy)` with north up.
+pub fn generator(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+    let direction = |_, c| match c {
+        '^' => Some((0, 1)),
+        'v' => Some((0, -1)),
+        '>' => Some((1, 0)),
+        '<' => Some((-1, 0)),
+        _ => None,
+    };
+    Ok(Source::new(input)
+        .grid(input.trim(), direction, "^, v, > or <")?
+        .concat())
+}
+
+pub fn part_1(input: &[(i32, i32)]) -> usize {
     let mut seen_houses = HashSet::new();
-    input.chars().fold((0, 0), |(x, y), c| {
+    let end = input.iter().fold((0, 0), |(x, y), (dx, dy)| {
         seen_houses.insert((x, y));
-        match c {
-            '^' => (x, y + 1),
-            'v' => (x, y - 1),
-            '>' => (x + 1, y),
-            '<' => (x - 1, y),
-            _ => (x, y),
-        }
+        (x + dx, y + dy)
     });
-    println!("Santa visits {} houses", seen_houses.len());
+    seen_houses.insert(end);
+    seen_houses.len()
+}
 
+pub fn part_2(input: &[(i32, i32)]) -> usize {
     let mut seen_houses = HashSet::new();
-    input
-        .chars()
-        .enumerate()
-        .fold(((0, 0), (0, 0)), |((xs, ys), (xr, yr)), (i, c)| {
-            if i % 2 == 0 {
-                seen_houses.insert((xs, ys));
-                match c {
-                    '^' => ((xs, ys + 1), (xr, yr)),
-                    'v' => ((xs, ys - 1), (xr, yr)),
-                    '>' => ((xs + 1, ys), (xr, yr)),
-                    '<' => ((xs - 1, ys), (xr, yr)),
-                    _ => ((xs, ys), (xr, yr)),
+    let (santa, robo_santa) =
+        input
+            .iter()
+            .enumerate()
+            .fold(((0, 0), (0, 0)), |((xs, ys), (xr, yr)), (i, (dx, dy))| {
+                if i % 2 == 0 {
+                    seen_houses.insert((xs, ys));
+                    ((xs + dx, ys + dy), (xr, yr))
+                } else {
+                    seen_houses.insert((xr, yr));
+                    ((xs, ys), (xr + dx, yr + dy))
                 }
-            } else {
-                seen_houses.insert((xr, yr));
-                match c {
-                    '^' => ((xs, ys), (xr, yr + 1)),
-                    'v' => ((xs, ys), (xr, yr - 1)),
-                    '>' => ((xs, ys), (xr + 1, yr)),
-                    '<' => ((xs, ys), (xr - 1, yr)),
-                    _ => ((xs, ys), (xr, yr)),
-                }
-            }
-        });
-    println!("Santa and Robo-Santa visits {} houses", seen_houses.len());
+            });
+    seen_houses.insert(santa);
+    seen_houses.insert(robo_santa);
+    seen_houses.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        single_move: (">", part_1 = 2),
+        square: ("^>v<", part_1 = 4, part_2 = 3),
+        back_and_forth: ("^v^v^v^v^v", part_1 = 2, part_2 = 11),
+        split_up: ("^v", part_2 = 3),
+    }
 }
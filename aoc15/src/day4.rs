@@ -1,19 +1,33 @@
+use aoc_utils::prelude::*;
 use md5::{Digest, Md5};
 
-pub fn solution() {
-    let input = include_str!("../data/day4.txt");
-    let mut part1_done = false;
+fn find_hash(secret: &str, is_valid: impl Fn(&[u8]) -> bool) -> usize {
     let mut hasher = Md5::new();
     for i in 0.. {
-        hasher.update(format!("{}{i}", input.trim()));
+        hasher.update(format!("{secret}{i}"));
         let hash = hasher.finalize_reset();
-        if !part1_done && hash[..2] == [0, 0] && &hash[2] & 0xf0 == 0 {
-            part1_done = true;
-            println!("Part 1: {i}");
-        }
-        if hash[..3] == [0, 0, 0] {
-            println!("Part 2: {i}");
-            break;
+        if is_valid(&hash) {
+            return i;
         }
     }
+    unreachable!()
+}
+
+/// The secret key, which must be a single word.
+pub fn generator(input: &str) -> Result<String, ParseError> {
+    let source = Source::new(input);
+    let secret = input.trim();
+    match secret.split_whitespace().collect::<Vec<_>>()[..] {
+        [key] => Ok(key.to_string()),
+        [] => Err(source.error_after(secret, "a secret key")),
+        [_, extra, ..] => Err(source.error(extra, "the end of the secret key")),
+    }
+}
+
+pub fn part_1(input: &str) -> usize {
+    find_hash(input, |hash| hash[..2] == [0, 0] && hash[2] & 0xf0 == 0)
+}
+
+pub fn part_2(input: &str) -> usize {
+    find_hash(input, |hash| hash[..3] == [0, 0, 0])
 }
@@ -1,31 +1,54 @@
 use std::collections::HashMap;
 
-pub fn solution() {
-    let input = include_str!("../data/day5.txt");
-    println!(
-        "Part 1 has {} nice strings",
-        input.lines().fold(0, |acc, line| {
-            let contains_3_vowels = line.matches(|c| "aeiou".contains(c)).count() >= 3;
-            let no_disallowed_chars = !line.contains("xy")
-                && !line.contains("ab")
-                && !line.contains("cd")
-                && !line.contains("pq");
-            let chars = line.chars().collect::<Vec<char>>();
-            let occuring_letter = chars.windows(2).any(|w| w[0] == w[1]);
-            acc + (contains_3_vowels && no_disallowed_chars && occuring_letter) as usize
-        })
-    );
-    println!(
-        "Part 2 has {} nice strings",
-        input.lines().fold(0, |acc, line| {
-            let chars = line.chars().collect::<Vec<char>>();
-            let mut seen_pairs = HashMap::new();
-            let occuring_pair = chars.windows(2).enumerate().any(|(i, w)| {
-                let seen = seen_pairs.entry(w).or_insert(i);
-                *seen != i && *seen + 2 <= i
-            });
-            let occuring_letter = chars.windows(3).any(|w| w[0] == w[2]);
-            acc + (occuring_pair && occuring_letter) as usize
-        })
-    );
+use aoc_utils::prelude::*;
+
+/// The strings on Santa's list, which must be lowercase letters.
+pub fn generator(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(input);
+    let letter = |_, c: char| c.is_ascii_lowercase().then_some(());
+    source.grid(input, letter, "a lowercase letter")?;
+    Ok(input.lines().collect())
+}
+
+pub fn part_1(input: &[&str]) -> usize {
+    input.iter().fold(0, |acc, line| {
+        let contains_3_vowels = line.matches(|c| "aeiou".contains(c)).count() >= 3;
+        let no_disallowed_chars = !line.contains("xy")
+            && !line.contains("ab")
+            && !line.contains("cd")
+            && !line.contains("pq");
+        let chars = line.chars().collect::<Vec<char>>();
+        let occuring_letter = chars.windows(2).any(|w| w[0] == w[1]);
+        acc + (contains_3_vowels && no_disallowed_chars && occuring_letter) as usize
+    })
+}
+
+pub fn part_2(input: &[&str]) -> usize {
+    input.iter().fold(0, |acc, line| {
+        let chars = line.chars().collect::<Vec<char>>();
+        let mut seen_pairs = HashMap::new();
+        let occuring_pair = chars.windows(2).enumerate().any(|(i, w)| {
+            let seen = seen_pairs.entry(w).or_insert(i);
+            *seen != i && *seen + 2 <= i
+        });
+        let occuring_letter = chars.windows(3).any(|w| w[0] == w[2]);
+        acc + (occuring_pair && occuring_letter) as usize
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (
+            "ugknbfddgicrmopn\naaa\njchzalrnumimnmhp\nhaegwjzuvuyypxyu\ndvszwmarrgswjxmb",
+            part_1 = 2,
+        ),
+        example_2: (
+            "qjhvhtzxzqqjkmpb\nxxyxx\nuurcxstgmygtbstg\nieodomkazucvgmuy",
+            part_2 = 2,
+        ),
+    }
 }
@@ -1,40 +1,87 @@
-pub fn solution() {
-    let mut grid1 = vec![vec![false; 1000]; 1000];
-    let mut grid2 = vec![vec![0usize; 1000]; 1000];
-    let input = include_str!("../data/day6.txt");
-    input.lines().for_each(|line| {
-        let (part, p2) = line.split_once(" through ").unwrap();
-        let (action, p1) = part.rsplit_once(' ').unwrap();
-        let (x1, y1) = p1.split_once(',').unwrap();
-        let (x1, y1) = (x1.parse::<usize>().unwrap(), y1.parse::<usize>().unwrap());
-        let (x2, y2) = p2.split_once(',').unwrap();
-        let (x2, y2) = (x2.parse::<usize>().unwrap(), y2.parse::<usize>().unwrap());
+use aoc_utils::prelude::*;
+
+pub struct Instruction<'a> {
+    action: &'a str,
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+pub fn generator(input: &str) -> Result<Vec<Instruction<'_>>, ParseError> {
+    let source = Source::new(input);
+    let corner = |corner| {
+        let (x, y) = source.split_once(corner, ",")?;
+        Ok::<_, ParseError>((
+            source.parse(x, "a coordinate")?,
+            source.parse(y, "a coordinate")?,
+        ))
+    };
+    input
+        .lines()
+        .map(|line| {
+            let (part, p2) = source.split_once(line, " through ")?;
+            let (action, p1) = part
+                .rsplit_once(' ')
+                .ok_or_else(|| source.error(part, "an action and a corner"))?;
+            if !matches!(action, "turn on" | "turn off" | "toggle") {
+                return Err(source.error(action, "turn on, turn off or toggle"));
+            }
+            Ok(Instruction {
+                action,
+                from: corner(p1)?,
+                to: corner(p2)?,
+            })
+        })
+        .collect()
+}
+
+fn apply<T: Copy>(
+    instructions: &[Instruction],
+    initial: T,
+    update: impl Fn(&str, T) -> T,
+) -> Vec<Vec<T>> {
+    let mut grid = vec![vec![initial; 1000]; 1000];
+    instructions.iter().for_each(|instruction| {
+        let (x1, y1) = instruction.from;
+        let (x2, y2) = instruction.to;
         (x1..=x2).for_each(|x| {
-            (y1..=y2).for_each(|y| match action {
-                "turn on" => {
-                    grid1[x][y] = true;
-                    grid2[x][y] += 1;
-                }
-                "turn off" => {
-                    grid1[x][y] = false;
-                    grid2[x][y] = (grid2[x][y]).saturating_sub(1)
-                }
-                "toggle" => {
-                    grid1[x][y] = !grid1[x][y];
-                    grid2[x][y] += 2;
-                }
-                _ => (),
-            });
+            (y1..=y2).for_each(|y| grid[x][y] = update(instruction.action, grid[x][y]));
         });
     });
+    grid
+}
+
+pub fn part_1(input: &[Instruction]) -> usize {
+    let grid = apply(input, false, |action, light| match action {
+        "turn on" => true,
+        "turn off" => false,
+        "toggle" => !light,
+        _ => light,
+    });
+    grid.iter().flatten().map(|x| *x as usize).sum::<usize>()
+}
+
+pub fn part_2(input: &[Instruction]) -> usize {
+    let grid = apply(input, 0usize, |action, brightness| match action {
+        "turn on" => brightness + 1,
+        "turn off" => brightness.saturating_sub(1),
+        "toggle" => brightness + 2,
+        _ => brightness,
+    });
+    grid.iter().flatten().sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!(
-        "There are {} lights lit",
-        grid1.iter().flatten().map(|x| *x as usize).sum::<usize>()
-    );
+    const INPUT: &str = r#"turn on 0,0 through 999,999
+toggle 0,0 through 999,0
+turn off 499,499 through 500,500
+"#;
 
-    println!(
-        "The total brightness is: {}",
-        grid2.iter().flatten().sum::<usize>()
-    );
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 998996, part_2 = 1001996),
+        single_light: ("turn on 0,0 through 0,0", part_2 = 1),
+    }
 }
@@ -1,85 +1,112 @@
-use std::{collections::HashMap, str::FromStr};
+use aoc_utils::circuit::{Circuit, Gate, Signal};
+use aoc_utils::dot;
+use aoc_utils::prelude::*;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-enum LogicGate {
-    Number(u16),
-    Operation(String),
-}
-
-impl FromStr for LogicGate {
-    type Err = std::convert::Infallible;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Ok(num) = s.parse::<u16>() {
-            Ok(LogicGate::Number(num))
-        } else {
-            Ok(LogicGate::Operation(s.to_owned()))
+/// Parses a signal: a number or the name of a wire.
+fn signal(source: &Source, circuit: &mut Circuit, s: &str) -> Result<Signal, ParseError> {
+    match s.parse::<u64>() {
+        Ok(value) => Ok(Signal::Value(value)),
+        Err(_) if !s.is_empty() && s.chars().all(|c| c.is_ascii_lowercase()) => {
+            Ok(Signal::Wire(circuit.wire(s)))
         }
+        Err(_) => Err(source.error(s, "a signal or a wire")),
     }
 }
 
-fn get_wire_value(wire: String, logic_gates: &mut HashMap<String, LogicGate>) -> u16 {
-    if let Ok(num) = wire.parse::<u16>() {
-        return num;
+pub fn generator(input: &str) -> Result<Circuit, ParseError> {
+    let source = Source::new(input);
+    let mut circuit = Circuit::new(16);
+    for line in input.lines() {
+        let (gate, wire) = source.split_once(line, " -> ")?;
+        let mut signal = |s| signal(&source, &mut circuit, s);
+        let shift = |s: &str| source.parse::<u32>(s, "a number of bits");
+        let gate = match gate.split(' ').collect::<Vec<_>>()[..] {
+            [a] => Gate::Buffer(signal(a)?),
+            ["NOT", a] => Gate::Not(signal(a)?),
+            [a, "AND", b] => Gate::And(signal(a)?, signal(b)?),
+            [a, "OR", b] => Gate::Or(signal(a)?, signal(b)?),
+            [a, "LSHIFT", n] => Gate::Shl(signal(a)?, shift(n)?),
+            [a, "RSHIFT", n] => Gate::Shr(signal(a)?, shift(n)?),
+            _ => return Err(source.error(gate, "a signal, a wire or a gate")),
+        };
+        let wire = circuit.wire(wire);
+        circuit.connect(wire, gate);
     }
-    if let Some(LogicGate::Number(num)) = logic_gates.get(&wire) {
-        return *num;
+    Ok(circuit)
+}
+
+/// Evaluates the circuit and returns the signal on `a`, exporting the wires
+/// with their signals as the graph `name` when asked to.
+fn signal_on_a(circuit: &Circuit, name: &str) -> u16 {
+    let netlist = circuit.netlist().unwrap_or_else(|e| panic!("{e}"));
+    let mut values = netlist.values();
+    netlist.evaluate(&mut values);
+    if dot::is_active() {
+        dot::export(circuit.to_dot(name, Some(&values)));
     }
-    let logic_gate = logic_gates.get(&wire).unwrap().clone();
-    let res = match logic_gate {
-        LogicGate::Number(n) => n,
-        LogicGate::Operation(s) => {
-            let parts = s.split(' ').collect::<Vec<_>>();
-            if parts.len() == 3 {
-                match parts[1] {
-                    "AND" => {
-                        get_wire_value(parts[0].to_owned(), logic_gates)
-                            & get_wire_value(parts[2].to_owned(), logic_gates)
-                    }
-                    "OR" => {
-                        get_wire_value(parts[0].to_owned(), logic_gates)
-                            | get_wire_value(parts[2].to_owned(), logic_gates)
-                    }
-                    "LSHIFT" => {
-                        get_wire_value(parts[0].to_owned(), logic_gates)
-                            << parts[2].parse::<u16>().unwrap()
-                    }
-                    "RSHIFT" => {
-                        get_wire_value(parts[0].to_owned(), logic_gates)
-                            >> parts[2].parse::<u16>().unwrap()
-                    }
-                    _ => panic!("Unknown operation"),
-                }
-            } else {
-                match parts[0] {
-                    "NOT" => !get_wire_value(parts[1].to_owned(), logic_gates),
-                    _ => get_wire_value(parts[0].to_owned(), logic_gates),
-                }
-            }
-        }
-    };
-    logic_gates.insert(wire, LogicGate::Number(res));
-    res
+    let a = circuit.find("a").expect("There is no wire a");
+    values.get(a) as u16
+}
+
+pub fn part_1(circuit: &Circuit) -> u16 {
+    signal_on_a(circuit, "wires")
 }
 
-fn parse_input(input: &str) -> HashMap<String, LogicGate> {
-    input
-        .lines()
-        .map(|line| {
-            let parts = line.split(" -> ").collect::<Vec<_>>();
-            (parts[1].to_owned(), parts[0].parse::<LogicGate>().unwrap())
-        })
-        .collect()
+pub fn part_2(circuit: &Circuit) -> u16 {
+    let mut circuit = circuit.clone();
+    let a = signal_on_a(&circuit, "wires");
+    let b = circuit.wire("b");
+    circuit.connect(b, Gate::Buffer(Signal::Value(a as u64)));
+    signal_on_a(&circuit, "overridden_wires")
 }
 
-pub fn solution() {
-    let mut gates = parse_input(include_str!("../data/day7.txt"));
-    let mut gates2 = gates.clone();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+e -> b
+b LSHIFT 1 -> a
+"#;
 
-    let part1 = get_wire_value("a".to_owned(), &mut gates);
-    println!("The signal on wire a for part 1 is {part1}");
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 1014, part_2 = 2028),
+    }
 
-    gates2.insert("b".to_owned(), LogicGate::Number(part1));
-    let part2 = get_wire_value("a".to_owned(), &mut gates2);
-    println!("The signal on wire a for part 2 is {part2}");
+    #[test]
+    fn example_wires() {
+        let circuit = generator(INPUT).unwrap();
+        let netlist = circuit.netlist().unwrap();
+        let mut values = netlist.values();
+        netlist.evaluate(&mut values);
+        let expected = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        for (name, value) in expected {
+            assert_eq!(values.get(circuit.find(name).unwrap()), value, "{name}");
+        }
+    }
+
+    #[test]
+    fn rejects_cycles_and_bad_gates() {
+        assert!(generator("x XOR y -> z").is_err());
+        assert!(generator("x LSHIFT y -> z").is_err());
+        let circuit = generator("b -> a\na -> b").unwrap();
+        assert!(circuit.netlist().is_err());
+    }
 }
@@ -1,6 +1,32 @@
-pub fn solution() {
-    let input = include_str!("../data/day8.txt");
-    let part1 = input.lines().fold(0, |acc, line| {
+use aoc_utils::prelude::*;
+
+/// The string literals, checked to be quoted and to use only `\\`, `\"` and
+/// `\x` followed by two hexadecimal digits as escape sequences.
+pub fn generator(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            let mut rest = line
+                .strip_prefix('"')
+                .and_then(|line| line.strip_suffix('"'))
+                .ok_or_else(|| source.error(line, "a quoted string"))?;
+            while let Some(i) = rest.find(['\\', '"']) {
+                let escape = &rest[i..];
+                let len = match escape.as_bytes() {
+                    [b'\\', b'\\' | b'"', ..] => 2,
+                    [b'\\', b'x', a, b, ..] if a.is_ascii_hexdigit() && b.is_ascii_hexdigit() => 4,
+                    _ => return Err(source.error(&escape[..1], "an escape sequence")),
+                };
+                rest = &escape[len..];
+            }
+            Ok(line)
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[&str]) -> usize {
+    input.iter().fold(0, |acc, line| {
         let tot_chars = line.len();
         // Calculate the number of charcaters in memory
         let memory_str = line.replace("\\\\", "a").replace("\\\"", "a");
@@ -8,12 +34,28 @@ pub fn solution() {
         let memory_chars =
             memory_str.len() - 3 * chars.windows(2).filter(|w| w == &['\\', 'x']).count() - 2;
         acc + tot_chars - memory_chars
-    });
-    println!("Characters of code - characters of memory = {part1}");
+    })
+}
 
-    let part2 = input.lines().fold(0, |acc, line| {
+pub fn part_2(input: &[&str]) -> usize {
+    input.iter().fold(0, |acc, line| {
         let encoded_string = line.replace('\\', "\\\\").replace('\"', "\\\"");
         acc + encoded_string.len() + 2 - line.len()
-    });
-    println!("Characters of encoded string - characters of code = {part2}");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"""
+"abc"
+"aaa\"aaa"
+"\x27"
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 12, part_2 = 19),
+    }
 }
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-fn get_distances(graph: &HashMap<&str, HashMap<&str, usize>>) -> Vec<usize> {
+use aoc_utils::prelude::*;
+
+type Graph<'a> = HashMap<&'a str, HashMap<&'a str, usize>>;
+
+fn get_distances(graph: &Graph) -> Vec<usize> {
     let mut queue = Vec::new();
     let mut visited = HashMap::new();
     for node in graph.keys() {
@@ -26,13 +30,13 @@ fn get_distances(graph: &HashMap<&str, HashMap<&str, usize>>) -> Vec<usize> {
     distances
 }
 
-pub fn solution() {
+pub fn generator(input: &str) -> Result<Graph<'_>, ParseError> {
+    let source = Source::new(input);
     let mut routes = HashMap::new();
-    let input = include_str!("../data/day9.txt");
-    input.lines().for_each(|line| {
-        let mut parts = line.split(" = ");
-        let (from, to) = parts.next().unwrap().split_once(" to ").unwrap();
-        let distance = parts.next().unwrap().parse::<usize>().unwrap();
+    for line in input.lines() {
+        let (cities, distance) = source.split_once(line, " = ")?;
+        let (from, to) = source.split_once(cities, " to ")?;
+        let distance = source.parse::<usize>(distance, "a distance")?;
         routes
             .entry(from)
             .or_insert_with(HashMap::new)
@@ -41,13 +45,29 @@ pub fn solution() {
             .entry(to)
             .or_insert_with(HashMap::new)
             .insert(from, distance);
-    });
-    println!(
-        "Min distance: {}",
-        get_distances(&routes).iter().min().unwrap()
-    );
-    println!(
-        "Max distance: {}",
-        get_distances(&routes).iter().max().unwrap()
-    );
+    }
+    Ok(routes)
+}
+
+pub fn part_1(input: &Graph) -> usize {
+    *get_distances(input).iter().min().unwrap()
+}
+
+pub fn part_2(input: &Graph) -> usize {
+    *get_distances(input).iter().max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 605, part_2 = 982),
+    }
 }
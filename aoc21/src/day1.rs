@@ -1,8 +1,8 @@
-pub fn generator(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .map(|l| l.parse::<u32>().expect("Line must be parsable to int."))
-        .collect::<Vec<u32>>()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|l| source.parse(l, "a depth")).collect()
 }
 
 pub fn part_1(input: &[u32]) -> u32 {
@@ -29,17 +29,8 @@ mod tests {
 263
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 7);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 5);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 7, part_2 = 5),
     }
 }
@@ -1,5 +1,13 @@
-pub fn generator(input: &str) -> Vec<&str> {
-    input.lines().collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(input);
+    source.grid(
+        input,
+        |_, c| "([{<>}])".contains(c).then_some(()),
+        "a bracket",
+    )?;
+    Ok(input.lines().collect())
 }
 
 fn matching_closing_bracket(open: char) -> char {
@@ -105,17 +113,8 @@ mod tests {
 <{([{{}}[<[[[<>{}]]]>[]]
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 26397);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 288957);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 26397, part_2 = 288957),
     }
 }
@@ -1,18 +1,17 @@
 use std::{collections::VecDeque, fmt, str::FromStr};
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone)]
 pub struct Grid {
     map: Vec<Vec<u8>>,
 }
 
 impl FromStr for Grid {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s
-            .lines()
-            .map(|line| line.bytes().map(|b| (b - b'0')).collect::<Vec<u8>>())
-            .collect();
+        let map = Source::new(s).grid(s, |_, c| Some(c.to_digit(10)? as u8), "an energy level")?;
         Ok(Grid { map })
     }
 }
@@ -115,8 +114,8 @@ impl Grid {
     }
 }
 
-pub fn generator(input: &str) -> Grid {
-    input.parse().expect("Input must be a grid of numbers.")
+pub fn generator(input: &str) -> Result<Grid, ParseError> {
+    input.parse()
 }
 
 pub fn part_1(input: &Grid) -> u32 {
@@ -153,17 +152,8 @@ mod tests {
 5283751526
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 1656);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 195);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 1656, part_2 = 195),
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_utils::prelude::*;
+
 pub struct CaveSystem {
     graph: Vec<Vec<usize>>,   // Graph represented as adjacency lists
     start_idx: usize,         // Index of the start cave
@@ -8,14 +10,18 @@ pub struct CaveSystem {
     small_cave_indices: HashMap<usize, usize>, // Mapping of small cave indices to bit positions
 }
 
-pub fn generator(input: &str) -> CaveSystem {
+pub fn generator(input: &str) -> Result<CaveSystem, ParseError> {
+    let source = Source::new(input);
+    let connections = input
+        .lines()
+        .map(|line| source.split_once(line, "-"))
+        .collect::<Result<Vec<_>, _>>()?;
     let mut cave_indices = HashMap::new();
     let mut caves = Vec::new();
     let mut is_small_cave = Vec::new();
 
     // Assign indices to caves and determine if they're small
-    for line in input.lines() {
-        let (start, end) = line.split_once('-').expect("Invalid input.");
+    for &(start, end) in &connections {
         for cave in [start, end] {
             if !cave_indices.contains_key(cave) {
                 let index = caves.len();
@@ -28,16 +34,21 @@ pub fn generator(input: &str) -> CaveSystem {
 
     // Build the graph as an adjacency list
     let mut graph = vec![Vec::new(); caves.len()];
-    for line in input.lines() {
-        let (start, end) = line.split_once('-').expect("Invalid input.");
+    for &(start, end) in &connections {
         let start_idx = cave_indices[start];
         let end_idx = cave_indices[end];
         graph[start_idx].push(end_idx);
         graph[end_idx].push(start_idx);
     }
 
-    let start_idx = cave_indices["start"];
-    let end_idx = cave_indices["end"];
+    let cave_index = |name| {
+        cave_indices
+            .get(name)
+            .copied()
+            .ok_or_else(|| source.error_after(input, &format!("a connection to {name:?}")))
+    };
+    let start_idx = cave_index("start")?;
+    let end_idx = cave_index("end")?;
 
     // Map small caves (excluding "start" and "end") to bit positions
     let mut small_cave_indices = HashMap::new();
@@ -50,13 +61,13 @@ pub fn generator(input: &str) -> CaveSystem {
         }
     }
 
-    CaveSystem {
+    Ok(CaveSystem {
         graph,
         start_idx,
         end_idx,
         is_small_cave,
         small_cave_indices,
-    }
+    })
 }
 
 fn count_paths(
@@ -171,17 +182,8 @@ A-end
 b-end
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 10);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 36);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 10, part_2 = 36),
     }
 }
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use aoc_utils::prelude::*;
+
 const FILLED_CHARACTER: &str = "██";
 const EMPTY_CHARACTER: &str = "░░";
 
@@ -15,53 +17,42 @@ enum Direction {
     Left(u32),
 }
 
-#[derive(Debug)]
-pub enum GeneratorError {
-    ParseError,
-}
-
-pub fn generator(input: &str) -> Result<Grid, GeneratorError> {
-    if let Some((map_str, instructions_str)) = input.split_once("\n\n") {
-        let points = map_str
-            .lines()
-            .map(|l| {
-                let (x, y) = l.split_once(',').expect("Must be comma separated numbers");
-                (
-                    x.parse::<u32>().expect("Must be a valid number"),
-                    y.parse::<u32>().expect("Must be a valid number"),
-                )
-            })
-            .collect::<Vec<_>>();
-        let max_x = points.iter().map(|(x, _)| x).max().unwrap();
-        let max_y = points.iter().map(|(_, y)| y).max().unwrap();
-        let mut map = vec![vec![false; *max_x as usize + 1]; *max_y as usize + 1];
-        points
-            .iter()
-            .for_each(|(x, y)| map[*y as usize][*x as usize] = true);
-
-        let instructions = instructions_str
-            .lines()
-            .map(|l| match l.starts_with("fold along y=") {
-                true => {
-                    let fold_value = l
-                        .trim_start_matches("fold along y=")
-                        .parse::<u32>()
-                        .expect("Must be a valid fold point");
-                    Direction::Up(fold_value)
-                }
-                false => {
-                    let fold_value = l
-                        .trim_start_matches("fold along x=")
-                        .parse::<u32>()
-                        .expect("Must be a valid fold point");
-                    Direction::Left(fold_value)
-                }
-            })
-            .collect();
-        Ok(Grid { map, instructions })
-    } else {
-        Err(GeneratorError::ParseError)
+pub fn generator(input: &str) -> Result<Grid, ParseError> {
+    let source = Source::new(input);
+    let (map_str, instructions_str) = source.split_once(input, "\n\n")?;
+    let points = map_str
+        .lines()
+        .map(|l| {
+            let (x, y) = source.split_once(l, ",")?;
+            Ok((
+                source.parse::<u32>(x, "a coordinate")?,
+                source.parse::<u32>(y, "a coordinate")?,
+            ))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    if points.is_empty() {
+        return Err(source.error(map_str, "a dot"));
     }
+    let max_x = points.iter().map(|(x, _)| x).max().unwrap();
+    let max_y = points.iter().map(|(_, y)| y).max().unwrap();
+    let mut map = vec![vec![false; *max_x as usize + 1]; *max_y as usize + 1];
+    points
+        .iter()
+        .for_each(|(x, y)| map[*y as usize][*x as usize] = true);
+
+    let instructions = instructions_str
+        .lines()
+        .map(|l| {
+            if let Some(fold_value) = l.strip_prefix("fold along y=") {
+                Ok(Direction::Up(source.parse(fold_value, "a fold point")?))
+            } else if let Some(fold_value) = l.strip_prefix("fold along x=") {
+                Ok(Direction::Left(source.parse(fold_value, "a fold point")?))
+            } else {
+                Err(source.error(l, "a fold along x or y"))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Grid { map, instructions })
 }
 
 impl Debug for Grid {
@@ -126,8 +117,8 @@ impl Grid {
     }
 }
 
-pub fn part_1(input: &Result<Grid, GeneratorError>) -> u32 {
-    let mut grid = input.as_ref().unwrap().clone();
+pub fn part_1(input: &Grid) -> u32 {
+    let mut grid = input.clone();
     grid.fold_direction(&grid.instructions[0].clone());
     grid.map
         .iter()
@@ -135,8 +126,8 @@ pub fn part_1(input: &Result<Grid, GeneratorError>) -> u32 {
         .sum()
 }
 
-pub fn part_2(input: &Result<Grid, GeneratorError>) -> String {
-    let mut grid = input.as_ref().unwrap().clone();
+pub fn part_2(input: &Grid) -> String {
+    let mut grid = input.clone();
     grid.fold()
 }
 
@@ -167,24 +158,16 @@ fold along y=7
 fold along x=5
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 17);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        let expected = r#"██████████
+    const EXPECTED: &str = r#"██████████
 ██░░░░░░██
 ██░░░░░░██
 ██░░░░░░██
 ██████████
 ░░░░░░░░░░
 ░░░░░░░░░░"#;
-        assert_eq!(result, expected.to_string());
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 17, part_2 = EXPECTED),
     }
 }
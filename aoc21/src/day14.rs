@@ -1,13 +1,16 @@
 use core::str;
 use std::collections::HashMap;
 
-pub fn generator(input: &str) -> (String, HashMap<&str, &str>) {
-    let (polymer_template, pair_insertions) = input.split_once("\n\n").expect("Invalid input");
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<(String, HashMap<&str, &str>), ParseError> {
+    let source = Source::new(input);
+    let (polymer_template, pair_insertions) = source.split_once(input, "\n\n")?;
     let pairs = pair_insertions
         .lines()
-        .map(|line| line.split_once(" -> ").expect("Could not parse pairs"))
-        .collect();
-    (polymer_template.to_owned(), pairs)
+        .map(|line| source.split_once(line, " -> "))
+        .collect::<Result<_, _>>()?;
+    Ok((polymer_template.to_owned(), pairs))
 }
 
 fn count_pairs(
@@ -109,17 +112,8 @@ CC -> N
 CN -> C
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 1588);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 2188189693529);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 1588, part_2 = 2188189693529),
     }
 }
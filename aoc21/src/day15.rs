@@ -1,6 +1,8 @@
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone)]
 pub struct Map {
     grid: Vec<Vec<u8>>,
@@ -94,16 +96,10 @@ impl Map {
     }
 }
 
-pub fn generator(input: &str) -> Map {
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).expect("Map must be numbers") as u8)
-                .collect()
-        })
-        .collect();
-    Map { grid }
+pub fn generator(input: &str) -> Result<Map, ParseError> {
+    let grid =
+        Source::new(input).grid(input, |_, c| Some(c.to_digit(10)? as u8), "a risk level")?;
+    Ok(Map { grid })
 }
 
 pub fn part_1(input: &Map) -> u32 {
@@ -132,17 +128,8 @@ mod tests {
 2311944581
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 40);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 315);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 40, part_2 = 315),
     }
 }
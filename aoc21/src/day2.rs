@@ -1,12 +1,15 @@
-pub fn generator(input: &str) -> Vec<(String, i32)> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<(String, i32)>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|line| match line.split_once(' ') {
-            Some((dir, num)) => {
-                let num = num.parse::<i32>().expect("Second part must be a number.");
-                (dir.to_owned(), num)
+        .map(|line| {
+            let (dir, num) = source.split_once(line, " ")?;
+            if !matches!(dir, "forward" | "down" | "up") {
+                return Err(source.error(dir, "forward, down or up"));
             }
-            None => panic!("We need to be able to split every line."),
+            Ok((dir.to_owned(), source.parse(num, "a number")?))
         })
         .collect()
 }
@@ -48,17 +51,8 @@ down 8
 forward 2
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 150);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 900);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 150, part_2 = 900),
     }
 }
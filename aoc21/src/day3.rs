@@ -1,5 +1,9 @@
-pub fn generator(input: &str) -> Vec<&str> {
-    input.lines().collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(input);
+    source.grid(input, |_, c| matches!(c, '0' | '1').then_some(()), "a bit")?;
+    Ok(input.lines().collect())
 }
 
 pub fn part_1(input: &[&str]) -> u32 {
@@ -110,17 +114,8 @@ mod tests {
 01010
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 198);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 230);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 198, part_2 = 230),
     }
 }
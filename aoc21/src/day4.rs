@@ -1,5 +1,7 @@
 use std::{fmt, str::FromStr};
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone)]
 pub struct BingoBoard {
     numbers: Vec<Vec<u8>>,
@@ -7,17 +9,18 @@ pub struct BingoBoard {
 }
 
 impl FromStr for BingoBoard {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
         let numbers = s
             .lines()
             .map(|line| {
                 line.split_whitespace()
-                    .map(|n| n.parse().expect("Should be a number."))
+                    .map(|n| source.parse(n, "a number"))
                     .collect()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         let marked_numbers = [false; 25];
         Ok(BingoBoard {
             numbers,
@@ -76,17 +79,18 @@ impl BingoBoard {
     }
 }
 
-pub fn generator(input: &str) -> (Vec<u8>, Vec<BingoBoard>) {
-    let (numbers_input, boards_input) = input.split_once("\n\n").expect("Wrong format.");
+pub fn generator(input: &str) -> Result<(Vec<u8>, Vec<BingoBoard>), ParseError> {
+    let source = Source::new(input);
+    let (numbers_input, boards_input) = source.split_once(input, "\n\n")?;
     let numbers = numbers_input
         .split(',')
-        .map(|n| n.parse().expect("Should be a number."))
-        .collect();
+        .map(|n| source.parse(n, "a number"))
+        .collect::<Result<_, _>>()?;
     let boards = boards_input
         .split("\n\n")
-        .map(|board| board.parse().expect("Must be parsable to a bingo board."))
-        .collect();
-    (numbers, boards)
+        .map(|board| source.parse_as(board))
+        .collect::<Result<_, _>>()?;
+    Ok((numbers, boards))
 }
 
 pub fn part_1(input: &(Vec<u8>, Vec<BingoBoard>)) -> u32 {
@@ -166,17 +170,8 @@ mod tests {
  2  0 12  3  7
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 4512);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 1924);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 4512, part_2 = 1924),
     }
 }
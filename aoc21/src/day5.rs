@@ -1,52 +1,55 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug)]
 pub struct Point {
     x: i32,
     y: i32,
 }
 
-#[derive(Debug)]
-pub struct ParsePointError;
-
 impl FromStr for Point {
-    type Err = ParsePointError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(ParsePointError)?;
-        let x = x.trim().parse::<i32>().map_err(|_| ParsePointError)?;
-        let y = y.trim().parse::<i32>().map_err(|_| ParsePointError)?;
+        let source = Source::new(s);
+        let (x, y) = source.split_once(s, ",")?;
+        let x = source.parse(x.trim(), "a coordinate")?;
+        let y = source.parse(y.trim(), "a coordinate")?;
         Ok(Point { x, y })
     }
 }
 
-pub fn generator(input: &str) -> (Point, Point, Vec<(Point, Point)>) {
+type Vents = (Point, Point, Vec<(Point, Point)>);
+
+pub fn generator(input: &str) -> Result<Vents, ParseError> {
+    let source = Source::new(input);
     let (mut min_x, mut min_y, mut max_x, mut max_y) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
 
     let maps: Vec<(Point, Point)> = input
         .lines()
         .map(|line| {
-            let (from, to) = line.split_once(" -> ").expect("Wrong input format.");
-            let from = from.parse::<Point>().expect("Invalid point format.");
-            let to = to.parse::<Point>().expect("Invalid point format.");
+            let (from, to) = source.split_once(line, " -> ")?;
+            let from: Point = source.parse_as(from)?;
+            let to: Point = source.parse_as(to)?;
 
             max_x = max_x.max(from.x).max(to.x);
             max_y = max_y.max(from.y).max(to.y);
             min_x = min_x.min(from.x).min(to.x);
             min_y = min_y.min(from.y).min(to.y);
 
-            (from, to)
+            Ok((from, to))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    (
+    Ok((
         Point { x: min_x, y: min_y },
         Point { x: max_x, y: max_y },
         maps,
-    )
+    ))
 }
 
-pub fn part_1(input: &(Point, Point, Vec<(Point, Point)>)) -> u32 {
+pub fn part_1(input: &Vents) -> u32 {
     let (_, max_point, maps) = input;
     let mut grid = vec![0; ((max_point.x + 1) * (max_point.y + 1)) as usize];
     maps.iter().for_each(|(from, to)| {
@@ -73,7 +76,7 @@ pub fn part_1(input: &(Point, Point, Vec<(Point, Point)>)) -> u32 {
     grid.iter().filter(|&&x| x > 1).count() as u32
 }
 
-pub fn part_2(input: &(Point, Point, Vec<(Point, Point)>)) -> u32 {
+pub fn part_2(input: &Vents) -> u32 {
     let (_, max_point, maps) = input;
 
     // Calculate grid dimensions
@@ -128,17 +131,8 @@ mod tests {
 5,5 -> 8,2
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 5);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 12);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 5, part_2 = 12),
     }
 }
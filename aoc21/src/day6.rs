@@ -1,5 +1,11 @@
-pub fn generator(input: &str) -> Vec<u8> {
-    input.split(',').map(|n| n.parse::<u8>().unwrap()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u8>, ParseError> {
+    let source = Source::new(input);
+    input
+        .split(',')
+        .map(|n| source.parse(n, "a timer"))
+        .collect()
 }
 
 fn simulate_steps(steps: u64, lanternfish: &[u8]) -> u64 {
@@ -48,17 +54,8 @@ mod tests {
 
     const INPUT: &str = r#"3,4,3,1,2"#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 5934);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 26984457539);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 5934, part_2 = 26984457539),
     }
 }
@@ -1,5 +1,11 @@
-pub fn generator(input: &str) -> Vec<u32> {
-    input.split(',').map(|n| n.parse().unwrap()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(input);
+    input
+        .split(',')
+        .map(|n| source.parse(n, "a position"))
+        .collect()
 }
 
 fn calculate_mean(positions: &[u32]) -> u32 {
@@ -50,20 +56,41 @@ pub fn part_2(input: &[u32]) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::testing::check;
 
     const INPUT: &str = r#"16,1,2,0,4,2,7,1,2,14"#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 37);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 37, part_2 = 168),
+    }
+
+    /// The cheapest fuel to align every crab, trying every position.
+    fn cheapest(positions: &[u32], fuel: impl Fn(u32) -> u32) -> u32 {
+        let (&min, &max) = (
+            positions.iter().min().unwrap(),
+            positions.iter().max().unwrap(),
+        );
+        (min..=max)
+            .map(|target| positions.iter().map(|&p| fuel(p.abs_diff(target))).sum())
+            .min()
+            .unwrap()
     }
 
     #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 168);
+    fn aligns_as_cheaply_as_trying_every_position() {
+        check(2000, |rng| {
+            let positions = rng.vec(1..=20, |rng| rng.range(0..=60) as u32);
+            assert_eq!(
+                part_1(&positions),
+                cheapest(&positions, |distance| distance),
+                "{positions:?}"
+            );
+            assert_eq!(
+                part_2(&positions),
+                cheapest(&positions, |distance| distance * (distance + 1) / 2),
+                "{positions:?}"
+            );
+        });
     }
 }
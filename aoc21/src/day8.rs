@@ -1,10 +1,13 @@
-pub fn generator(input: &str) -> Vec<(Vec<Vec<char>>, Vec<Vec<char>>)> {
+use aoc_utils::prelude::*;
+
+type Entry = (Vec<Vec<char>>, Vec<Vec<char>>);
+
+pub fn generator(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|l| {
-            let (input, output) = l
-                .split_once(" | ")
-                .expect("Wrong format. Could not find '|'");
+            let (input, output) = source.split_once(l, " | ")?;
             let input_elements: Vec<Vec<char>> = input
                 .split(' ')
                 .map(|chars| chars.chars().collect())
@@ -13,12 +16,12 @@ pub fn generator(input: &str) -> Vec<(Vec<Vec<char>>, Vec<Vec<char>>)> {
                 .split(' ')
                 .map(|chars| chars.chars().collect())
                 .collect();
-            (input_elements, output_elements)
+            Ok((input_elements, output_elements))
         })
         .collect()
 }
 
-pub fn part_1(input: &[(Vec<Vec<char>>, Vec<Vec<char>>)]) -> u32 {
+pub fn part_1(input: &[Entry]) -> u32 {
     let mut count = 0;
     for data in input {
         let (_, output) = data;
@@ -32,7 +35,7 @@ pub fn part_1(input: &[(Vec<Vec<char>>, Vec<Vec<char>>)]) -> u32 {
     count
 }
 
-pub fn part_2(input: &[(Vec<Vec<char>>, Vec<Vec<char>>)]) -> u32 {
+pub fn part_2(input: &[Entry]) -> u32 {
     let mut sum = 0;
     for data in input {
         let (input, output) = data;
@@ -175,17 +178,8 @@ bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbg
 egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
 gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce"#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 26);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 61229);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 26, part_2 = 61229),
     }
 }
@@ -1,14 +1,9 @@
 use std::collections::HashSet;
 
-pub fn generator(input: &str) -> Vec<Vec<u8>> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|n| n.to_digit(10).expect("Must be parsable to a digit.") as u8)
-                .collect::<Vec<u8>>()
-        })
-        .collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    Source::new(input).grid(input, |_, n| Some(n.to_digit(10)? as u8), "a digit")
 }
 
 fn find_low_points(height_map: &[Vec<u8>]) -> Vec<(usize, usize)> {
@@ -143,17 +138,8 @@ mod tests {
 9899965678
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 15);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 1134);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 15, part_2 = 1134),
     }
 }
@@ -1,5 +1,3 @@
-#![recursion_limit = "256"]
-
 mod day1;
 mod day2;
 mod day3;
@@ -16,7 +14,7 @@ mod day13;
 mod day14;
 mod day15;
 
-aoc_main::main! {
+aoc_utils::main! {
     year 2021;
     day1 : generator => part_1, part_2;
     day2 : generator => part_1, part_2;
@@ -1,9 +1,12 @@
-pub fn generator(input: &str) -> Vec<u32> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(input);
         let mut calories_list = input.split("\n\n")
-        .map(|x| x.lines().map(|x| x.parse::<u32>().unwrap()).sum::<u32>())
-        .collect::<Vec<u32>>();
+        .map(|x| x.lines().map(|x| source.parse::<u32>(x, "a number of calories")).sum::<Result<u32, _>>())
+        .collect::<Result<Vec<u32>, _>>()?;
     calories_list.sort_by(|a, b| b.cmp(a));
-    calories_list
+    Ok(calories_list)
 }
 
 pub fn part_1(input: &[u32]) -> u32 {
@@ -1,83 +1,200 @@
+use std::str::FromStr;
+
+use aoc_utils::ocr::Bitmap;
+use aoc_utils::prelude::*;
+
 const FILLED_CHARACTER: &str = "██";
 const EMPTY_CHARACTER: &str = "░░";
-pub fn part_1(input: &str) -> u32 {
-    let mut register_x = 1;
-    let mut cycle = 0;
-    input.trim().lines().fold(0, |acc, line| {
-        let signal_strength;
-        let add_cycles;
-        let val;
-        if line == "noop" {
-            add_cycles = 1;
-            val = 0;
-            signal_strength = if (cycle + 1 - 20) % 40 == 0 || (cycle + 1) == 20 {
-                (register_x) * (cycle + 1)
-            } else {
-                0
-            };
-        } else {
-            add_cycles = 2;
-            val = line
-                .split_once(' ')
-                .map(|(_, x)| x.parse::<isize>().unwrap())
-                .unwrap();
-            signal_strength = if (cycle + 2 - 20) % 40 == 0 || (cycle + 2) == 20 {
-                (register_x) * (cycle + 2)
-            } else if (cycle + 1 - 20) % 40 == 0 || (cycle + 2) == 20 {
-                (register_x) * (cycle + 1)
-            } else {
-                0
-            };
+
+/// The number of pixels in a row of the CRT.
+static WIDTH: Param<usize> = Param::new("width", 40);
+/// The number of rows of the CRT.
+static HEIGHT: Param<usize> = Param::new("height", 6);
+/// The number of pixels covered by the sprite.
+static SPRITE: Param<isize> = Param::new("sprite", 3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(isize),
+}
+
+impl Instruction {
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        match s.split_once(' ') {
+            None if s == "noop" => Ok(Instruction::Noop),
+            Some(("addx", value)) => Ok(Instruction::Addx(source.parse(value, "a number")?)),
+            _ => Err(source.error(s, "noop or addx")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Cpu {
+    program: Vec<Instruction>,
+}
+
+impl Cpu {
+    /// The value of the X register during every cycle, numbered from 1.
+    fn states(&self) -> impl Iterator<Item = (usize, isize)> + '_ {
+        let mut x = 1;
+        self.program
+            .iter()
+            .flat_map(move |instruction| {
+                let during = std::iter::repeat_n(x, instruction.cycles());
+                if let Instruction::Addx(value) = instruction {
+                    x += value;
+                }
+                during
+            })
+            .zip(1..)
+            .map(|(x, cycle)| (cycle, x))
+    }
+
+    /// The sum of the signal strengths during the 20th cycle and every 40
+    /// cycles after that.
+    fn signal_strength(&self) -> isize {
+        self.states()
+            .filter(|(cycle, _)| *cycle >= 20 && (cycle - 20) % 40 == 0)
+            .map(|(cycle, x)| cycle as isize * x)
+            .sum()
+    }
+}
+
+/// A screen drawing one pixel per cycle, left to right and top to bottom,
+/// lit when the sprite centred on X covers it.
+struct Crt {
+    width: usize,
+    height: usize,
+    sprite: isize,
+}
+
+impl Crt {
+    fn draw(&self, cpu: &Cpu) -> Bitmap {
+        let mut bitmap = Bitmap::new(self.width, self.height);
+        let sprite = -(self.sprite - 1) / 2..=self.sprite / 2;
+        for (cycle, x) in cpu.states().take(self.width * self.height) {
+            let pixel = cycle - 1;
+            let column = (pixel % self.width) as isize;
+            bitmap.set(
+                pixel % self.width,
+                pixel / self.width,
+                sprite.contains(&(column - x)),
+            );
         }
+        bitmap
+    }
+}
+
+/// Draws the bitmap with wide characters, to be read in the terminal.
+fn render(bitmap: &Bitmap) -> String {
+    (0..bitmap.height())
+        .map(|y| {
+            (0..bitmap.width())
+                .map(|x| {
+                    if bitmap.get(x, y) {
+                        FILLED_CHARACTER
+                    } else {
+                        EMPTY_CHARACTER
+                    }
+                })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
 
-        register_x += val;
-        cycle += add_cycles;
-        acc + signal_strength
-    }) as u32
+pub fn generator(input: &str) -> Result<Cpu, ParseError> {
+    let source = Source::new(input);
+    let program = input
+        .trim()
+        .lines()
+        .map(|line| source.parse_as(line))
+        .collect::<Result<_, _>>()?;
+    Ok(Cpu { program })
 }
 
-pub fn part_2(input: &str) -> String {
-    let mut image: Vec<Vec<&str>> = vec![Vec::new(); 6];
-    let mut register_x = 1;
-    let mut cycle = 0;
-    input.trim().lines().for_each(|line| {
-        let add_cycles;
-        let val;
-        if line == "noop" {
-            add_cycles = 1;
-            val = 0;
-            let char = if (register_x - 1..=register_x + 1).contains(&(cycle % 40)) {
-                FILLED_CHARACTER
-            } else {
-                EMPTY_CHARACTER
-            };
-            image[(cycle / 40) as usize].push(char);
-        } else {
-            add_cycles = 2;
-            val = line
-                .split_once(' ')
-                .map(|(_, x)| x.parse::<isize>().unwrap())
-                .unwrap();
-            let char1 = if (register_x - 1..=register_x + 1).contains(&(cycle % 40)) {
-                FILLED_CHARACTER
-            } else {
-                EMPTY_CHARACTER
-            };
-            let char2 = if (register_x - 1..=register_x + 1).contains(&((cycle + 1) % 40)) {
-                FILLED_CHARACTER
-            } else {
-                EMPTY_CHARACTER
-            };
-            image[(cycle / 40) as usize].push(char1);
-            image[((cycle + 1) / 40) as usize].push(char2);
+pub fn part_1(cpu: &Cpu) -> isize {
+    cpu.signal_strength()
+}
+
+pub fn part_2(cpu: &Cpu) -> String {
+    let crt = Crt {
+        width: WIDTH.get(),
+        height: HEIGHT.get(),
+        sprite: SPRITE.get(),
+    };
+    let bitmap = crt.draw(cpu);
+    bitmap.read().unwrap_or_else(|| render(&bitmap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "noop\naddx 3\naddx -5\n";
+
+    #[test]
+    fn states() {
+        let cpu = generator(INPUT).unwrap();
+        let states = cpu.states().collect::<Vec<_>>();
+        assert_eq!(states, [(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert!(generator("noop\naddx\n").is_err());
+    }
+
+    /// A program drawing `rows` with the default sprite, picking X for every
+    /// pair of pixels drawn during an `addx`. The first two pixels must be lit,
+    /// as X starts at 1.
+    fn draw(rows: &[&str]) -> String {
+        let mut xs = Vec::new();
+        for row in rows {
+            for (pair, pixels) in row.as_bytes().chunks(2).enumerate() {
+                let c = 2 * pair as isize;
+                xs.push(match pixels {
+                    b"##" => c + 1,
+                    b"#." => c - 1,
+                    b".#" => c + 2,
+                    _ => c + 10,
+                });
+            }
         }
+        assert_eq!(xs[0], 1);
+        xs.push(xs[xs.len() - 1]);
+        let addx = xs.windows(2).map(|w| format!("addx {}\n", w[1] - w[0]));
+        addx.collect()
+    }
 
-        register_x += val;
-        cycle += add_cycles;
-    });
+    const SIGNAL: &str = "addx 0\nnoop\nnoop\n";
 
-    image
-        .iter()
-        .map(|row| row.concat() + "\n")
-        .collect::<String>()
+    aoc_utils::aoc_test! {
+        generator =>
+        signal: (&SIGNAL.repeat(55), part_1 = 20 + 60 + 100 + 140 + 180 + 220),
+        letters { width = 20 }: (
+            &draw(&[
+                "####.####.###...##..",
+                "...#.#....#..#.#..#.",
+                "..#..###..#..#.#..#.",
+                ".#...#....###..#..#.",
+                "#....#....#.#..#..#.",
+                "####.####.#..#..##..",
+            ]),
+            part_2 = "ZERO"
+        ),
+        picture { width = 4, height = 2 }: (
+            &draw(&["##..", ".##."]),
+            part_2 = format!("{0}{0}{1}{1}\n{1}{0}{0}{1}\n", FILLED_CHARACTER, EMPTY_CHARACTER)
+        ),
+    }
 }
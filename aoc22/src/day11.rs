@@ -1,13 +1,36 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Copy, Clone)]
 struct Divisble(usize);
 #[derive(Debug, Copy, Clone)]
 struct MonkeyNumber(usize);
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Copy, Clone)]
+enum Operand {
+    Old,
+    Number(usize),
+}
+
+impl Operand {
+    fn value(&self, old: usize) -> usize {
+        match self {
+            Operand::Old => old,
+            Operand::Number(n) => *n,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Operation {
+    Add(Operand),
+    Multiply(Operand),
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
     starting_items: Vec<usize>,
-    operation: String,
+    operation: Operation,
     test: (Divisble, MonkeyNumber, MonkeyNumber),
 }
 
@@ -15,32 +38,9 @@ impl Monkey {
     fn inspect_items(&self) -> Vec<usize> {
         self.starting_items
             .iter()
-            .map(|item| {
-                let new_val;
-                if self.operation.starts_with('*') {
-                    if self.operation.starts_with("* old") {
-                        new_val = item * item;
-                    } else {
-                        new_val = item
-                            * self
-                                .operation
-                                .strip_prefix("* ")
-                                .unwrap()
-                                .parse::<usize>()
-                                .unwrap();
-                    }
-                } else if self.operation.starts_with("+ old") {
-                    new_val = item + item;
-                } else {
-                    new_val = item
-                        + self
-                            .operation
-                            .strip_prefix("+ ")
-                            .unwrap()
-                            .parse::<usize>()
-                            .unwrap();
-                }
-                new_val
+            .map(|&item| match self.operation {
+                Operation::Add(operand) => item + operand.value(item),
+                Operation::Multiply(operand) => item * operand.value(item),
             })
             .collect::<Vec<usize>>()
     }
@@ -55,51 +55,39 @@ impl Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.split('\n').map(|line| line.trim()).skip(1);
-        let starting_items = lines
-            .next()
-            .unwrap()
-            .strip_prefix("Starting items: ")
-            .unwrap()
-            .split(", ")
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
-        let operation = lines
-            .next()
-            .unwrap()
-            .strip_prefix("Operation: new = old ")
-            .unwrap()
-            .to_owned();
-        let divisible = Divisble(
-            lines
+        let source = Source::new(s);
+        let mut lines = s.lines().map(|line| line.trim());
+        let mut field = |prefix: &str| {
+            let expected = format!("{prefix:?}");
+            let line = lines
                 .next()
-                .unwrap()
-                .strip_prefix("Test: divisible by ")
-                .unwrap()
-                .parse()
-                .unwrap(),
-        );
-        let true_monkey = MonkeyNumber(
-            lines
-                .next()
-                .unwrap()
-                .strip_prefix("If true: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap(),
-        );
-        let false_monkey = MonkeyNumber(
-            lines
-                .next()
-                .unwrap()
-                .strip_prefix("If false: throw to monkey ")
-                .unwrap()
-                .parse()
-                .unwrap(),
-        );
+                .ok_or_else(|| source.error_after(s.trim_end(), &expected))?;
+            line.strip_prefix(prefix)
+                .ok_or_else(|| source.error(line, &expected))
+        };
+        field("Monkey ")?;
+        let starting_items = field("Starting items: ")?
+            .split(", ")
+            .map(|n| source.parse(n, "a worry level"))
+            .collect::<Result<Vec<usize>, _>>()?;
+        let (operator, operand) = source.split_once(field("Operation: new = old ")?, " ")?;
+        let operand = match operand {
+            "old" => Operand::Old,
+            n => Operand::Number(source.parse(n, "old or a number")?),
+        };
+        let operation = match operator {
+            "+" => Operation::Add(operand),
+            "*" => Operation::Multiply(operand),
+            _ => return Err(source.error(operator, "+ or *")),
+        };
+        let divisible = Divisble(source.parse(field("Test: divisible by ")?, "a number")?);
+        let true_monkey =
+            MonkeyNumber(source.parse(field("If true: throw to monkey ")?, "a monkey number")?);
+        let false_monkey =
+            MonkeyNumber(source.parse(field("If false: throw to monkey ")?, "a monkey number")?);
         let test = (divisible, true_monkey, false_monkey);
         Ok(Monkey {
             starting_items,
@@ -109,15 +97,17 @@ impl FromStr for Monkey {
     }
 }
 
-fn get_monkeys(input: &str) -> Vec<Monkey> {
+pub fn generator(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let source = Source::new(input);
     input
+        .trim()
         .split("\n\n")
-        .map(|monkey| monkey.parse::<Monkey>().unwrap())
+        .map(|monkey| source.parse_as(monkey))
         .collect()
 }
 
-pub fn part_1(input: &str) -> usize {
-    let mut monkeys = get_monkeys(input);
+pub fn part_1(input: &[Monkey]) -> usize {
+    let mut monkeys = input.to_vec();
     let mut processed_items = vec![0; monkeys.len()];
     for _ in 0..20 {
         for i in 0..monkeys.len() {
@@ -134,8 +124,8 @@ pub fn part_1(input: &str) -> usize {
     processed_items[0] * processed_items[1]
 }
 
-pub fn part_2(input: &str) -> u64 {
-    let mut monkeys = get_monkeys(input);
+pub fn part_2(input: &[Monkey]) -> u64 {
+    let mut monkeys = input.to_vec();
     let mut processed_items = vec![0; monkeys.len()];
     let mod_val = monkeys.iter().fold(1, |acc, monkey| acc * monkey.test.0 .0);
     for _ in 0..10000 {
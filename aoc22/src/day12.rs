@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use aoc_utils::prelude::*;
+
 // https://www.geeksforgeeks.org/dijkstras-shortest-path-algorithm-using-priority_queue-stl/
 fn dijkstra(end: &(usize, usize), heightmap: &[Vec<u8>], distances: &mut [Vec<u32>]) {
     let mut search_queue = VecDeque::with_capacity(heightmap.len() * heightmap[0].len());
@@ -28,37 +30,31 @@ type Heightmap = Vec<Vec<u8>>;
 type Distances = Vec<Vec<u32>>;
 type Pos = (usize, usize);
 
-pub fn generator(input: &str) -> (Heightmap, Distances, Pos) {
+pub fn generator(input: &str) -> Result<(Heightmap, Distances, Pos), ParseError> {
     let (mut start, mut end) = ((0, 0), (0, 0));
-    let heightmap = input
-        .trim()
-        .split('\n')
-        .enumerate()
-        .map(|(i, line)| {
-            line.as_bytes()
-                .iter()
-                .enumerate()
-                .map(|(j, c)| {
-                    if c == &b'S' {
-                        start = (i, j);
-                        b'a'
-                    } else if c == &b'E' {
-                        end = (i, j);
-                        b'z'
-                    } else {
-                        *c
-                    }
-                })
-                .collect::<Vec<u8>>()
-        })
-        .collect::<Vec<_>>();
+    let heightmap = Source::new(input).grid(
+        input.trim(),
+        |(j, i), c| match c {
+            'S' => {
+                start = (i, j);
+                Some(b'a')
+            }
+            'E' => {
+                end = (i, j);
+                Some(b'z')
+            }
+            'a'..='z' => Some(c as u8),
+            _ => None,
+        },
+        "a height, 'S' or 'E'",
+    )?;
     let mut distances = heightmap
         .iter()
         .map(|r| r.iter().map(|_| u32::MAX).collect::<Vec<_>>())
         .collect::<Vec<_>>();
     distances[end.0][end.1] = 0;
     dijkstra(&end, &heightmap, &mut distances);
-    (heightmap, distances, start)
+    Ok((heightmap, distances, start))
 }
 
 pub fn part_1((_, distances, start): &(Heightmap, Distances, Pos)) -> u32 {
@@ -1,4 +1,7 @@
 use std::str::FromStr;
+
+use aoc_utils::prelude::*;
+
 #[derive(PartialEq, Eq)]
 pub enum Node {
     List(Vec<Node>),
@@ -6,48 +9,48 @@ pub enum Node {
 }
 
 impl FromStr for Node {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let packet = s.trim();
         let mut stack: Vec<Vec<Node>> = Vec::new();
         let mut current_list: Vec<Node> = Vec::new();
-        let mut chars = s.trim().chars();
-        while let Some(c) = chars.next() {
+        let mut chars = packet.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
             match c {
                 '[' => {
                     stack.push(current_list);
                     current_list = Vec::new();
                 }
                 ']' => {
-                    let mut node = stack.pop().unwrap();
+                    let mut node = stack
+                        .pop()
+                        .ok_or_else(|| source.error(&packet[i..=i], "a matching '['"))?;
                     node.push(Node::List(current_list));
                     current_list = node;
                 }
                 ',' => (),
                 '0'..='9' => {
                     let mut n = c.to_digit(10).unwrap();
-                    for number in chars.by_ref() {
-                        match number {
-                            '0'..='9' => n = n * 10 + number.to_digit(10).unwrap(),
-                            ']' => {
-                                current_list.push(Node::Leaf(n));
-                                let mut node = stack.pop().unwrap();
-                                node.push(Node::List(current_list));
-                                current_list = node;
-                                break;
-                            }
-                            ',' => {
-                                current_list.push(Node::Leaf(n));
-                                break;
-                            }
-                            _ => unreachable!(),
-                        }
+                    while let Some(digit) = chars.peek().and_then(|(_, c)| c.to_digit(10)) {
+                        n = n * 10 + digit;
+                        chars.next();
                     }
+                    current_list.push(Node::Leaf(n));
+                }
+                _ => {
+                    let found = &packet[i..i + c.len_utf8()];
+                    return Err(source.error(found, "a number, ',', '[' or ']'"));
                 }
-                _ => unreachable!(),
             }
         }
-        Ok(current_list.pop().unwrap())
+        if !stack.is_empty() {
+            return Err(source.error_after(packet, "']'"));
+        }
+        current_list
+            .pop()
+            .ok_or_else(|| source.error(packet, "a packet"))
     }
 }
 
@@ -68,18 +71,13 @@ impl PartialOrd for Node {
     }
 }
 
-pub fn generator(input: &str) -> Vec<(Node, Node)> {
+pub fn generator(input: &str) -> Result<Vec<(Node, Node)>, ParseError> {
+    let source = Source::new(input);
     input
         .split("\n\n")
         .map(|pair| {
-            pair.split_once('\n')
-                .map(|(node_x, node_y)| {
-                    (
-                        node_x.parse::<Node>().unwrap(),
-                        node_y.parse::<Node>().unwrap(),
-                    )
-                })
-                .unwrap()
+            let (node_x, node_y) = source.split_once(pair, "\n")?;
+            Ok((source.parse_as(node_x)?, source.parse_as(node_y)?))
         })
         .collect()
 }
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+use aoc_utils::viz::{self, Cell, Color, Frame};
+
+/// Grains of sand settling between the frames shown.
+static GRAINS_PER_FRAME: Param<u32> = Param::new("grains_per_frame", 10);
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
+pub struct Point {
     x: i32,
     y: i32,
 }
@@ -22,27 +28,28 @@ struct Cave {
 }
 
 impl FromStr for Point {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let point: Vec<i32> = s.split(',').map(|s| s.trim().parse().unwrap()).collect();
+        let source = Source::new(s);
+        let (x, y) = source.split_once(s, ",")?;
         Ok(Point {
-            x: point[0],
-            y: point[1],
+            x: source.parse(x.trim(), "a coordinate")?,
+            y: source.parse(y.trim(), "a coordinate")?,
         })
     }
 }
 
 impl Cave {
-    fn new(scan: &str, source: Point) -> Cave {
+    fn new(scan: &[Vec<Point>], source: Point) -> Cave {
         let mut map = HashMap::new();
         let mut highest_y = 0;
-        for line in scan.lines() {
-            let mut iter = line.split("->");
-            let first_point: Point = iter.next().unwrap().parse().unwrap();
+        for path in scan {
+            let Some(&first_point) = path.first() else {
+                continue;
+            };
             let mut prev_point = first_point;
-            for point in line.split("->") {
-                let new_point: Point = point.parse().unwrap();
+            for &new_point in path {
                 if new_point.x == prev_point.x {
                     // Vertical line
                     let range = if prev_point.y < new_point.y {
@@ -93,6 +100,8 @@ impl Cave {
     }
 
     fn simulate(&mut self) {
+        let grains_per_frame = GRAINS_PER_FRAME.get().max(1);
+        let animate = viz::is_active();
         let mut current_point = self.source;
         let mut stack = vec![current_point];
         loop {
@@ -112,10 +121,16 @@ impl Cave {
                 None => {
                     self.map.insert(current_point, Tile::Sand);
                     self.sand_counter += 1;
+                    if animate && self.sand_counter.is_multiple_of(grains_per_frame) {
+                        viz::show(self);
+                    }
                     current_point = stack.pop().unwrap();
                 }
             };
         }
+        if animate {
+            viz::show(self);
+        }
     }
 
     fn get_move(&self, point: Point) -> Option<(i32, i32)> {
@@ -144,6 +159,26 @@ impl Cave {
     }
 }
 
+/// The reach of the sand, which piles up from the source down to the floor.
+impl Frame for Cave {
+    fn size(&self) -> (usize, usize) {
+        (2 * self.floor as usize + 1, self.floor as usize + 1)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let point = Point {
+            x: self.source.x - self.floor as i32 + x as i32,
+            y: y as i32,
+        };
+        match self.map.get(&point) {
+            Some(Tile::Sand) => Cell::new('o').color(Color::Yellow),
+            Some(Tile::Rock) => Cell::new('#').color(Color::White),
+            None if point == self.source => Cell::new('+').color(Color::Red),
+            None => Cell::new('.').color(Color::Gray),
+        }
+    }
+}
+
 impl std::fmt::Debug for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut string = String::new();
@@ -166,13 +201,26 @@ impl std::fmt::Debug for Cave {
     }
 }
 
-pub fn part_1(input: &str) -> u32 {
+/// The paths of rock in the scan.
+pub fn generator(input: &str) -> Result<Vec<Vec<Point>>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            line.split("->")
+                .map(|point| source.parse_as(point.trim()))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[Vec<Point>]) -> u32 {
     let mut cave = Cave::new(input, Point { x: 500, y: 0 });
     cave.simulate();
     cave.sand_counter
 }
 
-pub fn part_2(input: &str) -> u32 {
+pub fn part_2(input: &[Vec<Point>]) -> u32 {
     let mut cave = Cave::new(input, Point { x: 500, y: 0 });
     cave.add_floor();
     cave.simulate();
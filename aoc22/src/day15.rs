@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Eq, PartialEq, Hash)]
 struct Point {
     x: isize,
@@ -74,43 +76,49 @@ impl Sensor {
     }
 }
 
-pub fn generator(input: &str) -> Vec<Sensor> {
+pub fn generator(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    let source = Source::new(input);
     input
         .trim()
         .lines()
         .map(|line| {
-            let (sensor_x, line) = line
-                .strip_prefix("Sensor at x=")
-                .unwrap()
-                .split_once(',')
-                .unwrap();
-            let (sensor_y, line) = line.strip_prefix(" y=").unwrap().split_once(':').unwrap();
-            let (beacon_x, line) = line
-                .strip_prefix(" closest beacon is at x=")
-                .unwrap()
-                .split_once(',')
-                .unwrap();
-            let beacon_y = line.strip_prefix(" y=").unwrap();
+            let [sensor_x, sensor_y, beacon_x, beacon_y] = source.integers(line)?;
             let location = Point {
-                x: sensor_x.parse().unwrap(),
-                y: sensor_y.parse().unwrap(),
+                x: sensor_x,
+                y: sensor_y,
             };
             let beacon = Beacon {
                 location: Point {
-                    x: beacon_x.parse().unwrap(),
-                    y: beacon_y.parse().unwrap(),
+                    x: beacon_x,
+                    y: beacon_y,
                 },
             };
-            Sensor::new(location, beacon)
+            Ok(Sensor::new(location, beacon))
         })
         .collect()
 }
 
+/// The row inspected by the first part.
+static ROW: Param<isize> = Param::new("row", 2_000_000);
+/// The largest coordinate of the distress beacon.
+static BOUND: Param<isize> = Param::new("bound", 4_000_000);
+
 pub fn part_1(input: &[Sensor]) -> u32 {
     let beacons = Sensor::get_beacons(input);
-    let y = 2_000_000;
+    let y = ROW.get();
+    let reach = |s: &Sensor| s.beacon_distance as isize;
+    let min_x = input
+        .iter()
+        .map(|s| s.location.x - reach(s))
+        .min()
+        .unwrap_or(0);
+    let max_x = input
+        .iter()
+        .map(|s| s.location.x + reach(s))
+        .max()
+        .unwrap_or(0);
     let mut count = 0;
-    for x in -y..=3 * y {
+    for x in min_x..=max_x {
         let position = Point { x, y };
         if input.iter().any(|s| s.is_within_beacon_distance(&position)) {
             count += 1
@@ -126,7 +134,7 @@ pub fn part_1(input: &[Sensor]) -> u32 {
 }
 
 pub fn part_2(input: &[Sensor]) -> u64 {
-    let bound = 4_000_000;
+    let bound = BOUND.get();
     for sensor in input {
         for p in sensor.get_border_points() {
             if p.x < 0 || p.y < 0 || p.x > bound || p.y > bound {
@@ -140,3 +148,29 @@ pub fn part_2(input: &[Sensor]) -> u64 {
     }
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example { row = 10, bound = 20 }: (INPUT, part_1 = 26, part_2 = 56_000_011),
+    }
+}
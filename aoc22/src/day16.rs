@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct Valve {
     flow_rate: u32,
@@ -8,21 +10,22 @@ pub struct Valve {
 }
 
 impl FromStr for Valve {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (flow_rate, line) = s
+        let source = Source::new(s);
+        let line = s
             .strip_prefix("has flow rate=")
-            .unwrap()
-            .split_once(';')
-            .unwrap();
+            .ok_or_else(|| source.error(s, "\"has flow rate=\""))?;
+        let (flow_rate, line) = source.split_once(line, ";")?;
         let valve_names = line
             .strip_prefix(" tunnels lead to valves ")
-            .unwrap_or_else(|| line.strip_prefix(" tunnel leads to valve ").unwrap())
+            .or_else(|| line.strip_prefix(" tunnel leads to valve "))
+            .ok_or_else(|| source.error(line, "the tunnels leading to other valves"))?
             .split(", ")
             .map(|name| name.to_owned())
             .collect::<Vec<_>>();
-        let flow_rate = flow_rate.parse().unwrap();
+        let flow_rate = source.parse(flow_rate, "a flow rate")?;
         Ok(Valve {
             flow_rate,
             leading_valves: valve_names,
@@ -191,21 +194,17 @@ fn bfs2(valves: &HashMap<String, Valve>, start_valve: Valve, time_left: i16) ->
     max_flow
 }
 
-pub fn generator(input: &str) -> HashMap<String, Valve> {
+pub fn generator(input: &str) -> Result<HashMap<String, Valve>, ParseError> {
+    let source = Source::new(input);
     let mut valves = HashMap::new();
-    input
-        .trim_end()
-        .split('\n')
-        .map(|line| {
-            let (valve_name, line) = line
-                .strip_prefix("Valve ")
-                .unwrap()
-                .split_once(' ')
-                .unwrap();
-            valves.insert(valve_name.to_owned(), line.parse::<Valve>().unwrap());
-        })
-        .for_each(drop);
-    valves
+    for line in input.trim_end().split('\n') {
+        let valve = line
+            .strip_prefix("Valve ")
+            .ok_or_else(|| source.error(line, "\"Valve \""))?;
+        let (valve_name, line) = source.split_once(valve, " ")?;
+        valves.insert(valve_name.to_owned(), source.parse_as(line)?);
+    }
+    Ok(valves)
 }
 
 pub fn part_1(input: &HashMap<String, Valve>) -> u32 {
@@ -1,11 +1,21 @@
 use std::collections::HashMap;
 
+use aoc_utils::prelude::*;
+use aoc_utils::viz::{self, Cell, Color, Frame};
+
 #[derive(Clone, Copy)]
 struct Point {
     x: u64,
     y: u64,
 }
 
+/// A jet of hot gas pushing the falling rock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
 enum Shape {
     Horizontal,
     Plus,
@@ -87,17 +97,16 @@ impl Shape {
             .any(|Point { x, y }| cave[y as usize][x as usize])
     }
 
-    fn move_from_stream(&self, point: &Point, direction: char, cave: &[Vec<bool>]) -> Point {
+    fn move_from_stream(&self, point: &Point, direction: Jet, cave: &[Vec<bool>]) -> Point {
         let new_point = match direction {
-            '<' => Point {
+            Jet::Left => Point {
                 x: point.x.saturating_sub(1),
                 y: point.y,
             },
-            '>' => Point {
+            Jet::Right => Point {
                 x: (point.x + 1).clamp(0, 7 - self.width()),
                 y: point.y,
             },
-            _ => unreachable!(),
         };
         if self.is_colliding(&new_point, cave) {
             *point
@@ -115,7 +124,7 @@ impl Shape {
 
 fn drop_rock<'a>(
     rock: &mut impl Iterator<Item = (usize, &'a Shape)>,
-    stream: &mut impl Iterator<Item = (usize, char)>,
+    stream: &mut impl Iterator<Item = (usize, Jet)>,
     cave: &mut [Vec<bool>],
     drop_height: u64,
 ) -> (usize, usize, u64) {
@@ -147,6 +156,36 @@ fn drop_rock<'a>(
     )
 }
 
+/// The top of the tower, between the walls of the chamber.
+struct Chamber<'a> {
+    cave: &'a [Vec<bool>],
+    height: u64,
+}
+
+impl Chamber<'_> {
+    /// Rows of the chamber shown, up to where the next rock appears.
+    const ROWS: usize = 40;
+}
+
+impl Frame for Chamber<'_> {
+    fn size(&self) -> (usize, usize) {
+        (9, Chamber::ROWS)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let Some(row) = (self.height as usize + 3).checked_sub(y) else {
+            return Cell::EMPTY;
+        };
+        match (x, row) {
+            (0 | 8, 0) => Cell::new('+').color(Color::Gray),
+            (0 | 8, _) => Cell::new('|').color(Color::Gray),
+            (_, 0) => Cell::new('-').color(Color::Gray),
+            _ if self.cave[row][x - 1] => Cell::new('#').color(Color::Cyan),
+            _ => Cell::new('.').color(Color::Gray),
+        }
+    }
+}
+
 // This state struct I saw somewhere on reddit
 #[derive(Hash, Clone, Copy, PartialEq, Eq, Debug)]
 struct State([u64; 7], usize, usize);
@@ -175,7 +214,7 @@ impl State {
 
 fn simulate<'a>(
     n_rocks: u64,
-    stream: &mut impl Iterator<Item = (usize, char)>,
+    stream: &mut impl Iterator<Item = (usize, Jet)>,
     rocks: &mut impl Iterator<Item = (usize, &'a Shape)>,
 ) -> u64 {
     let mut cave = vec![vec![false; 7]; (4 * n_rocks).min(250_000) as usize];
@@ -185,6 +224,12 @@ fn simulate<'a>(
     for i in 0..n_rocks {
         let (rock_idx, stream_idx, new_height) = drop_rock(rocks, stream, &mut cave, height);
         height = new_height;
+        if viz::is_active() {
+            viz::show(&Chamber {
+                cave: &cave,
+                height,
+            });
+        }
         let ceiling = State::ceiling_map(&cave, height);
         let state = State(ceiling, rock_idx, stream_idx);
         if let Some((blocks_placed, prev_height)) = state_map.get(&state) {
@@ -206,14 +251,30 @@ fn simulate<'a>(
     height - 1
 }
 
-pub fn part_1(input: &str) -> u64 {
-    let mut stream = input.trim().chars().enumerate().cycle();
+pub fn generator(input: &str) -> Result<Vec<Jet>, ParseError> {
+    let source = Source::new(input);
+    let pattern = input.trim();
+    if pattern.is_empty() {
+        return Err(source.error_after(pattern, "< or >"));
+    }
+    pattern
+        .char_indices()
+        .map(|(i, direction)| match direction {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(source.error(&pattern[i..i + direction.len_utf8()], "< or >")),
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[Jet]) -> u64 {
+    let mut stream = input.iter().copied().enumerate().cycle();
     let mut rocks = Shape::ORDER.iter().enumerate().cycle();
     simulate(2022, &mut stream, &mut rocks)
 }
 
-pub fn part_2(input: &str) -> u64 {
-    let mut stream = input.trim().chars().enumerate().cycle();
+pub fn part_2(input: &[Jet]) -> u64 {
+    let mut stream = input.iter().copied().enumerate().cycle();
     let mut rocks = Shape::ORDER.iter().enumerate().cycle();
     simulate(1_000_000_000_000, &mut stream, &mut rocks)
 }
@@ -1,5 +1,7 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+use aoc_utils::prelude::*;
+
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug, PartialOrd, Ord)]
 pub struct Point {
     x: isize,
@@ -131,24 +133,16 @@ impl BoundingBox {
     }
 }
 
-pub fn generator(input: &str) -> HashSet<Point> {
-    let mut points = HashSet::new();
+pub fn generator(input: &str) -> Result<HashSet<Point>, ParseError> {
+    let source = Source::new(input);
     input
         .trim()
         .split('\n')
         .map(|line| {
-            let nums: Vec<isize> = line
-                .split(',')
-                .map(|x| x.parse::<isize>().unwrap())
-                .collect();
-            points.insert(Point {
-                x: nums[0],
-                y: nums[1],
-                z: nums[2],
-            })
+            let [x, y, z] = source.integers(line)?;
+            Ok(Point { x, y, z })
         })
-        .for_each(drop);
-    points
+        .collect()
 }
 
 pub fn part_1(input: &HashSet<Point>) -> u32 {
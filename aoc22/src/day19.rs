@@ -1,6 +1,8 @@
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug)]
 pub struct Blueprint {
     ore_cost: u32,
@@ -10,7 +12,7 @@ pub struct Blueprint {
 }
 
 impl FromStr for Blueprint {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let nums = s
@@ -18,11 +20,14 @@ impl FromStr for Blueprint {
             .split(' ')
             .filter_map(|x| x.parse::<u32>().ok())
             .collect::<Vec<_>>();
+        let [ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] = nums[..] else {
+            return Err(Source::new(s).error(s.trim(), "the costs of the four robots"));
+        };
         Ok(Blueprint {
-            ore_cost: nums[0],
-            clay_cost: nums[1],
-            obsidian_cost: (nums[2], nums[3]),
-            geode_cost: (nums[4], nums[5]),
+            ore_cost: ore,
+            clay_cost: clay,
+            obsidian_cost: (obsidian_ore, obsidian_clay),
+            geode_cost: (geode_ore, geode_obsidian),
         })
     }
 }
@@ -160,11 +165,12 @@ fn simulate(blueprint: &Blueprint, time: u32) -> u32 {
     max_geodes
 }
 
-pub fn generator(input: &str) -> Vec<Blueprint> {
+pub fn generator(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    let source = Source::new(input);
     input
         .trim()
         .split('\n')
-        .map(|bp| bp.parse::<Blueprint>().unwrap())
+        .map(|bp| source.parse_as(bp))
         .collect()
 }
 
@@ -1,9 +1,11 @@
 use std::cmp::Ordering;
 use std::str::FromStr;
+
+use aoc_utils::prelude::*;
 use Shape::*;
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
@@ -30,13 +32,34 @@ impl From<Shape> for u32 {
 }
 
 impl FromStr for Shape {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "A" | "X" => Ok(Rock),
-            "B" | "Y" => Ok(Paper),
-            "C" | "Z" => Ok(Scissors),
-            _ => unreachable!(),
+            "A" => Ok(Rock),
+            "B" => Ok(Paper),
+            "C" => Ok(Scissors),
+            _ => Err(Source::new(s).error(s, "A, B or C")),
+        }
+    }
+}
+
+/// The second column of the strategy guide, read as a shape in part 1 and as
+/// an outcome in part 2.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Column {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Column::X),
+            "Y" => Ok(Column::Y),
+            "Z" => Ok(Column::Z),
+            _ => Err(Source::new(s).error(s, "X, Y or Z")),
         }
     }
 }
@@ -59,10 +82,24 @@ impl Shape {
     }
 }
 
-pub fn part_1(input: &str) -> u32 {
-    input.lines().fold(0, |acc, line| {
-        let (a, b) = line.split_once(' ').unwrap();
-        let (a, b) = (Shape::from_str(a).unwrap(), Shape::from_str(b).unwrap());
+pub fn generator(input: &str) -> Result<Vec<(Shape, Column)>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = source.split_once(line, " ")?;
+            Ok((source.parse_as(a)?, source.parse_as(b)?))
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[(Shape, Column)]) -> u32 {
+    input.iter().fold(0, |acc, &(a, b)| {
+        let b = match b {
+            Column::X => Rock,
+            Column::Y => Paper,
+            Column::Z => Scissors,
+        };
         let score = if a < b {
             6
         } else if a == b {
@@ -74,15 +111,12 @@ pub fn part_1(input: &str) -> u32 {
     })
 }
 
-pub fn part_2(input: &str) -> u32 {
-    input.lines().fold(0, |acc, line| {
-        let (a, b) = line.split_once(' ').unwrap();
-        let a = Shape::from_str(a).unwrap();
+pub fn part_2(input: &[(Shape, Column)]) -> u32 {
+    input.iter().fold(0, |acc, &(a, b)| {
         let score = match b {
-            "X" => u32::from(a.get_winner_shape()),
-            "Y" => 3 + u32::from(a),
-            "Z" => 6 + u32::from(a.get_loser_shape()),
-            _ => panic!("NO MATCH"),
+            Column::X => u32::from(a.get_winner_shape()),
+            Column::Y => 3 + u32::from(a),
+            Column::Z => 6 + u32::from(a.get_loser_shape()),
         };
         acc + score
     })
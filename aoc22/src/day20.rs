@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use aoc_utils::prelude::*;
+
 fn mix(data: &[i64], positions: &mut VecDeque<usize>) {
     for (idx, val) in data.iter().enumerate() {
         if *val == 0 {
@@ -29,11 +31,12 @@ fn calculate_coordinates(data: &[i64], positions: &VecDeque<usize>) -> i64 {
     })
 }
 
-pub fn generator(input: &str) -> Vec<i64> {
+pub fn generator(input: &str) -> Result<Vec<i64>, ParseError> {
+    let source = Source::new(input);
     input
         .trim()
         .split('\n')
-        .map(|num| num.parse::<i64>().unwrap())
+        .map(|num| source.parse(num, "a number"))
         .collect()
 }
 
@@ -49,3 +52,75 @@ pub fn part_2(input: &[i64]) -> i64 {
     (0..10).for_each(|_| mix(&data, &mut positions));
     calculate_coordinates(&data, &positions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::testing::check;
+
+    const INPUT: &str = r#"1
+2
+-3
+3
+-2
+0
+4
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 3, part_2 = 1623178306),
+    }
+
+    /// Mixes by moving each number one step at a time around the circle.
+    fn mix_stepwise(data: &[i64], order: &mut [usize]) {
+        for idx in 0..data.len() {
+            let steps = data[idx].unsigned_abs() as usize % (data.len() - 1);
+            for _ in 0..steps {
+                let pos = order.iter().position(|&i| i == idx).unwrap();
+                let next = if data[idx] > 0 {
+                    (pos + 1) % data.len()
+                } else {
+                    (pos + data.len() - 1) % data.len()
+                };
+                order.swap(pos, next);
+            }
+        }
+    }
+
+    /// The numbers of the circle, starting from 0.
+    fn from_zero<'a>(data: &[i64], order: impl IntoIterator<Item = &'a usize>) -> Vec<i64> {
+        let mut circle: Vec<i64> = order.into_iter().map(|&i| data[i]).collect();
+        let zero = circle.iter().position(|&n| n == 0).unwrap();
+        circle.rotate_left(zero);
+        circle
+    }
+
+    #[test]
+    fn mixes_like_moving_step_by_step() {
+        check(2000, |rng| {
+            let mut data = rng.vec(2..=12, |rng| match rng.range(0..=3) {
+                0 => rng.range(-1_000_000..=1_000_000),
+                _ => rng.range(-15..=15),
+            });
+            // The file holds a single 0.
+            data.retain(|&n| n != 0);
+            data.insert(rng.range(0..=data.len() as i64) as usize, 0);
+            if data.len() < 3 {
+                return;
+            }
+
+            let mut positions = (0..data.len()).collect();
+            let mut order: Vec<usize> = (0..data.len()).collect();
+            for _ in 0..rng.range(1..=3) {
+                mix(&data, &mut positions);
+                mix_stepwise(&data, &mut order);
+            }
+            assert_eq!(
+                from_zero(&data, &positions),
+                from_zero(&data, &order),
+                "{data:?}"
+            );
+        });
+    }
+}
@@ -1,106 +1,442 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-pub enum MonkeyYell {
-    Number(u64),
-    Operation(String),
+use aoc_utils::prelude::*;
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
 }
 
-impl FromStr for MonkeyYell {
-    type Err = std::convert::Infallible;
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v = s.trim().split(' ').collect::<Vec<_>>();
-        if v.len() == 1 {
-            Ok(MonkeyYell::Number(v[0].parse().unwrap()))
-        } else {
-            Ok(MonkeyYell::Operation(s.to_owned()))
+    /// # Panics
+    ///
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Division by zero");
+        let g = gcd(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
         }
     }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
 }
 
-fn contain_humn(monkey: &str, monkeys: &HashMap<&str, MonkeyYell>) -> bool {
-    match monkeys.get(monkey).unwrap() {
-        MonkeyYell::Number(_) => false,
-        MonkeyYell::Operation(s) => {
-            if s.contains("humn") {
-                true
-            } else {
-                let parts = s.split(' ').collect::<Vec<_>>();
-                contain_humn(parts[0], monkeys) || contain_humn(parts[2], monkeys)
-            }
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::new(n as i128, 1)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
         }
     }
 }
 
-fn get_monkey_number(monkey: &str, monkeys: &HashMap<&str, MonkeyYell>) -> u64 {
-    match monkeys.get(monkey).unwrap() {
-        MonkeyYell::Number(n) => *n,
-        MonkeyYell::Operation(s) => {
-            let parts = s.split(' ').collect::<Vec<_>>();
-            match parts[1] {
-                "+" => get_monkey_number(parts[0], monkeys) + get_monkey_number(parts[2], monkeys),
-                "-" => get_monkey_number(parts[0], monkeys) - get_monkey_number(parts[2], monkeys),
-                "*" => get_monkey_number(parts[0], monkeys) * get_monkey_number(parts[2], monkeys),
-                "/" => get_monkey_number(parts[0], monkeys) / get_monkey_number(parts[2], monkeys),
-                _ => panic!("Unknown operation"),
-            }
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{den}", self.num),
         }
     }
 }
 
-fn test_equality(monkey: &str, monkeys: &HashMap<&str, MonkeyYell>) -> u64 {
-    let mut res = 0;
-    if let MonkeyYell::Operation(s) = monkeys.get(monkey).unwrap() {
-        let mut v = s.split(' ').collect::<Vec<_>>();
-        let (mut left, mut right) = (v[0], v[2]);
-        v[1] = "-";
-        loop {
-            let (calc_side, next_branch) = if contain_humn(left, monkeys) || left == "humn" {
-                (right, left)
-            } else {
-                (left, right)
-            };
-
-            match (v[1], calc_side == right) {
-                ("+", _) => res -= get_monkey_number(calc_side, monkeys),
-                ("-", true) => res += get_monkey_number(calc_side, monkeys),
-                ("-", false) => res = get_monkey_number(calc_side, monkeys) - res,
-                ("*", _) => res /= get_monkey_number(calc_side, monkeys),
-                ("/", true) => res *= get_monkey_number(calc_side, monkeys),
-                ("/", false) => res = get_monkey_number(calc_side, monkeys) / res,
-                _ => panic!("Unknown operation"),
-            }
-            if [left, right].contains(&"humn") {
-                break;
-            }
-            if let MonkeyYell::Operation(s) = monkeys.get(next_branch).unwrap() {
-                v = s.split(' ').collect::<Vec<_>>();
-                (left, right) = (v[0], v[2]);
+/// The interned name of a monkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MonkeyId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    fn apply(self, a: Rational, b: Rational) -> Rational {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div => a / b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    Number(i64),
+    Operation(MonkeyId, Op, MonkeyId),
+}
+
+/// `a * x + b`, for an unknown `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub a: Rational,
+    pub b: Rational,
+}
+
+impl Linear {
+    fn constant(b: Rational) -> Self {
+        Linear {
+            a: Rational::ZERO,
+            b,
+        }
+    }
+
+    /// The value of the expression, if it does not depend on the unknown.
+    fn value(self) -> Option<Rational> {
+        self.a.is_zero().then_some(self.b)
+    }
+
+    fn combine(self, op: Op, other: Linear) -> Result<Linear, String> {
+        match (op, self.value(), other.value()) {
+            (Op::Add, ..) => Ok(Linear {
+                a: self.a + other.a,
+                b: self.b + other.b,
+            }),
+            (Op::Sub, ..) => Ok(Linear {
+                a: self.a - other.a,
+                b: self.b - other.b,
+            }),
+            (Op::Mul, Some(k), _) | (Op::Mul, _, Some(k)) => {
+                let scaled = if self.value().is_some() { other } else { self };
+                Ok(Linear {
+                    a: scaled.a * k,
+                    b: scaled.b * k,
+                })
             }
+            (Op::Div, _, Some(k)) if k.is_zero() => Err("division by zero".to_string()),
+            (Op::Div, _, Some(k)) => Ok(Linear {
+                a: self.a / k,
+                b: self.b / k,
+            }),
+            (Op::Mul, None, None) => Err("the unknown is multiplied by itself".to_string()),
+            (Op::Div, _, None) => Err("the unknown is in a divisor".to_string()),
         }
     }
-    res
 }
 
-pub fn generator(input: &str) -> HashMap<&str, MonkeyYell> {
-    let mut monkeys = HashMap::new();
-    input
-        .trim()
-        .split('\n')
-        .map(|line| {
-            let (name, monkey_yell) = line.split_once(':').unwrap();
-            monkeys.insert(name, monkey_yell.trim().parse::<MonkeyYell>().unwrap());
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}*x + {}", self.a, self.b)
+    }
+}
+
+/// The jobs of the monkeys, with their names interned.
+#[derive(Debug, Clone)]
+pub struct Monkeys {
+    names: Vec<String>,
+    ids: HashMap<String, MonkeyId>,
+    jobs: Vec<Job>,
+}
+
+impl Monkeys {
+    /// Returns the id of the monkey called `name`, adding it if needed.
+    fn intern(&mut self, name: &str) -> MonkeyId {
+        let next = MonkeyId(self.names.len());
+        *self.ids.entry(name.to_string()).or_insert_with(|| {
+            self.names.push(name.to_string());
+            next
         })
-        .for_each(drop);
+    }
+
+    pub fn find(&self, name: &str) -> Option<MonkeyId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, monkey: MonkeyId) -> &str {
+        &self.names[monkey.0]
+    }
+
+    pub fn job(&self, monkey: MonkeyId) -> Job {
+        self.jobs[monkey.0]
+    }
+
+    /// The exact number yelled by `monkey`.
+    pub fn evaluate(&self, monkey: MonkeyId) -> Rational {
+        match self.job(monkey) {
+            Job::Number(n) => n.into(),
+            Job::Operation(a, op, b) => op.apply(self.evaluate(a), self.evaluate(b)),
+        }
+    }
+
+    /// Simplifies the number yelled by `monkey` to a linear function of the
+    /// number yelled by `unknown`, ignoring the job of `unknown`.
+    pub fn linear(&self, monkey: MonkeyId, unknown: MonkeyId) -> Result<Linear, String> {
+        if monkey == unknown {
+            return Ok(Linear {
+                a: Rational::ONE,
+                b: Rational::ZERO,
+            });
+        }
+        match self.job(monkey) {
+            Job::Number(n) => Ok(Linear::constant(n.into())),
+            Job::Operation(a, op, b) => {
+                let (a, b) = (self.linear(a, unknown)?, self.linear(b, unknown)?);
+                a.combine(op, b)
+                    .map_err(|e| format!("{} is not linear: {e}", self.name(monkey)))
+            }
+        }
+    }
+
+    /// Finds the number `unknown` must yell for the two monkeys `root` waits
+    /// for to yell the same number.
+    pub fn solve(&self, root: MonkeyId, unknown: MonkeyId) -> Result<Rational, String> {
+        let Job::Operation(left, _, right) = self.job(root) else {
+            return Err(format!(
+                "{} does not wait for other monkeys",
+                self.name(root)
+            ));
+        };
+        if root == unknown {
+            return Err(format!("{} cannot be the unknown", self.name(root)));
+        }
+        let (left, right) = (self.linear(left, unknown)?, self.linear(right, unknown)?);
+        // a1 * x + b1 = a2 * x + b2
+        let a = left.a - right.a;
+        if a.is_zero() {
+            return Err(format!(
+                "the equality does not depend on {}",
+                self.name(unknown)
+            ));
+        }
+        Ok((right.b - left.b) / a)
+    }
+}
+
+pub fn generator(input: &str) -> Result<Monkeys, ParseError> {
+    let source = Source::new(input);
+    let mut monkeys = Monkeys {
+        names: Vec::new(),
+        ids: HashMap::new(),
+        jobs: Vec::new(),
+    };
+    // The first mention of each monkey, to point at those without a job
+    let mut mentions = Vec::new();
+    let mut parsed = Vec::new();
+    let mut intern = |monkeys: &mut Monkeys, name| {
+        let monkey = monkeys.intern(name);
+        if monkey.0 == mentions.len() {
+            mentions.push(name);
+        }
+        monkey
+    };
+    for line in input.trim().lines() {
+        let (name, job) = source.split_once(line, ": ")?;
+        let monkey = intern(&mut monkeys, name);
+        let job = match job.split(' ').collect::<Vec<_>>()[..] {
+            [n] => Job::Number(source.parse(n, "a number")?),
+            [a, op, b] => {
+                let op = match op {
+                    "+" => Op::Add,
+                    "-" => Op::Sub,
+                    "*" => Op::Mul,
+                    "/" => Op::Div,
+                    _ => return Err(source.error(op, "+, -, * or /")),
+                };
+                Job::Operation(intern(&mut monkeys, a), op, intern(&mut monkeys, b))
+            }
+            _ => return Err(source.error(job, "a number or an operation")),
+        };
+        parsed.push((monkey, job));
+    }
+    let mut jobs = vec![None; mentions.len()];
+    for (monkey, job) in parsed {
+        jobs[monkey.0] = Some(job);
+    }
+    monkeys.jobs = jobs
+        .into_iter()
+        .zip(mentions)
+        .map(|(job, name)| job.ok_or_else(|| source.error(name, "a monkey with a job")))
+        .collect::<Result<_, _>>()?;
+    Ok(monkeys)
+}
+
+fn monkey(monkeys: &Monkeys, name: &str) -> MonkeyId {
     monkeys
+        .find(name)
+        .unwrap_or_else(|| panic!("There is no monkey {name}"))
+}
 
+pub fn part_1(monkeys: &Monkeys) -> i128 {
+    let root = monkeys.evaluate(monkey(monkeys, "root"));
+    root.to_integer()
+        .unwrap_or_else(|| panic!("root yells {root}, which is not an integer"))
 }
 
-pub fn part_1(input: &HashMap<&str, MonkeyYell>) -> u64 {
-    get_monkey_number("root", input)
+pub fn part_2(monkeys: &Monkeys) -> i128 {
+    let (root, humn) = (monkey(monkeys, "root"), monkey(monkeys, "humn"));
+    let number = monkeys.solve(root, humn).unwrap_or_else(|e| panic!("{e}"));
+    number
+        .to_integer()
+        .unwrap_or_else(|| panic!("humn must yell {number}, which is not an integer"))
 }
 
-pub fn part_2(input: &HashMap<&str, MonkeyYell>) -> u64 {
-    test_equality("root", input)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 152, part_2 = 301),
+    }
+
+    /// Solves `root` for `unknown` in `input`, and checks that the solution
+    /// balances `root`.
+    fn solve(input: &str, unknown: &str) -> Result<Rational, String> {
+        let mut monkeys = generator(input).unwrap();
+        let (root, unknown) = (monkey(&monkeys, "root"), monkey(&monkeys, unknown));
+        let x = monkeys.solve(root, unknown)?;
+        if let (Some(n), Job::Operation(a, _, b)) = (x.to_integer(), monkeys.job(root)) {
+            monkeys.jobs[unknown.0] = Job::Number(n as i64);
+            assert_eq!(monkeys.evaluate(a), monkeys.evaluate(b));
+        }
+        Ok(x)
+    }
+
+    #[test]
+    fn inverts_subtraction_on_both_sides() {
+        // humn - 3 = 7 and 3 - humn = 7
+        let left = "root: a + b\na: humn - c\nb: 7\nc: 3\nhumn: 0";
+        assert_eq!(solve(left, "humn"), Ok(10.into()));
+        let right = "root: a + b\na: c - humn\nb: 7\nc: 3\nhumn: 0";
+        assert_eq!(solve(right, "humn"), Ok((-4).into()));
+        // With the unknown on the right of root
+        let swapped = "root: b + a\na: c - humn\nb: 7\nc: 3\nhumn: 0";
+        assert_eq!(solve(swapped, "humn"), Ok((-4).into()));
+    }
+
+    #[test]
+    fn inverts_division_on_both_sides() {
+        // humn / 4 = 6
+        let left = "root: a + b\na: humn / c\nb: 6\nc: 4\nhumn: 0";
+        assert_eq!(solve(left, "humn"), Ok(24.into()));
+        // (humn + 1) / 4 = 2 needs no integer division on the way
+        let exact = "root: a + b\na: d / c\nd: humn + e\ne: 1\nb: 2\nc: 4\nhumn: 0";
+        assert_eq!(solve(exact, "humn"), Ok(7.into()));
+        // humn / 4 = 3 / 2 has a fractional solution
+        let fraction = "root: a + b\na: humn / c\nb: f / g\nc: 4\nf: 3\ng: 2\nhumn: 0";
+        assert_eq!(solve(fraction, "humn"), Ok(6.into()));
+        let half = "root: a + b\na: humn / c\nb: f / g\nc: 2\nf: 3\ng: 4\nhumn: 0";
+        assert_eq!(solve(half, "humn"), Ok(Rational::new(3, 2)));
+        // 12 / humn = 3 cannot be made linear
+        let divisor = "root: a + b\na: c / humn\nb: 3\nc: 12\nhumn: 0";
+        assert!(solve(divisor, "humn").is_err());
+    }
+
+    #[test]
+    fn solves_for_any_monkey() {
+        // With humn yelling 301, dvpt balances the example when it yells 3
+        let mut monkeys = generator(INPUT).unwrap();
+        let humn = monkey(&monkeys, "humn");
+        monkeys.jobs[humn.0] = Job::Number(301);
+        let (root, dvpt) = (monkey(&monkeys, "root"), monkey(&monkeys, "dvpt"));
+        assert_eq!(monkeys.solve(root, dvpt), Ok(3.into()));
+        assert_eq!(solve(INPUT, "hmdt"), Ok(Rational::new(12, 5)));
+        assert!(solve(INPUT, "root").is_err());
+    }
+
+    #[test]
+    fn simplifies_to_linear_forms() {
+        let monkeys = generator(INPUT).unwrap();
+        let humn = monkey(&monkeys, "humn");
+        let pppw = monkeys.linear(monkey(&monkeys, "pppw"), humn).unwrap();
+        // (4 + 2 * (humn - 3)) / 4
+        assert_eq!(pppw.to_string(), "1/2*x + -1/2");
+        let sjmn = monkeys.linear(monkey(&monkeys, "sjmn"), humn).unwrap();
+        assert_eq!(sjmn.value(), Some(150.into()));
+        let squared = generator("root: humn * humn\nhumn: 1").unwrap();
+        let root = monkey(&squared, "root");
+        assert!(squared.linear(root, monkey(&squared, "humn")).is_err());
+    }
+
+    #[test]
+    fn rejects_monkeys_without_jobs() {
+        assert!(generator("root: a + b\na: 1").is_err());
+        assert!(generator("root: a % b\na: 1\nb: 2").is_err());
+    }
 }
@@ -1,6 +1,8 @@
 use std::str::FromStr;
 use std::vec::Vec;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Clone)]
 pub struct Map {
     map: Vec<Row>,
@@ -52,23 +54,28 @@ enum Action {
 }
 
 impl FromStr for Instructions {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let path = s.trim();
         let mut v = Vec::new();
         let mut num = 0;
-        for c in s.trim().chars() {
-            if c.is_alphabetic() {
-                v.push(Action::MoveForward(num));
-                num = 0;
-                v.push(match c {
-                    'L' => Action::TurnLeft,
-                    'R' => Action::TurnRight,
-                    _ => panic!("Unknown instruction: {c}"),
-                });
-            } else {
-                num = num * 10 + c.to_digit(10).unwrap() as usize;
-            };
+        for (i, c) in path.char_indices() {
+            if let Some(digit) = c.to_digit(10) {
+                num = num * 10 + digit as usize;
+                continue;
+            }
+            v.push(Action::MoveForward(num));
+            num = 0;
+            v.push(match c {
+                'L' => Action::TurnLeft,
+                'R' => Action::TurnRight,
+                _ => {
+                    let found = &path[i..i + c.len_utf8()];
+                    return Err(source.error(found, "a number of steps, 'L' or 'R'"));
+                }
+            });
         }
         v.push(Action::MoveForward(num));
         Ok(Instructions { instructions: v })
@@ -76,15 +83,19 @@ impl FromStr for Instructions {
 }
 
 impl FromStr for Row {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut v = vec![Tile::Void];
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             v.push(match c {
                 '#' => Tile::Solid,
                 '.' => Tile::Open,
-                _ => Tile::Void,
+                ' ' => Tile::Void,
+                _ => {
+                    let found = &s[i..i + c.len_utf8()];
+                    return Err(Source::new(s).error(found, "'#', '.' or ' '"));
+                }
             });
         }
         v.push(Tile::Void);
@@ -93,13 +104,14 @@ impl FromStr for Row {
 }
 
 impl Map {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
         let rows = input
             .lines()
-            .map(|l| l.parse().unwrap())
-            .collect::<Vec<Row>>();
+            .map(|l| source.parse_as(l))
+            .collect::<Result<Vec<Row>, _>>()?;
 
-        let width = rows.iter().map(|row| row.tiles.len()).max().unwrap();
+        let width = rows.iter().map(|row| row.tiles.len()).max().unwrap_or(0);
         let mut map = rows
             .iter()
             .map(|row| {
@@ -111,7 +123,10 @@ impl Map {
                 row.clone()
             })
             .collect::<Vec<_>>();
-        let start_x = rows[0].tiles.iter().position(|t| *t == Tile::Open).unwrap();
+        let start_x = rows
+            .first()
+            .and_then(|row| row.tiles.iter().position(|t| *t == Tile::Open))
+            .ok_or_else(|| source.error(input.lines().next().unwrap_or(input), "an open tile"))?;
         let player = Player {
             pos: Position { x: start_x, y: 1 },
             dir: Direction::East,
@@ -129,11 +144,11 @@ impl Map {
                 tiles: vec![Tile::Void; width],
             },
         );
-        Map { map, player }
+        Ok(Map { map, player })
     }
 }
 
-fn solve(instructions: &Instructions, map: &mut Map, warp: fn(&mut Map, Position)) -> u32 {
+fn solve(instructions: &Instructions, map: &mut Map, warp: impl Fn(&mut Map, Position)) -> u32 {
     for action in instructions.instructions.iter() {
         match action {
             Action::TurnLeft => {
@@ -204,106 +219,195 @@ fn go_through_void(map: &mut Map, old_pos: Position) {
     }
 }
 
-fn get_quadrant(pos: &Position) -> usize {
-    match (pos.x, pos.y) {
-        (x, y) if (51..=100).contains(&x) && (1..=50).contains(&y) => 1,
-        (x, y) if (101..=150).contains(&x) && (1..=50).contains(&y) => 2,
-        (x, y) if (51..=100).contains(&x) && (51..=100).contains(&y) => 3,
-        (x, y) if (1..=50).contains(&x) && (101..=150).contains(&y) => 4,
-        (x, y) if (51..=100).contains(&x) && (101..=150).contains(&y) => 5,
-        (x, y) if (1..=50).contains(&x) && (151..=200).contains(&y) => 6,
-        _ => panic!("Point not on Cube"),
-    }
+/// A unit vector along one of the axes of space.
+type Axis = [i64; 3];
+
+fn neg(a: Axis) -> Axis {
+    [-a[0], -a[1], -a[2]]
 }
 
-fn go_through_void_cube(map: &mut Map, old_pos: Position) {
-    let (x, y);
-    let mut dir = map.player.dir;
-    let quadrant = get_quadrant(&old_pos);
-    match (quadrant, map.player.dir) {
-        (1, Direction::North) => {
-            x = 1;
-            y = map.player.pos.x + 100;
-            dir = Direction::East;
-        }
-        (1, Direction::West) => {
-            x = 1;
-            y = 151 - map.player.pos.y;
-            dir = Direction::East;
-        }
-        (2, Direction::North) => {
-            x = map.player.pos.x - 100;
-            y = 200;
-        }
-        (2, Direction::East) => {
-            x = 100;
-            y = 151 - map.player.pos.y;
-            dir = Direction::West;
-        }
-        (2, Direction::South) => {
-            x = 100;
-            y = map.player.pos.x - 50;
-            dir = Direction::West;
-        }
-        (3, Direction::West) => {
-            x = map.player.pos.y - 50;
-            y = 101;
-            dir = Direction::South;
-        }
-        (3, Direction::East) => {
-            x = map.player.pos.y + 50;
-            y = 50;
-            dir = Direction::North;
-        }
-        (4, Direction::North) => {
-            x = 51;
-            y = map.player.pos.x + 50;
-            dir = Direction::East;
-        }
-        (4, Direction::West) => {
-            x = 51;
-            y = 151 - map.player.pos.y;
-            dir = Direction::East;
-        }
-        (5, Direction::East) => {
-            x = 150;
-            y = 151 - map.player.pos.y;
-            dir = Direction::West;
+fn dot(a: Axis, b: Axis) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::East,
+        Direction::South,
+        Direction::West,
+        Direction::North,
+    ];
+}
+
+/// One face of the folded cube: its cell in the net, and how the net's axes
+/// lie in space once folded.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    column: usize,
+    row: usize,
+    normal: Axis,
+    right: Axis,
+    down: Axis,
+}
+
+impl Face {
+    /// The direction in space of walking `dir` on this face.
+    fn axis(&self, dir: Direction) -> Axis {
+        match dir {
+            Direction::East => self.right,
+            Direction::South => self.down,
+            Direction::West => neg(self.right),
+            Direction::North => neg(self.down),
         }
-        (5, Direction::South) => {
-            x = 50;
-            y = map.player.pos.x + 100;
-            dir = Direction::West;
+    }
+
+    /// The neighbouring cell of the net in `dir`, folded down over the shared
+    /// edge.
+    fn fold(&self, dir: Direction, column: usize, row: usize) -> Face {
+        let (normal, right, down) = match dir {
+            Direction::East => (self.right, neg(self.normal), self.down),
+            Direction::South => (self.down, self.right, neg(self.normal)),
+            Direction::West => (neg(self.right), self.normal, self.down),
+            Direction::North => (neg(self.down), self.right, self.normal),
+        };
+        Face {
+            column,
+            row,
+            normal,
+            right,
+            down,
         }
-        (6, Direction::South) => {
-            x = map.player.pos.x + 100;
-            y = 1;
+    }
+}
+
+/// The map folded into a cube, used to glue together the edges of the net.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Detects the face size from the number of tiles and folds the net,
+    /// starting from the first face on the top row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is not the net of a cube.
+    fn fold(map: &Map) -> Self {
+        let tiles = map
+            .map
+            .iter()
+            .flat_map(|row| &row.tiles)
+            .filter(|t| **t != Tile::Void)
+            .count();
+        let size = (1..).find(|s| 6 * s * s >= tiles).unwrap();
+        assert_eq!(6 * size * size, tiles, "The map is not the net of a cube");
+
+        let on_net = |(column, row): (usize, usize)| {
+            map.map
+                .get(row * size + 1)
+                .and_then(|r| r.tiles.get(column * size + 1))
+                .is_some_and(|t| *t != Tile::Void)
+        };
+        let column = (0..).find(|&c| on_net((c, 0))).unwrap();
+        let mut faces = vec![Face {
+            column,
+            row: 0,
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut i = 0;
+        while let Some(&face) = faces.get(i) {
+            for dir in Direction::ALL {
+                let cell = match dir {
+                    Direction::East => Some((face.column + 1, face.row)),
+                    Direction::South => Some((face.column, face.row + 1)),
+                    Direction::West => face.column.checked_sub(1).map(|c| (c, face.row)),
+                    Direction::North => face.row.checked_sub(1).map(|r| (face.column, r)),
+                };
+                let Some(cell) = cell.filter(|&cell| on_net(cell)) else {
+                    continue;
+                };
+                if !faces.iter().any(|f| (f.column, f.row) == cell) {
+                    faces.push(face.fold(dir, cell.0, cell.1));
+                }
+            }
+            i += 1;
         }
-        (6, Direction::East) => {
-            x = map.player.pos.y - 100;
-            y = 150;
-            dir = Direction::North;
+
+        assert_eq!(faces.len(), 6, "The map is not the net of a cube");
+        for (i, face) in faces.iter().enumerate() {
+            assert!(
+                faces[..i].iter().all(|f| f.normal != face.normal),
+                "The net folds two faces onto each other"
+            );
         }
-        (6, Direction::West) => {
-            x = map.player.pos.y - 100;
-            y = 1;
-            dir = Direction::South;
+        Cube { size, faces }
+    }
+
+    fn face_of(&self, pos: Position) -> &Face {
+        let (column, row) = ((pos.x - 1) / self.size, (pos.y - 1) / self.size);
+        self.faces
+            .iter()
+            .find(|f| (f.column, f.row) == (column, row))
+            .expect("Point not on Cube")
+    }
+
+    /// Where walking off the net from `pos` in direction `dir` ends up on the
+    /// cube, and the direction the player faces there.
+    fn wrap(&self, pos: Position, dir: Direction) -> (Position, Direction) {
+        let s = self.size as i64;
+        let from = self.face_of(pos);
+        let to = self
+            .faces
+            .iter()
+            .find(|f| f.normal == from.axis(dir))
+            .unwrap();
+
+        // Place the centre of the tile in space, with the cube spanning -s..=s
+        // along every axis, then step over the edge onto the next face.
+        let i = (pos.x - 1 - from.column * self.size) as i64;
+        let j = (pos.y - 1 - from.row * self.size) as i64;
+        let mut centre = [0; 3];
+        for (k, c) in centre.iter_mut().enumerate() {
+            *c = s * from.normal[k]
+                + (2 * i + 1 - s) * from.right[k]
+                + (2 * j + 1 - s) * from.down[k]
+                - from.normal[k]
+                + to.normal[k];
         }
-        (_, _) => panic!("Not a possible void value"),
-    };
-    if map.map[y].tiles[x] != Tile::Solid {
-        map.player.pos = Position { x, y };
+        let i = ((dot(centre, to.right) + s - 1) / 2) as usize;
+        let j = ((dot(centre, to.down) + s - 1) / 2) as usize;
+        let pos = Position {
+            x: to.column * self.size + i + 1,
+            y: to.row * self.size + j + 1,
+        };
+        let dir = Direction::ALL
+            .into_iter()
+            .find(|&d| to.axis(d) == neg(from.normal))
+            .unwrap();
+        (pos, dir)
+    }
+}
+
+fn go_through_void_cube(map: &mut Map, old_pos: Position, cube: &Cube) {
+    let (pos, dir) = cube.wrap(old_pos, map.player.dir);
+    if map.map[pos.y].tiles[pos.x] != Tile::Solid {
+        map.player.pos = pos;
         map.player.dir = dir;
     } else {
         map.player.pos = old_pos;
     }
 }
 
-pub fn generator(input: &str) -> (Map, Instructions) {
-    let (map, instructions) = input.split_once("\n\n").unwrap();
-    let map = Map::new(map);
-    let i = instructions.parse::<Instructions>().unwrap();
-    (map, i)
+pub fn generator(input: &str) -> Result<(Map, Instructions), ParseError> {
+    let source = Source::new(input);
+    let (map, instructions) = source.split_once(input, "\n\n")?;
+    let map = Map::new(map)?;
+    let i = source.parse_as(instructions)?;
+    Ok((map, i))
 }
 
 pub fn part_1((map, instructions): &(Map, Instructions)) -> u32 {
@@ -313,17 +417,35 @@ pub fn part_1((map, instructions): &(Map, Instructions)) -> u32 {
 
 pub fn part_2((map, instructions): &(Map, Instructions)) -> u32 {
     let mut m = map.clone();
-    solve(instructions, &mut m, go_through_void_cube)
+    let cube = Cube::fold(map);
+    solve(instructions, &mut m, |map, old_pos| {
+        go_through_void_cube(map, old_pos, &cube)
+    })
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Direction, Instructions, Map, Player, Position};
+    use super::*;
+
+    const INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
 
     fn get_map() -> Map {
-        let input = include_str!("../../input/2022/day22.test");
-        let (map, _) = input.split_once("\n\n").unwrap();
-        Map::new(map)
+        let (map, _) = INPUT.split_once("\n\n").unwrap();
+        Map::new(map).unwrap()
     }
 
     #[test]
@@ -416,33 +538,109 @@ mod test {
     }
 
     #[test]
-    fn test_get_quadrant() {
-        let pos = Position { x: 75, y: 25 };
-        assert_eq!(super::get_quadrant(&pos), 1);
-
-        let pos = Position { x: 125, y: 25 };
-        assert_eq!(super::get_quadrant(&pos), 2);
-
-        let pos = Position { x: 75, y: 75 };
-        assert_eq!(super::get_quadrant(&pos), 3);
-
-        let pos = Position { x: 25, y: 125 };
-        assert_eq!(super::get_quadrant(&pos), 4);
-
-        let pos = Position { x: 75, y: 125 };
-        assert_eq!(super::get_quadrant(&pos), 5);
+    fn test_fold_example() {
+        let map = get_map();
+        let cube = Cube::fold(&map);
+        assert_eq!(cube.size, 4);
+
+        // A -> B from the puzzle description
+        let (pos, dir) = cube.wrap(Position { x: 12, y: 6 }, Direction::East);
+        assert_eq!((pos, dir), (Position { x: 15, y: 9 }, Direction::South));
+
+        // C -> D from the puzzle description
+        let (pos, dir) = cube.wrap(Position { x: 11, y: 12 }, Direction::South);
+        assert_eq!((pos, dir), (Position { x: 2, y: 8 }, Direction::North));
+    }
 
-        let pos = Position { x: 25, y: 175 };
-        assert_eq!(super::get_quadrant(&pos), 6);
+    /// The 11 nets of a cube, one character per face.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    /// Blows up a net to an open map with faces of `size` tiles.
+    fn net_map(net: &str, size: usize) -> Map {
+        let mut rows = Vec::new();
+        for line in net.lines() {
+            let row = line
+                .chars()
+                .map(|c| if c == '#' { "." } else { " " }.repeat(size))
+                .collect::<String>();
+            rows.extend(std::iter::repeat_n(row, size));
+        }
+        Map::new(&rows.join("\n")).unwrap()
+    }
 
-        let pos = Position { x: 51, y: 53 };
-        assert_eq!(super::get_quadrant(&pos), 3);
+    #[test]
+    fn test_fold_nets() {
+        let size = 3;
+        for net in NETS {
+            let mut map = net_map(net, size);
+            let cube = Cube::fold(&map);
+            assert_eq!(cube.size, size, "{net}");
+            let warp = |map: &mut Map, old_pos| go_through_void_cube(map, old_pos, &cube);
+            let around = format!("{}", 4 * size).parse::<Instructions>().unwrap();
+            let back = "1".parse::<Instructions>().unwrap();
+
+            for y in 1..map.map.len() - 1 {
+                for x in 1..map.map[y].tiles.len() - 1 {
+                    if map.map[y].tiles[x] == Tile::Void {
+                        continue;
+                    }
+                    for dir in Direction::ALL {
+                        let start = Player {
+                            pos: Position { x, y },
+                            dir,
+                        };
+
+                        // Walking straight ahead circles the cube once.
+                        map.player = start;
+                        solve(&around, &mut map, warp);
+                        assert_eq!(map.player, start, "{net}");
+
+                        // Crossing an edge and turning around leads back.
+                        let edge = match dir {
+                            Direction::East => map.map[y].tiles[x + 1],
+                            Direction::South => map.map[y + 1].tiles[x],
+                            Direction::West => map.map[y].tiles[x - 1],
+                            Direction::North => map.map[y - 1].tiles[x],
+                        };
+                        if edge != Tile::Void {
+                            continue;
+                        }
+                        let (pos, dir) = cube.wrap(start.pos, start.dir);
+                        let reverse = Direction::ALL[(dir as usize + 2) % 4];
+                        map.player = Player { pos, dir: reverse };
+                        solve(&back, &mut map, warp);
+                        assert_eq!(map.player.pos, start.pos, "{net}");
+                        assert_eq!(
+                            map.player.dir,
+                            Direction::ALL[(start.dir as usize + 2) % 4],
+                            "{net}"
+                        );
+                    }
+                }
+            }
+        }
     }
 
     #[test]
-    #[should_panic]
-    fn test_get_quadrant_panic() {
-        let pos = Position { x: 0, y: 0 };
-        super::get_quadrant(&pos);
+    #[should_panic(expected = "not the net of a cube")]
+    fn test_fold_not_a_cube() {
+        Cube::fold(&net_map("####\n####", 2));
+    }
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 6032, part_2 = 5031),
     }
 }
@@ -3,6 +3,9 @@ use std::{
     iter::successors,
 };
 
+use aoc_utils::prelude::*;
+use aoc_utils::viz::{self, Cell, Color, Frame};
+
 #[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Pos {
     x: i32,
@@ -133,8 +136,14 @@ fn update_positions(positions: &mut HashSet<Pos>, proposals: &mut HashMap<Pos, P
 fn simulate(max_iterations: Option<u32>, positions: &mut HashSet<Pos>, mut dir: Dir) -> u32 {
     let mut proposals = HashMap::new();
     let mut num = 1;
+    if viz::is_active() {
+        viz::show(&Grove::new(positions));
+    }
     generate_proposals(positions, &mut proposals, dir);
     while update_positions(positions, &mut proposals) {
+        if viz::is_active() {
+            viz::show(&Grove::new(positions));
+        }
         if num == max_iterations.unwrap_or(u32::MAX) {
             break;
         }
@@ -167,18 +176,42 @@ fn get_bounding_box(positions: &HashSet<Pos>) -> (Pos, Pos) {
     (Pos { x: min_x, y: min_y }, Pos { x: max_x, y: max_y })
 }
 
-#[allow(dead_code)]
-fn print_positions(positions: &HashSet<Pos>) {
-    let (min_pos, max_pos) = get_bounding_box(positions);
-    for y in min_pos.y..=max_pos.y {
-        for x in min_pos.x..=max_pos.x {
-            if positions.contains(&Pos { x, y }) {
-                print!("#");
-            } else {
-                print!(".");
-            }
+/// The elves within their bounding box.
+struct Grove<'a> {
+    positions: &'a HashSet<Pos>,
+    min: Pos,
+    max: Pos,
+}
+
+impl<'a> Grove<'a> {
+    fn new(positions: &'a HashSet<Pos>) -> Self {
+        let (min, max) = get_bounding_box(positions);
+        Grove {
+            positions,
+            min,
+            max,
+        }
+    }
+}
+
+impl Frame for Grove<'_> {
+    fn size(&self) -> (usize, usize) {
+        (
+            (self.max.x - self.min.x + 1) as usize,
+            (self.max.y - self.min.y + 1) as usize,
+        )
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let pos = Pos {
+            x: self.min.x + x as i32,
+            y: self.min.y + y as i32,
+        };
+        if self.positions.contains(&pos) {
+            Cell::new('#').color(Color::Green).bold()
+        } else {
+            Cell::new('.').color(Color::Gray)
         }
-        println!();
     }
 }
 
@@ -193,20 +226,20 @@ fn get_empty_positions(positions: &HashSet<Pos>) -> u32 {
         .sum()
 }
 
-pub fn generator(input: &str) -> HashSet<Pos> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| c == &'#')
-                .map(move |(x, _)| Pos {
-                    x: x as i32,
-                    y: y as i32,
-                })
-        })
-        .collect()
+pub fn generator(input: &str) -> Result<HashSet<Pos>, ParseError> {
+    let grid = Source::new(input).grid(
+        input,
+        |(x, y), c| match c {
+            '#' => Some(Some(Pos {
+                x: x as i32,
+                y: y as i32,
+            })),
+            '.' => Some(None),
+            _ => None,
+        },
+        "'#' or '.'",
+    )?;
+    Ok(grid.into_iter().flatten().flatten().collect())
 }
 
 pub fn part_1(input: &HashSet<Pos>) -> u32 {
@@ -1,18 +1,128 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
+use std::str::FromStr;
 
-const MAX_ROWS: usize = 32;
+use aoc_utils::prelude::*;
 
-struct Grid {
+/// The waypoints visited in turn in part 2, such as `goal,start,goal`.
+static WAYPOINTS: Param<Waypoints> = Param::new(
+    "waypoints",
+    Waypoints(Cow::Borrowed(&[
+        Waypoint::Goal,
+        Waypoint::Start,
+        Waypoint::Goal,
+    ])),
+);
+
+const WORD: usize = u64::BITS as usize;
+
+/// A grid of bits stored row by row, with each row split over `words` words
+/// so the valley can be arbitrarily wide.
+#[derive(Clone)]
+struct Bits {
+    words: usize,
+    data: Vec<u64>,
+}
+
+impl Bits {
+    fn new(width: usize, height: usize) -> Self {
+        let words = width.div_ceil(WORD);
+        Bits {
+            words,
+            data: vec![0; words * height],
+        }
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words..(y + 1) * self.words]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.data[y * self.words..(y + 1) * self.words]
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.row(y)[x / WORD] & (1 << (x % WORD)) != 0
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        self.row_mut(y)[x / WORD] |= 1 << (x % WORD);
+    }
+
+    fn clear(&mut self, x: usize, y: usize) {
+        self.row_mut(y)[x / WORD] &= !(1 << (x % WORD));
+    }
+
+    fn any(&self, y: usize, mask: &Bits) -> bool {
+        self.row(y).iter().zip(mask.row(y)).any(|(a, b)| a & b != 0)
+    }
+
+    /// Moves every bit of each row in `rows` one column east.
+    fn shift_east(&mut self, rows: std::ops::Range<usize>) {
+        for y in rows {
+            let mut carry = 0;
+            for word in self.row_mut(y) {
+                let next = *word >> (WORD - 1);
+                *word = (*word << 1) | carry;
+                carry = next;
+            }
+        }
+    }
+
+    /// Moves every bit of each row in `rows` one column west.
+    fn shift_west(&mut self, rows: std::ops::Range<usize>) {
+        for y in rows {
+            let mut carry = 0;
+            for word in self.row_mut(y).iter_mut().rev() {
+                let next = *word << (WORD - 1);
+                *word = (*word >> 1) | carry;
+                carry = next;
+            }
+        }
+    }
+}
+
+/// Where the expedition is heading next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Waypoint {
+    Start,
+    Goal,
+}
+
+/// A list of waypoints, written as `start` and `goal` separated by commas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Waypoints(Cow<'static, [Waypoint]>);
+
+impl FromStr for Waypoints {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let waypoints = s
+            .split(',')
+            .map(|waypoint| match waypoint.trim() {
+                "start" => Ok(Waypoint::Start),
+                "goal" => Ok(Waypoint::Goal),
+                _ => Err(source.error(waypoint, "start or goal")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Waypoints(waypoints.into()))
+    }
+}
+
+#[derive(Clone)]
+pub struct Grid {
     width: usize,
     height: usize,
-    walls: [u128; MAX_ROWS],
-    start: (usize, u128),
-    goal: (usize, u128),
-    bliz_north: [u128; MAX_ROWS],
-    bliz_south: [u128; MAX_ROWS],
-    bliz_west: [u128; MAX_ROWS],
-    bliz_east: [u128; MAX_ROWS],
-    presence: [u128; MAX_ROWS],
+    walls: Bits,
+    start: Bits,
+    goal: Bits,
+    bliz_north: Bits,
+    bliz_south: Bits,
+    bliz_west: Bits,
+    bliz_east: Bits,
+    presence: Bits,
+    next: Bits,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -26,32 +136,20 @@ impl Debug for Grid {
         writeln!(f)?;
         for y in 0..self.height {
             for x in 0..self.width {
+                let winds = [
+                    (&self.bliz_north, '^'),
+                    (&self.bliz_south, 'v'),
+                    (&self.bliz_west, '<'),
+                    (&self.bliz_east, '>'),
+                ];
                 // Count number of blizzards at this position
-                let mut blizzards = 0;
-                if self.bliz_north[y] & (1 << x) != 0 {
-                    blizzards += 1;
-                }
-                if self.bliz_south[y] & (1 << x) != 0 {
-                    blizzards += 1;
-                }
-                if self.bliz_west[y] & (1 << x) != 0 {
-                    blizzards += 1;
-                }
-                if self.bliz_east[y] & (1 << x) != 0 {
-                    blizzards += 1;
-                }
+                let blizzards = winds.iter().filter(|(b, _)| b.get(x, y)).count();
                 if blizzards > 1 {
                     write!(f, "{blizzards}")?;
-                } else if self.walls[y] & (1 << x) != 0 {
+                } else if self.walls.get(x, y) {
                     write!(f, "#")?;
-                } else if self.bliz_north[y] & (1 << x) != 0 {
-                    write!(f, "^")?;
-                } else if self.bliz_south[y] & (1 << x) != 0 {
-                    write!(f, "v")?;
-                } else if self.bliz_west[y] & (1 << x) != 0 {
-                    write!(f, "<")?;
-                } else if self.bliz_east[y] & (1 << x) != 0 {
-                    write!(f, ">")?;
+                } else if let Some((_, c)) = winds.iter().find(|(b, _)| b.get(x, y)) {
+                    write!(f, "{c}")?;
                 } else {
                     write!(f, ".")?;
                 }
@@ -66,126 +164,215 @@ impl Grid {
     fn new(input: &str) -> Self {
         let width = input.lines().next().unwrap().len();
         let height = input.lines().count();
-        let mut start: (usize, u128) = (0, 0);
-        let mut goal: (usize, u128) = (height - 1, 0);
-        let mut presence = [0u128; MAX_ROWS];
-        let mut walls = [0u128; MAX_ROWS];
-        let mut bliz_north = [0u128; MAX_ROWS];
-        let mut bliz_south = [0u128; MAX_ROWS];
-        let mut bliz_east = [0u128; MAX_ROWS];
-        let mut bliz_west = [0u128; MAX_ROWS];
-
-        input.lines().enumerate().for_each(|(y, line)| {
-            line.chars().enumerate().for_each(|(x, c)| {
+        let empty = Bits::new(width, height);
+        let mut grid = Grid {
+            width,
+            height,
+            walls: empty.clone(),
+            start: empty.clone(),
+            goal: empty.clone(),
+            bliz_north: empty.clone(),
+            bliz_south: empty.clone(),
+            bliz_west: empty.clone(),
+            bliz_east: empty.clone(),
+            presence: empty.clone(),
+            next: empty,
+        };
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
                 match c {
-                    '^' => bliz_north[y] |= 1 << x,
-                    'v' => bliz_south[y] |= 1 << x,
-                    '<' => bliz_west[y] |= 1 << x,
-                    '>' => bliz_east[y] |= 1 << x,
-                    '#' => walls[y] |= 1 << x,
+                    '^' => grid.bliz_north.set(x, y),
+                    'v' => grid.bliz_south.set(x, y),
+                    '<' => grid.bliz_west.set(x, y),
+                    '>' => grid.bliz_east.set(x, y),
+                    '#' => grid.walls.set(x, y),
                     _ => (),
                 };
                 if y == 0 && c == '.' {
-                    start.1 |= 1 << x;
+                    grid.start.set(x, y);
                 }
                 if y == height - 1 && c == '.' {
-                    goal.1 |= 1 << x;
+                    grid.goal.set(x, y);
                 }
-            })
-        });
-        presence[start.0] = start.1;
-        Grid {
-            width,
-            height,
-            walls,
-            start,
-            goal,
-            bliz_north,
-            bliz_south,
-            bliz_west,
-            bliz_east,
-            presence,
+            }
         }
+        grid.presence = grid.start.clone();
+        grid
     }
 
     fn update_blizzards(&mut self) {
         // Simulate blizzards for this turn
-        self.bliz_north[1..(self.height - 1)].rotate_left(1);
-        self.bliz_south[1..(self.height - 1)].rotate_right(1);
-        for r in 1..(self.height - 1) {
-            self.bliz_west[r] = self.blow_west(self.bliz_west[r], self.walls[r]);
-            self.bliz_east[r] = self.blow_east(self.bliz_east[r], self.walls[r]);
-        }
-    }
+        let (words, inner) = (self.presence.words, 1..self.height - 1);
+        let rows = inner.start * words..inner.end * words;
+        self.bliz_north.data[rows.clone()].rotate_left(words);
+        self.bliz_south.data[rows].rotate_right(words);
 
-    fn blow_west(&self, before: u128, walls: u128) -> u128 {
-        let wind = before >> 1;
-        if wind & walls != 0 {
-            wind | (walls >> 1)
-        } else {
-            wind
-        }
-    }
-
-    fn blow_east(&self, row: u128, walls: u128) -> u128 {
-        let wind = row << 1;
-        if wind & walls != 0 {
-            wind | (walls << 1)
-        } else {
-            wind
+        // Blizzards blown into the side walls come back in on the other side
+        self.bliz_west.shift_west(inner.clone());
+        self.bliz_east.shift_east(inner.clone());
+        for y in inner {
+            if self.bliz_west.get(0, y) {
+                self.bliz_west.clear(0, y);
+                self.bliz_west.set(self.width - 2, y);
+            }
+            if self.bliz_east.get(self.width - 1, y) {
+                self.bliz_east.clear(self.width - 1, y);
+                self.bliz_east.set(1, y);
+            }
         }
     }
 
     fn step(&mut self) {
         self.update_blizzards();
-        let mut above = 0;
+        let words = self.presence.words;
         for row in 0..self.height {
-            let current = self.presence[row];
-            self.presence[row] |= above | (current << 1) | (current >> 1);
-            if row + 1 < self.height {
-                self.presence[row] |= self.presence[row + 1];
+            for w in 0..words {
+                let at = |y: usize, w: usize| self.presence.data[y * words + w];
+                let current = at(row, w);
+                let mut reach = current | (current << 1) | (current >> 1);
+                if w > 0 {
+                    reach |= at(row, w - 1) >> (WORD - 1);
+                }
+                if w + 1 < words {
+                    reach |= at(row, w + 1) << (WORD - 1);
+                }
+                if row > 0 {
+                    reach |= at(row - 1, w);
+                }
+                if row + 1 < self.height {
+                    reach |= at(row + 1, w);
+                }
+                let i = row * words + w;
+                let obstacle = self.walls.data[i]
+                    | self.bliz_north.data[i]
+                    | self.bliz_south.data[i]
+                    | self.bliz_west.data[i]
+                    | self.bliz_east.data[i];
+                self.next.data[i] = reach & !obstacle;
             }
-            above = current;
-            let obstacle = self.walls[row]
-                | self.bliz_north[row]
-                | self.bliz_south[row]
-                | self.bliz_west[row]
-                | self.bliz_east[row];
-            self.presence[row] &= !obstacle;
         }
+        std::mem::swap(&mut self.presence, &mut self.next);
     }
 
-    fn is_goal(&self) -> bool {
-        self.presence[self.goal.0] & self.goal.1 != 0
-    }
-
-    fn steps_to_goal(&mut self) -> u16 {
+    fn steps_to(&mut self, waypoint: Waypoint) -> u32 {
+        let (target, y) = match waypoint {
+            Waypoint::Start => (&self.start, 0),
+            Waypoint::Goal => (&self.goal, self.height - 1),
+        };
+        let target = target.clone();
         let mut steps = 0;
-        while !self.is_goal() {
+        while !self.presence.any(y, &target) {
             self.step();
             steps += 1;
         }
+        self.presence = target;
         steps
     }
 
-    fn reset_and_swap_goal(&mut self) {
-        std::mem::swap(&mut self.start, &mut self.goal);
-        self.presence.fill(0u128);
-        self.presence[self.start.0] = self.start.1;
+    /// Walks to each of the `waypoints` in turn, starting at the start, and
+    /// returns the total number of minutes.
+    fn journey(&mut self, waypoints: &[Waypoint]) -> u32 {
+        waypoints.iter().map(|&w| self.steps_to(w)).sum()
     }
 }
 
-pub fn part_1(input: &str) -> u16 {
-    let mut grid = Grid::new(input);
-    grid.steps_to_goal()
+/// Checks the shape of the valley before building its [`Grid`].
+pub fn generator(input: &str) -> Result<Grid, ParseError> {
+    let source = Source::new(input);
+    let lines = input.lines().collect::<Vec<_>>();
+    let [first, _, .., last] = lines[..] else {
+        return Err(source.error_after(input.trim_end(), "a valley of at least three rows"));
+    };
+    if first.len() < 3 {
+        return Err(source.error(first, "a row of at least three tiles"));
+    }
+    for line in &lines {
+        if let Some((i, c)) = line.char_indices().find(|&(_, c)| !"#.^v<>".contains(c)) {
+            let tile = &line[i..i + c.len_utf8()];
+            return Err(source.error(tile, "a wall, ground or blizzard"));
+        }
+        if line.len() != first.len() {
+            return Err(source.error(line, &format!("a row of {} tiles", first.len())));
+        }
+    }
+    if !first.contains('.') {
+        return Err(source.error(first, "an entrance"));
+    }
+    if !last.contains('.') {
+        return Err(source.error(last, "an exit"));
+    }
+    Ok(Grid::new(input))
+}
+
+pub fn part_1(grid: &Grid) -> u32 {
+    let mut grid = grid.clone();
+    grid.journey(&[Waypoint::Goal])
 }
 
-pub fn part_2(input: &str) -> u16 {
-    let mut grid = Grid::new(input);
-    let first = grid.steps_to_goal();
-    grid.reset_and_swap_goal();
-    let second = grid.steps_to_goal();
-    grid.reset_and_swap_goal();
-    let third = grid.steps_to_goal();
-    first + second + third
+pub fn part_2(grid: &Grid) -> u32 {
+    let mut grid = grid.clone();
+    grid.journey(&WAYPOINTS.get().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 18, part_2 = 54),
+        one_trip { waypoints = "goal" }: (INPUT, part_2 = 18),
+        back_and_forth { waypoints = "goal,start,goal,start,goal" }: (INPUT, part_2 = 90),
+        wait_at_the_goal { waypoints = "start,goal,goal,start" }: (INPUT, part_2 = 41),
+    }
+
+    /// An open valley without blizzards, spanning several words per row.
+    fn open_valley(width: usize, height: usize) -> String {
+        let mut rows = vec![format!("#.{}", "#".repeat(width - 2))];
+        for _ in 1..height - 1 {
+            rows.push(format!("#{}#", ".".repeat(width - 2)));
+        }
+        rows.push(format!("{}.#", "#".repeat(width - 2)));
+        rows.join("\n")
+    }
+
+    #[test]
+    fn wide_valley() {
+        let (width, height) = (200, 40);
+        let grid = generator(&open_valley(width, height)).unwrap();
+        let distance = (width - 3 + height - 1) as u32;
+        assert_eq!(part_1(&grid), distance);
+        assert_eq!(part_2(&grid), 3 * distance);
+    }
+
+    #[test]
+    fn rejects_invalid_valleys() {
+        assert!(generator("#.#\n#.#\n").is_err());
+        assert!(generator("#.##\n#..#\n#.#\n").is_err());
+        assert!(generator("#.#\n#x#\n#.#\n").is_err());
+        assert!(generator("###\n#.#\n#.#\n").is_err());
+        assert!(generator("#.#\n#.#\n###\n").is_err());
+    }
+
+    #[test]
+    fn blizzards_cross_words() {
+        // A blizzard wrapping around a row wider than one word
+        let mut input = open_valley(130, 3);
+        input.replace_range(132..133, "<");
+        let mut grid = Grid::new(&input);
+        let start = format!("{grid:?}");
+        for _ in 0..128 {
+            grid.update_blizzards();
+        }
+        assert_eq!(format!("{grid:?}"), start);
+    }
 }
@@ -1,15 +1,18 @@
 use std::{iter::Sum, str::FromStr};
-struct Snafu {
+
+use aoc_utils::prelude::*;
+
+pub struct Snafu {
     base_10: isize,
     base_snafu: String,
 }
 
 impl FromStr for Snafu {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut num = 0;
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             match c {
                 '0'..='2' => {
                     num = num * 5 + c.to_digit(5).unwrap() as isize;
@@ -20,7 +23,10 @@ impl FromStr for Snafu {
                 '=' => {
                     num = num * 5 - 2;
                 }
-                _ => unreachable!(),
+                _ => {
+                    let digit = &s[i..i + c.len_utf8()];
+                    return Err(Source::new(s).error(digit, "a SNAFU digit"));
+                }
             }
         }
         Ok(Snafu {
@@ -29,8 +35,8 @@ impl FromStr for Snafu {
         })
     }
 }
-impl Sum for Snafu {
-    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
         let num = iter.map(|s| s.base_10).sum();
         Snafu {
             base_10: num,
@@ -60,10 +66,11 @@ fn to_snafu(base_10: isize) -> String {
     }
 }
 
-pub fn solution(input: &str) -> String {
-    input
-        .lines()
-        .map(|l| l.parse::<Snafu>().unwrap())
-        .sum::<Snafu>()
-        .base_snafu
+pub fn generator(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|l| source.parse_as(l)).collect()
+}
+
+pub fn solution(input: &[Snafu]) -> String {
+    input.iter().sum::<Snafu>().base_snafu
 }
@@ -1,48 +1,36 @@
 use std::collections::HashSet;
 
-pub fn part_1(input: &str) -> u32 {
-    input.lines().fold(0, |acc, line| {
-        let char_count = &line.chars().count();
-        let mut first_compartment_set = HashSet::new();
-        let mut score = 0;
-        for (i, char) in line.chars().enumerate() {
-            if i < char_count / 2 {
-                first_compartment_set.insert(char.to_owned());
-            } else if first_compartment_set.contains(&char) {
-                score += if char.is_lowercase() {
-                    char as u32 - 96
-                } else {
-                    char as u32 - 38
-                };
-                break;
-            }
-        }
+use aoc_utils::prelude::*;
+
+/// The priorities of the items in each rucksack.
+pub fn generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let priority = |_, item: char| match item {
+        'a'..='z' => Some(item as u32 - 96),
+        'A'..='Z' => Some(item as u32 - 38),
+        _ => None,
+    };
+    Source::new(input).grid(input, priority, "an item")
+}
+
+pub fn part_1(input: &[Vec<u32>]) -> u32 {
+    input.iter().fold(0, |acc, rucksack| {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let first_compartment_set = first.iter().collect::<HashSet<_>>();
+        let score = second
+            .iter()
+            .find(|item| first_compartment_set.contains(item))
+            .map_or(0, |&item| item);
         acc + score
     })
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let mut i: u8 = 0;
-    let mut intersection_set = HashSet::new();
-    input.lines().fold(0, |acc, line| {
-        let mut score = 0;
-        if i == 0 {
-            intersection_set = line.chars().collect();
-        } else {
-            let mut _next_set = HashSet::new();
-            _next_set = line.chars().collect();
-            intersection_set = intersection_set.intersection(&_next_set).copied().collect();
-        }
-        i += 1;
-        if i == 3 {
-            let char = intersection_set.iter().next().unwrap();
-            score += if char.is_lowercase() {
-                *char as u32 - 96
-            } else {
-                *char as u32 - 38
-            };
-            i = 0;
-        }
-        acc + score
+pub fn part_2(input: &[Vec<u32>]) -> u32 {
+    input.chunks_exact(3).fold(0, |acc, group| {
+        let intersection_set = group
+            .iter()
+            .map(|rucksack| rucksack.iter().copied().collect::<HashSet<_>>())
+            .reduce(|a, b| a.intersection(&b).copied().collect())
+            .unwrap_or_default();
+        acc + intersection_set.iter().next().map_or(0, |&item| item)
     })
 }
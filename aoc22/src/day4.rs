@@ -1,14 +1,21 @@
-pub fn generator(input: &str) -> Vec<(u32, u32, u32, u32)> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<(u32, u32, u32, u32)>, ParseError> {
+    let source = Source::new(input);
     input
         .trim()
         .lines()
         .map(|line| {
-            let (a, b) = line.split_once(',').unwrap();
-            let mut a = a.split('-').map(|num| num.parse::<u32>().unwrap());
-            let mut b = b.split('-').map(|num| num.parse::<u32>().unwrap());
-            let (a_min, a_max) = (a.next().unwrap(), a.next().unwrap());
-            let (b_min, b_max) = (b.next().unwrap(), b.next().unwrap());
-            (a_min, a_max, b_min, b_max)
+            let (a, b) = source.split_once(line, ",")?;
+            let range = |range: &str| {
+                let (min, max) = source.split_once(range, "-")?;
+                Ok::<_, ParseError>((
+                    source.parse(min, "a section ID")?,
+                    source.parse(max, "a section ID")?,
+                ))
+            };
+            let ((a_min, a_max), (b_min, b_max)) = (range(a)?, range(b)?);
+            Ok((a_min, a_max, b_min, b_max))
         })
         .collect()
 }
@@ -1,28 +1,33 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Clone)]
-struct Dock {
+pub struct Dock {
     stacks: Vec<Vec<char>>,
 }
 
 impl FromStr for Dock {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s
-            .split('\n')
-            .collect::<Vec<_>>()
-            .iter()
-            .rev()
-            .skip(1)
-            .map(|line| line.chars().skip(1).step_by(4).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
-        let mut stacks = vec![Vec::<char>::new(); lines[0].len()];
-        for line in lines.iter() {
-            for (i, c) in line.iter().enumerate() {
-                if *c != ' ' {
-                    stacks[i].push(*c);
+        let source = Source::new(s);
+        let mut lines = s.lines().rev();
+        let numbers = lines
+            .next()
+            .ok_or_else(|| source.error_after(s, "stack numbers"))?;
+        let n_stacks = numbers.split_whitespace().count();
+        let mut stacks = vec![Vec::<char>::new(); n_stacks];
+        for line in lines {
+            for (i, (j, c)) in line.char_indices().skip(1).step_by(4).enumerate() {
+                if c != ' ' {
+                    stacks
+                        .get_mut(i)
+                        .ok_or_else(|| {
+                            let expected = format!("at most {n_stacks} stacks");
+                            source.error(&line[j..j + c.len_utf8()], &expected)
+                        })?
+                        .push(c);
                 }
             }
         }
@@ -30,53 +35,78 @@ impl FromStr for Dock {
     }
 }
 
-impl Dock {
-    fn use_crate_mover_9000(&mut self, instructions: &str) {
-        for instruction in instructions.trim().split('\n') {
-            let (n_crates, from_stack, to_stack) = Dock::parse_instruction(instruction);
+/// A rearrangement step, with stacks numbered from 1.
+#[derive(Debug, Clone, Copy)]
+pub struct Move {
+    n_crates: usize,
+    from_stack: usize,
+    to_stack: usize,
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let split = s
+            .strip_prefix("move ")
+            .ok_or_else(|| source.error(s, "\"move \""))?;
+        let (n_crates, split) = source.split_once(split, " from ")?;
+        let (from_stack, to_stack) = source.split_once(split, " to ")?;
+        Ok(Move {
+            n_crates: source.parse(n_crates, "a number of crates")?,
+            from_stack: source.parse(from_stack, "a stack number")?,
+            to_stack: source.parse(to_stack, "a stack number")?,
+        })
+    }
+}
 
-            for _ in 0..n_crates {
-                let item = self.stacks[from_stack - 1].pop().unwrap();
-                self.stacks[to_stack - 1].push(item);
+impl Dock {
+    fn use_crate_mover_9000(&mut self, instructions: &[Move]) {
+        for instruction in instructions {
+            for _ in 0..instruction.n_crates {
+                let item = self.stacks[instruction.from_stack - 1].pop().unwrap();
+                self.stacks[instruction.to_stack - 1].push(item);
             }
         }
     }
 
-    fn use_crate_mover_9001(&mut self, instructions: &str) {
-        for instruction in instructions.trim().split('\n') {
-            let (n_crates, from_stack, to_stack) = Dock::parse_instruction(instruction);
-            let end = self.stacks[from_stack - 1].len();
-            let items: Vec<_> = self.stacks[from_stack - 1]
-                .drain(end - n_crates..end)
-                .collect();
+    fn use_crate_mover_9001(&mut self, instructions: &[Move]) {
+        for instruction in instructions {
+            let from_stack = &mut self.stacks[instruction.from_stack - 1];
+            let end = from_stack.len();
+            let items: Vec<_> = from_stack.drain(end - instruction.n_crates..end).collect();
             for item in items {
-                self.stacks[to_stack - 1].push(item.to_owned());
+                self.stacks[instruction.to_stack - 1].push(item.to_owned());
             }
         }
     }
-
-    fn parse_instruction(instruction: &str) -> (usize, usize, usize) {
-        let (_, split) = instruction.split_once("move ").unwrap();
-        let (n_crates, split) = split.split_once(' ').unwrap();
-        let n_crates: usize = n_crates.parse().unwrap();
-        let (_, split) = split.split_once("from ").unwrap();
-        let (from_stack, split) = split.split_once(' ').unwrap();
-        let from_stack: usize = from_stack.parse().unwrap();
-        let (_, to_stack) = split.split_once("to ").unwrap();
-        let to_stack: usize = to_stack.parse().unwrap();
-
-        (n_crates, from_stack, to_stack)
-    }
 }
 
-pub fn generator(input: &str) -> (&str, &str) {
-    let (stacks_str, instructions): (&str, &str) = input.split_once("\n\n").unwrap();
-    (stacks_str, instructions)
+pub fn generator(input: &str) -> Result<(Dock, Vec<Move>), ParseError> {
+    let source = Source::new(input);
+    let (stack_str, instructions) = source.split_once(input, "\n\n")?;
+    let dock: Dock = source.parse_as(stack_str)?;
+    let n_stacks = dock.stacks.len();
+    let instructions = instructions
+        .trim()
+        .lines()
+        .map(|line| {
+            let instruction: Move = source.parse_as(line)?;
+            let stacks = [instruction.from_stack, instruction.to_stack];
+            if stacks.iter().any(|stack| !(1..=n_stacks).contains(stack)) {
+                let expected = format!("stacks between 1 and {n_stacks}");
+                return Err(source.error(line, &expected));
+            }
+            Ok(instruction)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((dock, instructions))
 }
 
-pub fn part_1(input: &(&str, &str)) -> String {
-    let (stack_str, instructions) = input;
-    let mut stacks = stack_str.parse::<Dock>().unwrap();
+pub fn part_1(input: &(Dock, Vec<Move>)) -> String {
+    let (dock, instructions) = input;
+    let mut stacks = dock.clone();
     stacks.use_crate_mover_9000(instructions);
     let mut result = "".to_owned();
     for i in 0..stacks.stacks.len() {
@@ -84,9 +114,9 @@ pub fn part_1(input: &(&str, &str)) -> String {
     }
     result
 }
-pub fn part_2(input: &(&str, &str)) -> String {
-    let (stack_str, instructions) = input;
-    let mut stacks = stack_str.parse::<Dock>().unwrap();
+pub fn part_2(input: &(Dock, Vec<Move>)) -> String {
+    let (dock, instructions) = input;
+    let mut stacks = dock.clone();
     stacks.use_crate_mover_9001(instructions);
     let mut result = "".to_owned();
     for i in 0..stacks.stacks.len() {
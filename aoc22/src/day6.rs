@@ -1,13 +1,27 @@
-fn get_marker(n_unique: usize, input: &str) -> usize {
+use aoc_utils::prelude::*;
+
+/// The letters of the datastream, numbered from 0 for `a`.
+pub fn generator(input: &str) -> Result<Vec<u8>, ParseError> {
+    let source = Source::new(input);
+    let datastream = input.trim();
+    datastream
+        .char_indices()
+        .map(|(i, char)| match char {
+            'a'..='z' => Ok(char as u8 - b'a'),
+            _ => Err(source.error(&datastream[i..i + char.len_utf8()], "a letter")),
+        })
+        .collect()
+}
+
+fn get_marker(n_unique: usize, input: &[u8]) -> usize {
     input
-        .as_bytes()
         .windows(n_unique)
         .position(|bytes| {
             bytes
                 .iter()
                 .try_fold(0usize, |mut set, char| {
                     let prev = set;
-                    set |= 1 << (char - b'a');
+                    set |= 1 << char;
                     if prev == set {
                         return None;
                     }
@@ -15,15 +29,15 @@ fn get_marker(n_unique: usize, input: &str) -> usize {
                 })
                 .is_some()
         })
-        .unwrap()
+        .expect("the datastream has no marker")
         + n_unique
 }
 
-pub fn part_1(input: &str) -> usize {
+pub fn part_1(input: &[u8]) -> usize {
     get_marker(4, input)
 }
 
-pub fn part_2(input: &str) -> usize {
+pub fn part_2(input: &[u8]) -> usize {
     get_marker(14, input)
 }
 
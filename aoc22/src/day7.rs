@@ -1,27 +1,32 @@
-pub fn generator(input: &str) -> Vec<(&str, u32)> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<(&str, u32)>, ParseError> {
+    let source = Source::new(input);
     let mut dirs = vec![("/", 0u32)];
     let mut final_dirs = Vec::new();
 
-    input
+    for line in input
         .trim()
         .lines()
         .skip(1)
         .filter(|line| *line != "$ ls" && !line.starts_with("dir"))
-        .map(|line| {
-            if let Some(dir) = line.strip_prefix("$ cd ") {
-                if dir == ".." {
-                    let (name, size) = dirs.pop().unwrap();
-                    dirs.last_mut().unwrap().1 += size;
-                    final_dirs.push((name, size))
-                } else {
-                    dirs.push((dir, 0));
-                }
-                return;
+    {
+        if let Some(dir) = line.strip_prefix("$ cd ") {
+            if dir == ".." {
+                let (name, size) = dirs
+                    .pop()
+                    .filter(|_| !dirs.is_empty())
+                    .ok_or_else(|| source.error(line, "a directory below the root"))?;
+                dirs.last_mut().unwrap().1 += size;
+                final_dirs.push((name, size))
+            } else {
+                dirs.push((dir, 0));
             }
-            let (size, _) = line.split_once(' ').unwrap();
-            dirs.last_mut().unwrap().1 += size.parse::<u32>().unwrap();
-        })
-        .for_each(drop);
+            continue;
+        }
+        let (size, _) = source.split_once(line, " ")?;
+        dirs.last_mut().unwrap().1 += source.parse::<u32>(size, "a file size")?;
+    }
 
     while let Some((name, size)) = dirs.pop() {
         final_dirs.push((name, size));
@@ -29,7 +34,7 @@ pub fn generator(input: &str) -> Vec<(&str, u32)> {
             dirs.last_mut().unwrap().1 += size;
         }
     }
-    final_dirs
+    Ok(final_dirs)
 }
 
 pub fn part_1(input: &[(&str, u32)]) -> u32 {
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_utils::prelude::*;
+
 fn get_scenic_score(tree_matrix: &[Vec<u32>], row: &usize, col: &usize) -> u32 {
     let cols = tree_matrix[0].len();
     let val = tree_matrix[*row][*col];
@@ -40,16 +42,11 @@ fn get_scenic_score(tree_matrix: &[Vec<u32>], row: &usize, col: &usize) -> u32 {
     north_count * south_count * east_count * west_count
 }
 
-pub fn generator(input: &str) -> (Vec<Vec<u32>>, HashSet<(usize, usize)>) {
-    let tree_matrix = input
-        .trim()
-        .split('\n')
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<Vec<_>>>();
+type Forest = (Vec<Vec<u32>>, HashSet<(usize, usize)>);
+
+pub fn generator(input: &str) -> Result<Forest, ParseError> {
+    let tree_matrix =
+        Source::new(input).grid(input.trim(), |_, c| c.to_digit(10), "a tree height")?;
 
     let rows = tree_matrix.len();
     let cols = tree_matrix[0].len();
@@ -102,17 +99,17 @@ pub fn generator(input: &str) -> (Vec<Vec<u32>>, HashSet<(usize, usize)>) {
             };
         }
     }
-    (tree_matrix, counted_trees)
+    Ok((tree_matrix, counted_trees))
 }
 
-pub fn part_1(input: &(Vec<Vec<u32>>, HashSet<(usize, usize)>)) -> u32 {
+pub fn part_1(input: &Forest) -> u32 {
     let rows = input.0.len() as u32;
     let cols = input.0[0].len() as u32;
     let counted_trees = &input.1;
     rows * 2 + (cols - 2) * 2 + counted_trees.len() as u32
 }
 
-pub fn part_2(input: &(Vec<Vec<u32>>, HashSet<(usize, usize)>)) -> u32 {
+pub fn part_2(input: &Forest) -> u32 {
     let tree_matrix = &input.0;
     let counted_trees = &input.1;
     let mut max_scenic_score = 0;
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoc_utils::prelude::*;
+
 fn update_position(first: (i32, i32), next: (i32, i32)) -> (i32, i32) {
     let (x, y) = match (first.0 - next.0, first.1 - next.1) {
         (-1..=1, -1..=1) => (0, 0),
@@ -23,26 +25,26 @@ fn update_positions(knots: &mut [(i32, i32)], tail_set: &mut [HashSet<(i32, i32)
     }
 }
 
-pub fn generator(input: &str) -> Vec<HashSet<(i32, i32)>> {
+pub fn generator(input: &str) -> Result<Vec<HashSet<(i32, i32)>>, ParseError> {
+    let source = Source::new(input);
     let mut knots = vec![(0, 0); 10];
     let mut tails_set = vec![HashSet::new(); 10];
     for line in input.trim().lines() {
-        let (direction, steps) = line
-            .split_once(' ')
-            .map(|(d, s)| (d, s.parse::<usize>().unwrap()))
-            .unwrap();
-        for _ in 0..steps {
-            match direction {
-                "R" => knots[0].0 += 1,
-                "L" => knots[0].0 -= 1,
-                "U" => knots[0].1 += 1,
-                "D" => knots[0].1 -= 1,
-                _ => unreachable!(),
-            }
+        let (direction, steps) = source.split_once(line, " ")?;
+        let step = match direction {
+            "R" => (1, 0),
+            "L" => (-1, 0),
+            "U" => (0, 1),
+            "D" => (0, -1),
+            _ => return Err(source.error(direction, "'R', 'L', 'U' or 'D'")),
+        };
+        for _ in 0..source.parse::<usize>(steps, "a number of steps")? {
+            knots[0].0 += step.0;
+            knots[0].1 += step.1;
             update_positions(&mut knots, &mut tails_set);
         }
     }
-    tails_set
+    Ok(tails_set)
 }
 
 pub fn part_1(input: &[HashSet<(i32, i32)>]) -> u32 {
@@ -1,5 +1,3 @@
-#![recursion_limit = "256"]
-
 mod day1;
 mod day2;
 mod day3;
@@ -26,31 +24,31 @@ mod day23;
 mod day24;
 mod day25;
 
-aoc_main::main! {
+aoc_utils::main! {
     year 2022;
     day1  : generator => part_1, part_2;
-    day2              => part_1, part_2;
-    day3              => part_1, part_2;
+    day2  : generator => part_1, part_2;
+    day3  : generator => part_1, part_2;
     day4  : generator => part_1, part_2;
     day5  : generator => part_1, part_2;
-    day6              => part_1, part_2;
+    day6  : generator => part_1, part_2;
     day7  : generator => part_1, part_2;
     day8  : generator => part_1, part_2;
     day9  : generator => part_1, part_2;
-    day10             => part_1, part_2;
-    day11             => part_1, part_2;
+    day10 : generator => part_1, part_2;
+    day11 : generator => part_1, part_2;
     day12 : generator => part_1, part_2;
     day13 : generator => part_1, part_2;
-    day14             => part_1, part_2;
+    day14 : generator => part_1, part_2;
     day15 : generator => part_1, part_2;
     day16 : generator => part_1, part_2;
-    day17             => part_1, part_2;
+    day17 : generator => part_1, part_2;
     day18 : generator => part_1, part_2;
     day19 : generator => part_1, part_2;
     day20 : generator => part_1, part_2;
     day21 : generator => part_1, part_2;
     day22 : generator => part_1, part_2;
     day23 : generator => part_1, part_2;
-    day24             => part_1, part_2;
-    day25             => solution;
+    day24 : generator => part_1, part_2;
+    day25 : generator => solution;
 }
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
-pub fn generator(input: &str) -> Vec<Vec<&str>> {
-    let numbers = input
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<&str>>, ParseError> {
+    let source = Source::new(input);
+    input
         .lines()
         .map(|line| {
-            line.chars()
+            let numbers: Vec<&str> = line.chars()
                 .enumerate()
                 .filter_map(|(i, c)| match c {
                     'o' => {
@@ -58,10 +61,13 @@ pub fn generator(input: &str) -> Vec<Vec<&str>> {
                     c if c.is_ascii_digit() => line.get(i..i + 1),
                     _ => None,
                 })
-                .collect()
+                .collect();
+            if numbers.is_empty() {
+                return Err(source.error(line, "a digit or a digit word"));
+            }
+            Ok(numbers)
         })
-        .collect();
-    numbers
+        .collect()
 }
 
 pub fn part_1(input: &[Vec<&str>]) -> u32 {
@@ -1,5 +1,7 @@
 use std::{collections::HashSet, fmt, str::FromStr};
 
+use aoc_utils::prelude::*;
+
 pub struct Map {
     map: Vec<Row>,
     start: Position,
@@ -86,11 +88,12 @@ impl fmt::Debug for Map {
 }
 
 impl FromStr for Row {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
         let mut v = vec![];
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             v.push(match c {
                 '.' => Tile::Floor,
                 '|' => Tile::VPipe,
@@ -100,7 +103,7 @@ impl FromStr for Row {
                 'F' => Tile::SEPipe,
                 '7' => Tile::SWPipe,
                 'S' => Tile::Start,
-                _ => panic!("Invalid character parsed."),
+                _ => return Err(source.error(&s[i..i + c.len_utf8()], "a pipe, '.' or 'S'")),
             });
         }
         Ok(Row { tiles: v })
@@ -108,16 +111,21 @@ impl FromStr for Row {
 }
 
 impl Map {
-    fn new(input: &str) -> Self {
-        let map = input.lines().map(|l| l.parse().unwrap()).collect();
-        let start = Map::find_start_position(input).expect("There is no start in this map.");
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(input);
+        let map = input
+            .lines()
+            .map(|l| source.parse_as(l))
+            .collect::<Result<_, _>>()?;
+        let start = Map::find_start_position(input)
+            .map_err(|_| source.error_after(input, "a start tile 'S'"))?;
 
-        Map {
+        Ok(Map {
             map,
             start,
             loop_positions: None,
             tiles_inside_loop: None,
-        }
+        })
     }
 
     fn find_start_position(map_str: &str) -> Result<Position, &'static str> {
@@ -319,28 +327,27 @@ impl Map {
     }
 }
 
-pub fn generator(input: &str) -> Result<Map, &'static str> {
-    let mut map = Map::new(input);
-    map.find_loop()?;
-    map.find_tiles_inside_loop()?;
+pub fn generator(input: &str) -> Result<Map, ParseError> {
+    let mut map = Map::new(input)?;
+    if map
+        .find_loop()
+        .and_then(|()| map.find_tiles_inside_loop())
+        .is_err()
+    {
+        let row = input
+            .lines()
+            .nth(map.start.y)
+            .expect("the start is in the map");
+        let start = &row[map.start.x..=map.start.x];
+        return Err(Source::new(input).error(start, "a loop through the start"));
+    }
     Ok(map)
 }
 
-pub fn part_1(map: &Result<Map, &'static str>) -> u32 {
-    map.as_ref()
-        .expect("Something is wrong with the map parsing")
-        .loop_positions
-        .as_ref()
-        .unwrap()
-        .len() as u32
-        / 2
+pub fn part_1(map: &Map) -> u32 {
+    map.loop_positions.as_ref().unwrap().len() as u32 / 2
 }
 
-pub fn part_2(map: &Result<Map, &'static str>) -> u32 {
-    map.as_ref()
-        .expect("Something is wrong with the map parsing")
-        .tiles_inside_loop
-        .as_ref()
-        .unwrap()
-        .len() as u32
+pub fn part_2(map: &Map) -> u32 {
+    map.tiles_inside_loop.as_ref().unwrap().len() as u32
 }
@@ -1,21 +1,20 @@
-pub fn generator(input: &str) -> (Vec<Vec<char>>, Vec<(usize, usize)>) {
+use aoc_utils::prelude::*;
+
+type Image = (Vec<Vec<char>>, Vec<(usize, usize)>);
+
+pub fn generator(input: &str) -> Result<Image, ParseError> {
     let mut galaxy_positions = Vec::new();
-    let map = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| {
-                    if c == '#' {
-                        galaxy_positions.push((i, j));
-                    }
-                    c
-                })
-                .collect()
-        })
-        .collect();
-    (map, galaxy_positions)
+    let map = Source::new(input).grid(
+        input,
+        |(j, i), c| {
+            if c == '#' {
+                galaxy_positions.push((i, j));
+            }
+            matches!(c, '#' | '.').then_some(c)
+        },
+        "'#' or '.'",
+    )?;
+    Ok((map, galaxy_positions))
 }
 
 fn min_path_length(
@@ -81,10 +80,10 @@ fn sum_all_lengths(
         })
         .sum()
 }
-pub fn part_1((map, galaxy_positions): &(Vec<Vec<char>>, Vec<(usize, usize)>)) -> u64 {
+pub fn part_1((map, galaxy_positions): &Image) -> u64 {
     sum_all_lengths(map, galaxy_positions, 2)
 }
 
-pub fn part_2((map, galaxy_positions): &(Vec<Vec<char>>, Vec<(usize, usize)>)) -> u64 {
+pub fn part_2((map, galaxy_positions): &Image) -> u64 {
     sum_all_lengths(map, galaxy_positions, 1_000_000)
 }
@@ -1,12 +1,6 @@
-use std::error::Error;
-use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug)]
-pub enum SpringsError {
-    UnknownCharacter(String),
-    ParseError(String),
-}
+use aoc_utils::prelude::*;
 
 #[derive(Debug, Clone)]
 pub struct SpringMap {
@@ -21,48 +15,31 @@ enum Condition {
     Unknown,
 }
 
-impl fmt::Display for SpringsError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            SpringsError::UnknownCharacter(s) => write!(f, "Unknown character: {}", s),
-            SpringsError::ParseError(s) => write!(f, "Parse error: {}", s),
-        }
-    }
-}
-
-impl Error for SpringsError {}
-
 impl FromStr for SpringMap {
-    type Err = SpringsError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
         let n_lines = s.lines().count();
         let mut springs = Vec::with_capacity(n_lines);
         let mut configurations = Vec::with_capacity(n_lines);
         for l in s.lines() {
-            let (springs_str, configuration_str) =
-                l.split_once(' ').expect("Could not correctly parse input");
+            let (springs_str, configuration_str) = source.split_once(l, " ")?;
 
             let row_springs = springs_str
-                .chars()
-                .map(|c| match c {
+                .char_indices()
+                .map(|(i, c)| match c {
                     '#' => Ok(Condition::Damaged),
                     '.' => Ok(Condition::Operational),
                     '?' => Ok(Condition::Unknown),
-                    _ => Err(SpringsError::UnknownCharacter(c.to_string())),
+                    _ => Err(source.error(&springs_str[i..i + c.len_utf8()], "'#', '.' or '?'")),
                 })
                 .collect::<Result<Vec<_>, _>>()?;
 
             let cfg = configuration_str
                 .split(',')
-                .map(|n| match n.parse::<usize>() {
-                    Ok(n) => Ok(n),
-                    Err(_) => Err(SpringsError::ParseError(format!(
-                        "Could not parse {} as a usize.",
-                        n
-                    ))),
-                })
-                .collect::<Result<Vec<_>, SpringsError>>()?;
+                .map(|n| source.parse(n, "a group size"))
+                .collect::<Result<Vec<usize>, _>>()?;
 
             springs.push(row_springs);
             configurations.push(cfg);
@@ -122,8 +99,8 @@ fn solve(springs: &[Condition], counts: Vec<usize>) -> usize {
     *dp.last().unwrap_or(&0)
 }
 
-pub fn generator(input: &str) -> SpringMap {
-    input.parse().expect("Could not correctly parse input.")
+pub fn generator(input: &str) -> Result<SpringMap, ParseError> {
+    input.parse()
 }
 
 pub fn part_1(spring_map: &SpringMap) -> usize {
@@ -1,7 +1,16 @@
-pub fn generator(input: &str) -> Vec<Vec<Vec<char>>> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<Vec<char>>>, ParseError> {
+    let source = Source::new(input);
     input
         .split("\n\n")
-        .map(|notes| notes.lines().map(|l| l.chars().collect()).collect())
+        .map(|notes| {
+            source.grid(
+                notes,
+                |_, c| matches!(c, '#' | '.').then_some(c),
+                "'#' or '.'",
+            )
+        })
         .collect()
 }
 
@@ -129,12 +138,12 @@ mod tests {
     // Test for the `part_1` function
     #[test]
     fn test_zero_smudges() {
-        assert_eq!(solve(&generator(INPUT), 0), 405);
+        assert_eq!(solve(&generator(INPUT).unwrap(), 0), 405);
     }
 
     // Test for the `part_2` function
     #[test]
     fn test_one_allowed_smudge() {
-        assert_eq!(solve(&generator(INPUT), 1), 400);
+        assert_eq!(solve(&generator(INPUT).unwrap(), 1), 400);
     }
 }
@@ -1,8 +1,10 @@
 use std::{
     collections::HashMap,
-    fmt::{Debug, Formatter, Result},
+    fmt::{self, Debug, Formatter},
 };
 
+use aoc_utils::prelude::*;
+
 const MAX_ROWS: usize = 102;
 
 #[derive(Clone, Copy)]
@@ -17,7 +19,7 @@ enum TiltDirection {
 }
 
 impl Debug for Grid {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let start_row = self
             .square_rocks
             .iter()
@@ -230,8 +232,13 @@ impl Grid {
     }
 }
 
-pub fn generator(input: &str) -> Grid {
-    Grid::new(input)
+pub fn generator(input: &str) -> Result<Grid, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| matches!(c, 'O' | '#' | '.').then_some(()),
+        "'O', '#' or '.'",
+    )?;
+    Ok(Grid::new(input))
 }
 
 pub fn part_1(input: &Grid) -> usize {
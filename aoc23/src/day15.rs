@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 fn hash(s: &str) -> usize {
     s.chars().fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
 }
@@ -13,8 +15,8 @@ fn calculate_focus_power<T>(boxes: &[Vec<(T, usize)>]) -> usize {
         })
         .sum()
 }
-pub fn generator(input: &str) -> Vec<&str> {
-    input.split(',').collect()
+pub fn generator(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(input.split(',').collect())
 }
 
 pub fn part_1(input: &[&str]) -> usize {
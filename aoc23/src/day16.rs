@@ -3,6 +3,9 @@ use std::{
     fmt::{self, Debug, Display},
 };
 
+use aoc_utils::prelude::*;
+use aoc_utils::viz::{self, Cell, Color, Frame};
+
 #[derive(Clone)]
 pub struct Grid {
     map: Vec<Vec<Tile>>,
@@ -97,6 +100,33 @@ impl Debug for Grid {
     }
 }
 
+/// The tiles crossed by a beam are lit, showing the direction of the beam
+/// or the number of beams crossing them.
+impl Frame for Grid {
+    fn size(&self) -> (usize, usize) {
+        (self.map[0].len(), self.map.len())
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let dirs: Vec<Direction> = (0..4)
+            .filter(|&dir| self.beams[get_idx!(self, y, x, dir)])
+            .map(|dir| dir.try_into().unwrap())
+            .collect();
+        let tile = &self.map[y][x];
+        let symbol = match (tile, dirs.as_slice()) {
+            (Tile::Empty, []) => return Cell::new('.').color(Color::Gray),
+            (Tile::Empty, [dir]) => dir.to_string().chars().next().unwrap(),
+            (Tile::Empty, dirs) => char::from_digit(dirs.len() as u32, 10).unwrap(),
+            _ => tile.to_string().chars().next().unwrap(),
+        };
+        if dirs.is_empty() {
+            Cell::new(symbol).color(Color::White)
+        } else {
+            Cell::new(symbol).color(Color::Yellow).bold()
+        }
+    }
+}
+
 impl Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -121,16 +151,22 @@ impl Display for Direction {
 }
 
 impl Grid {
-    fn populate_energized_tiles(&mut self, start_beam: (isize, isize, Direction)) -> usize {
+    /// Follows the beams entering at `start_beam`, showing every step of
+    /// them when `animate` is set.
+    fn populate_energized_tiles(
+        &mut self,
+        start_beam: (isize, isize, Direction),
+        animate: bool,
+    ) -> usize {
         let n_rows = self.map.len();
         let n_cols = self.map[0].len();
-        let mut seen_states = vec![false; n_rows * n_cols * 4];
+        self.beams.fill(false);
         let mut energized_tiles = vec![false; n_rows * n_cols];
         let mut beams = self.walk(start_beam);
         while let Some((row, col, direction)) = beams.pop() {
-            let seen_state = seen_states[get_idx!(self, row, col, direction)];
+            let seen_state = self.beams[get_idx!(self, row, col, direction)];
             if !seen_state {
-                seen_states[get_idx!(self, row, col, direction)] = true;
+                self.beams[get_idx!(self, row, col, direction)] = true;
             } else {
                 continue;
             }
@@ -139,10 +175,12 @@ impl Grid {
             if !is_energized {
                 energized_tiles[get_idx!(self, row, col)] = true;
             }
+            if animate {
+                viz::show(self);
+            }
 
             beams.extend(self.walk((row as isize, col as isize, direction)));
         }
-        self.beams = seen_states;
         energized_tiles.iter().filter(|s| **s).count()
     }
 
@@ -187,65 +225,49 @@ impl Grid {
     }
 }
 
-pub fn generator(input: &str) -> Grid {
-    let mut n_tiles = 0;
-    Grid {
-        map: input
-            .lines()
-            .map(|l| {
-                l.chars()
-                    .map(|c| {
-                        n_tiles += 1;
-                        match c {
-                            '|' => Tile::VerticalSplitter,
-                            '-' => Tile::HorizontalSplitter,
-                            '/' => Tile::SWMirror,
-                            '\\' => Tile::NWMirror,
-                            '.' => Tile::Empty,
-                            _ => panic!("Invalid character found."),
-                        }
-                    })
-                    .collect()
-            })
-            .collect(),
+pub fn generator(input: &str) -> Result<Grid, ParseError> {
+    let map: Vec<Vec<Tile>> = Source::new(input).grid(
+        input,
+        |_, c| match c {
+            '|' => Some(Tile::VerticalSplitter),
+            '-' => Some(Tile::HorizontalSplitter),
+            '/' => Some(Tile::SWMirror),
+            '\\' => Some(Tile::NWMirror),
+            '.' => Some(Tile::Empty),
+            _ => None,
+        },
+        "a mirror, a splitter or '.'",
+    )?;
+    let n_tiles = map.iter().map(Vec::len).sum::<usize>();
+    Ok(Grid {
+        map,
         beams: vec![false; n_tiles * 4],
-    }
+    })
 }
 
 pub fn part_1(input: &Grid) -> usize {
     let mut grid = input.clone();
-    grid.populate_energized_tiles((0, -1, Direction::East))
+    grid.populate_energized_tiles((0, -1, Direction::East), viz::is_active())
 }
 
 pub fn part_2(input: &Grid) -> usize {
     let mut grid = input.clone();
-    let mut all_energized_tiles = Vec::new();
-    // Left wall
-    for i in 0..grid.map.len() {
-        all_energized_tiles.push(grid.populate_energized_tiles((i as isize, -1, Direction::East)))
-    }
-
-    // Right wall
-    for i in 0..grid.map.len() {
-        all_energized_tiles.push(grid.populate_energized_tiles((
-            i as isize,
-            grid.map[i].len() as isize,
-            Direction::West,
-        )))
-    }
-
-    // Top wall
-    for j in 0..grid.map[0].len() {
-        all_energized_tiles.push(grid.populate_energized_tiles((-1, j as isize, Direction::South)))
-    }
+    let n_rows = grid.map.len() as isize;
+    let n_cols = grid.map[0].len() as isize;
+    let starts = (0..n_rows)
+        .flat_map(|i| [(i, -1, Direction::East), (i, n_cols, Direction::West)])
+        .chain(
+            (0..n_cols).flat_map(|j| [(-1, j, Direction::South), (n_rows, j, Direction::North)]),
+        );
 
-    // Bottom wall
-    for j in 0..grid.map[0].len() {
-        all_energized_tiles.push(grid.populate_energized_tiles((
-            grid.map.len() as isize,
-            j as isize,
-            Direction::North,
-        )))
+    let mut max_energized = 0;
+    for start in starts {
+        // Stepping through hundreds of starts would take ages, so only show
+        // the beams each of them ends up with.
+        max_energized = max_energized.max(grid.populate_energized_tiles(start, false));
+        if viz::is_active() {
+            viz::show(&grid);
+        }
     }
-    *all_energized_tiles.iter().max().unwrap()
+    max_energized
 }
@@ -1,6 +1,8 @@
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Position {
     x: usize,
@@ -79,18 +81,17 @@ fn a_star<const MIN_STEPS: usize, const MAX_STEPS: usize>(map: &mut [Vec<Tile>])
     u16::MAX
 }
 
-pub fn generator(input: &str) -> Vec<Vec<Tile>> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|n| Tile {
-                    heat_loss: n.to_digit(10).unwrap() as u8,
-                    g_scores: [u16::MAX; 2],
-                })
-                .collect()
-        })
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, n| {
+            Some(Tile {
+                heat_loss: n.to_digit(10)? as u8,
+                g_scores: [u16::MAX; 2],
+            })
+        },
+        "a digit",
+    )
 }
 
 pub fn part_1(input: &[Vec<Tile>]) -> u16 {
@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 #[derive(Copy, Clone)]
 enum Direction {
     Up,
@@ -9,49 +11,59 @@ enum Direction {
 pub struct Instruction {
     direction: Direction,
     steps: i64,
-    color: String,
-}
-
-fn get_steps_from_color(color: &str) -> i64 {
-    i64::from_str_radix(&color.trim_start_matches('#')[..5], 16).unwrap()
+    color_direction: Direction,
+    color_steps: i64,
 }
 
-fn get_dir_from_color(color: &str) -> Direction {
-    match color.chars().last() {
-        Some('0') => Direction::Right,
-        Some('1') => Direction::Down,
-        Some('2') => Direction::Left,
-        Some('3') => Direction::Up,
-        _ => panic!("Invalid color direction."),
-    }
+/// Decodes the steps, from the first five hex digits, and the direction, from
+/// the last one, hidden in a colour such as `(#70c710)`.
+fn parse_color(source: &Source, color: &str) -> Result<(i64, Direction), ParseError> {
+    let error = || source.error(color, "a colour such as (#70c710)");
+    let hex = color
+        .strip_prefix("(#")
+        .and_then(|color| color.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+        .ok_or_else(error)?;
+    let direction = match &hex[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        _ => return Err(error()),
+    };
+    let steps = i64::from_str_radix(&hex[..5], 16).map_err(|_| error())?;
+    Ok((steps, direction))
 }
 
-pub fn generator(input: &str) -> Vec<Instruction> {
+pub fn generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|l| {
             let mut parts = l.split_whitespace();
-            let direction = match parts.next() {
-                Some("R") => Direction::Right,
-                Some("L") => Direction::Left,
-                Some("U") => Direction::Up,
-                Some("D") => Direction::Down,
-                _ => panic!("Something wrong with parsing input."),
+            let mut next = |previous: &str, expected| {
+                parts
+                    .next()
+                    .ok_or_else(|| source.error_after(previous, expected))
+            };
+            let direction_str = next(l, "a direction")?;
+            let direction = match direction_str {
+                "R" => Direction::Right,
+                "L" => Direction::Left,
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                _ => return Err(source.error(direction_str, "'R', 'L', 'U' or 'D'")),
             };
-            let steps = parts
-                .next()
-                .expect("Not any number to parse.")
-                .parse()
-                .expect("Could not parse into a number.");
-            let color = parts
-                .next()
-                .expect("Not any color to parse.")
-                .replace(['(', ')'], "");
-            Instruction {
+            let steps_str = next(direction_str, "a number of steps")?;
+            let steps = source.parse(steps_str, "a number of steps")?;
+            let color = next(steps_str, "a colour")?;
+            let (color_steps, color_direction) = parse_color(&source, color)?;
+            Ok(Instruction {
                 direction,
                 steps,
-                color,
-            }
+                color_direction,
+                color_steps,
+            })
         })
         .collect()
 }
@@ -62,12 +74,12 @@ fn solve(instructions: &[Instruction], is_color_instructions: bool) -> i64 {
     let mut border_length = 0;
     instructions.iter().for_each(|i| {
         let step = if is_color_instructions {
-            get_steps_from_color(&i.color)
+            i.color_steps
         } else {
             i.steps
         };
         let direction = if is_color_instructions {
-            get_dir_from_color(&i.color)
+            i.color_direction
         } else {
             i.direction
         };
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Clone)]
 pub struct Condition {
     part_name: Option<char>,
@@ -16,7 +18,7 @@ enum Comparator {
     LessOrEqual,
 }
 
-fn parse_condition(condition_text: &str) -> Result<Condition, &str> {
+fn parse_condition(source: &Source, condition_text: &str) -> Result<Condition, ParseError> {
     let parts: Vec<&str> = condition_text.split(':').collect();
     // The 'else' part of the condition
     if parts.len() != 2 {
@@ -31,23 +33,21 @@ fn parse_condition(condition_text: &str) -> Result<Condition, &str> {
     let condition_part = parts[0];
     let destination = parts[1].to_string();
 
-    let (rating_part, rest) = condition_part.split_at(1);
-    let part_name = rating_part
+    let part_name = condition_part
         .chars()
         .next()
-        .ok_or("Could not get part name when parsing condition")?;
+        .ok_or_else(|| source.error(condition_text, "a part name"))?;
+    let rest = &condition_part[part_name.len_utf8()..];
 
     let (comparator, threshold_str) = if let Some(index) = rest.find('>') {
         (Comparator::GreaterThan, &rest[index + 1..])
     } else if let Some(index) = rest.find('<') {
         (Comparator::LessThan, &rest[index + 1..])
     } else {
-        return Err("Invalid comparison operator");
+        return Err(source.error(rest, "'<' or '>'"));
     };
 
-    let threshold = threshold_str
-        .parse::<u32>()
-        .map_err(|_| "Could not parse threshold to u32")?;
+    let threshold = source.parse(threshold_str, "a threshold")?;
 
     Ok(Condition {
         part_name: Some(part_name),
@@ -206,19 +206,17 @@ fn get_combinations(conditions: &[Condition], min_val: u64, max_val: u64) -> Res
 }
 
 type WorkflowRatingTuple<'a> = (HashMap<&'a str, Vec<Condition>>, Vec<HashMap<char, u32>>);
-pub fn generator(input: &str) -> Result<WorkflowRatingTuple, &str> {
-    let (workflows_str, ratings_str) = input.split_once("\n\n").ok_or("Incorrect input format")?;
+pub fn generator(input: &str) -> Result<WorkflowRatingTuple<'_>, ParseError> {
+    let source = Source::new(input);
+    let (workflows_str, ratings_str) = source.split_once(input, "\n\n")?;
 
     let mut workflow_map = HashMap::new();
     for line in workflows_str.lines() {
-        let (name, conditions_str) = line
-            .trim_end_matches('}')
-            .split_once('{')
-            .ok_or("Wrong workflow format")?;
+        let (name, conditions_str) = source.split_once(line.trim_end_matches('}'), "{")?;
 
         let mut conditions = Vec::new();
         for condition_str in conditions_str.split(',') {
-            let condition = parse_condition(condition_str)?;
+            let condition = parse_condition(&source, condition_str)?;
             conditions.push(condition);
         }
         workflow_map.insert(name, conditions);
@@ -232,11 +230,12 @@ pub fn generator(input: &str) -> Result<WorkflowRatingTuple, &str> {
             .trim_end_matches('}')
             .split(',')
         {
-            let (name, rating_str) = part_str.split_once('=').ok_or("Invalid rating map")?;
-            let name = name.chars().next().ok_or("Invalid part name")?;
-            let rating = rating_str
-                .parse::<u32>()
-                .map_err(|_| "Rating parse error")?;
+            let (name, rating_str) = source.split_once(part_str, "=")?;
+            let name = name
+                .chars()
+                .next()
+                .ok_or_else(|| source.error(part_str, "a part name"))?;
+            let rating = source.parse(rating_str, "a rating")?;
             rating_map.insert(name, rating);
         }
         rating_maps.push(rating_map);
@@ -245,10 +244,8 @@ pub fn generator(input: &str) -> Result<WorkflowRatingTuple, &str> {
     Ok((workflow_map, rating_maps))
 }
 
-pub fn part_1(workflow_rating_tuple: &Result<WorkflowRatingTuple, &str>) -> u32 {
-    let (workflow_map, part_ratings) = workflow_rating_tuple
-        .as_ref()
-        .expect("Could not properly parse input");
+pub fn part_1(workflow_rating_tuple: &WorkflowRatingTuple) -> u32 {
+    let (workflow_map, part_ratings) = workflow_rating_tuple;
     part_ratings
         .iter()
         .map(|ratings| {
@@ -267,11 +264,8 @@ pub fn part_1(workflow_rating_tuple: &Result<WorkflowRatingTuple, &str>) -> u32
         .sum()
 }
 
-pub fn part_2(workflow_rating_tuple: &Result<WorkflowRatingTuple, &str>) -> u64 {
-    let workflow_map = &workflow_rating_tuple
-        .as_ref()
-        .expect("Could not properly parse input")
-        .0;
+pub fn part_2(workflow_rating_tuple: &WorkflowRatingTuple) -> u64 {
+    let workflow_map = &workflow_rating_tuple.0;
     let mut all_paths = Vec::new();
     find_paths_to_acceptance(workflow_map, "in", Vec::new(), &mut all_paths);
     all_paths.iter().fold(0, |acc, cond| {
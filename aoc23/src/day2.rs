@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone, Debug)]
 pub struct Game {
     id: u32,
@@ -7,35 +9,39 @@ pub struct Game {
 }
 
 impl FromStr for Game {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (game_id, reveals_str) = s.split_once(':').unwrap();
-        let id = game_id.strip_prefix("Game ").unwrap().parse().unwrap();
+        let source = Source::new(s);
+        let (game_id, reveals_str) = source.split_once(s, ":")?;
+        let id = game_id
+            .strip_prefix("Game ")
+            .ok_or_else(|| source.error(game_id, "\"Game \""))?;
+        let id = source.parse(id, "a game number")?;
         let reveals = reveals_str
             .trim_end()
             .split(';')
             .map(|x| {
                 let mut cubes_revealed = (0, 0, 0);
-                x.split(',').for_each(|colors_str| {
-                    let (s1, color) = colors_str.trim().split_once(' ').unwrap();
-                    let n_cubes = s1.parse().unwrap_or(0);
-                    if color == "red" {
-                        cubes_revealed.0 = n_cubes;
-                    } else if color == "green" {
-                        cubes_revealed.1 = n_cubes;
-                    } else {
-                        cubes_revealed.2 = n_cubes;
+                for colors_str in x.split(',') {
+                    let (s1, color) = source.split_once(colors_str.trim(), " ")?;
+                    let n_cubes = source.parse(s1, "a number of cubes")?;
+                    match color {
+                        "red" => cubes_revealed.0 = n_cubes,
+                        "green" => cubes_revealed.1 = n_cubes,
+                        "blue" => cubes_revealed.2 = n_cubes,
+                        _ => return Err(source.error(color, "red, green or blue")),
                     }
-                });
-                cubes_revealed
+                }
+                Ok(cubes_revealed)
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
         Ok(Game { id, reveals })
     }
 }
-pub fn generator(input: &str) -> Vec<Game> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+pub fn generator(input: &str) -> Result<Vec<Game>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|l| source.parse_as(l)).collect()
 }
 
 pub fn part_1(input: &[Game]) -> u32 {
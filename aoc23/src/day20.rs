@@ -1,215 +1,496 @@
-use std::collections::{HashMap, VecDeque};
-use std::fmt::Debug;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Clone)]
-pub enum Module {
-    FlipFlop {
-        name: String,
-        is_on: bool,
-        destination_modules: Vec<String>,
-    },
-    Conjuction {
-        name: String,
-        connected_modules: HashMap<String, Pulse>,
-        destination_modules: Vec<String>,
-    },
-    Broadcaster {
-        name: String,
-        destination_modules: Vec<String>,
-    },
-    Button {
-        name: String,
-        destination_modules: Vec<String>,
-    },
-}
+use aoc_utils::dot::{self, Graph};
+use aoc_utils::prelude::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The number of presses after which a counter is given up on, if its state
+/// has not repeated.
+const MAX_PRESSES: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Pulse {
     Low,
     High,
 }
 
-impl Module {
-    fn receive_signal(&mut self, input_pulse: Pulse, sender_name: String) -> Option<Pulse> {
+impl std::ops::Not for Pulse {
+    type Output = Pulse;
+
+    fn not(self) -> Pulse {
         match self {
-            Module::FlipFlop { is_on, .. } => match input_pulse {
+            Pulse::Low => Pulse::High,
+            Pulse::High => Pulse::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    FlipFlop,
+    Conjunction,
+    Broadcaster,
+    Button,
+    /// A module only named as a destination, such as `rx`, which receives
+    /// pulses and sends none.
+    Output,
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    kind: Kind,
+    inputs: Vec<usize>,
+    destinations: Vec<usize>,
+}
+
+/// A pulse sent from one module to another during a button press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub press: u64,
+    pub from: usize,
+    pub to: usize,
+    pub pulse: Pulse,
+}
+
+/// Watches the pulses as they are delivered.
+pub trait Observer {
+    fn observe(&mut self, network: &PulseNetwork, event: &Event);
+}
+
+impl<F: FnMut(&PulseNetwork, &Event)> Observer for F {
+    fn observe(&mut self, network: &PulseNetwork, event: &Event) {
+        self(network, event)
+    }
+}
+
+/// Counts the low and high pulses delivered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PulseCounter {
+    pub low: u64,
+    pub high: u64,
+}
+
+impl Observer for PulseCounter {
+    fn observe(&mut self, _: &PulseNetwork, event: &Event) {
+        match event.pulse {
+            Pulse::Low => self.low += 1,
+            Pulse::High => self.high += 1,
+        }
+    }
+}
+
+/// Records the pulses sent by some modules, with the press they were sent
+/// during. A pulse sent to several destinations is recorded once.
+#[derive(Debug, Default, Clone)]
+pub struct PulseHistory {
+    sent: HashMap<usize, Vec<(u64, Pulse)>>,
+}
+
+impl PulseHistory {
+    pub fn new(modules: impl IntoIterator<Item = usize>) -> Self {
+        PulseHistory {
+            sent: modules.into_iter().map(|m| (m, Vec::new())).collect(),
+        }
+    }
+
+    /// The pulses sent by `module`, oldest first.
+    pub fn sent(&self, module: usize) -> &[(u64, Pulse)] {
+        self.sent.get(&module).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Observer for PulseHistory {
+    fn observe(&mut self, network: &PulseNetwork, event: &Event) {
+        if let Some(sent) = self.sent.get_mut(&event.from) {
+            if network.modules[event.from].destinations.first() == Some(&event.to) {
+                sent.push((event.press, event.pulse));
+            }
+        }
+    }
+}
+
+/// The modules, their state and the pulses still on their way.
+#[derive(Debug, Clone)]
+pub struct PulseNetwork {
+    modules: Vec<Module>,
+    ids: HashMap<String, usize>,
+    /// Whether each flip-flop is on.
+    on: Vec<bool>,
+    /// The last pulse received by each conjunction from each of its inputs.
+    memory: Vec<Vec<Pulse>>,
+    queue: VecDeque<Event>,
+    presses: u64,
+}
+
+/// What a module must send for a pulse to reach its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Requirement {
+    module: usize,
+    pulse: Pulse,
+    /// Whether a conjunction remembers the pulse, so that it may have been
+    /// sent during an earlier press.
+    held: bool,
+}
+
+/// The presses during which a part of the network does what is asked of it,
+/// repeating every `period` presses after the first `start` ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Counter {
+    hits: Vec<u64>,
+    start: u64,
+    period: u64,
+}
+
+impl Counter {
+    fn hits(&self, press: u64) -> bool {
+        let end = self.start + self.period;
+        let press = if press > end {
+            (press - self.start - 1) % self.period + self.start + 1
+        } else {
+            press
+        };
+        self.hits.contains(&press)
+    }
+}
+
+impl PulseNetwork {
+    fn add(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        self.modules.push(Module {
+            name: name.to_string(),
+            kind: Kind::Output,
+            inputs: Vec::new(),
+            destinations: Vec::new(),
+        });
+        self.ids.insert(name.to_string(), self.modules.len() - 1);
+        self.modules.len() - 1
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, module: usize) -> &str {
+        &self.modules[module].name
+    }
+
+    /// The modules sending pulses to `module`.
+    pub fn inputs(&self, module: usize) -> &[usize] {
+        &self.modules[module].inputs
+    }
+
+    /// The number of times the button was pressed.
+    pub fn presses(&self) -> u64 {
+        self.presses
+    }
+
+    /// Presses the button, queueing its low pulse to the broadcaster.
+    pub fn press(&mut self) {
+        self.presses += 1;
+        let button = self.ids["button"];
+        self.queue.push_back(Event {
+            press: self.presses,
+            from: button,
+            to: self.modules[button].destinations[0],
+            pulse: Pulse::Low,
+        });
+    }
+
+    /// Delivers the next pulse, queueing the pulses its destination sends in
+    /// response. Returns `None` once the network has settled.
+    pub fn step(&mut self) -> Option<Event> {
+        let event = self.queue.pop_front()?;
+        let module = &self.modules[event.to];
+        let output = match module.kind {
+            Kind::FlipFlop => match event.pulse {
                 Pulse::Low => {
-                    *is_on = !*is_on;
-                    if *is_on {
-                        Some(Pulse::High)
+                    self.on[event.to] = !self.on[event.to];
+                    Some(if self.on[event.to] {
+                        Pulse::High
                     } else {
-                        Some(Pulse::Low)
-                    }
+                        Pulse::Low
+                    })
                 }
                 Pulse::High => None,
             },
-
-            Module::Conjuction {
-                connected_modules, ..
-            } => {
-                connected_modules.insert(sender_name, input_pulse);
-                if connected_modules
-                    .values()
-                    .all(|pulse| matches!(pulse, Pulse::High))
-                {
+            Kind::Conjunction => {
+                let input = module.inputs.iter().position(|&m| m == event.from);
+                let memory = &mut self.memory[event.to];
+                memory[input.expect("a pulse from a module that is not an input")] = event.pulse;
+                if memory.iter().all(|&p| p == Pulse::High) {
                     Some(Pulse::Low)
                 } else {
                     Some(Pulse::High)
                 }
             }
-            Module::Broadcaster { .. } => Some(input_pulse),
-            Module::Button { .. } => Some(Pulse::Low),
-        }
-    }
-
-    fn get_name(&self) -> String {
-        match self {
-            Module::FlipFlop { name, .. }
-            | Module::Conjuction { name, .. }
-            | Module::Broadcaster { name, .. }
-            | Module::Button { name, .. } => name.to_owned(),
+            Kind::Broadcaster => Some(event.pulse),
+            Kind::Button | Kind::Output => None,
+        };
+        if let Some(pulse) = output {
+            for &to in &module.destinations {
+                self.queue.push_back(Event {
+                    press: event.press,
+                    from: event.to,
+                    to,
+                    pulse,
+                });
+            }
         }
+        Some(event)
     }
 
-    fn get_destination_modules(&self) -> &Vec<String> {
-        match self {
-            Module::FlipFlop {
-                destination_modules,
-                ..
-            }
-            | Module::Conjuction {
-                destination_modules,
-                ..
-            }
-            | Module::Broadcaster {
-                destination_modules,
-                ..
-            }
-            | Module::Button {
-                destination_modules,
-                ..
-            } => destination_modules,
+    /// Presses the button and delivers pulses until the network settles,
+    /// showing each of them to `observer`.
+    pub fn press_and_settle(&mut self, observer: &mut impl Observer) {
+        self.press();
+        while let Some(event) = self.step() {
+            observer.observe(self, &event);
         }
     }
-}
 
-fn initialize_conjuctions_with_low_pulse(module_network: &mut HashMap<String, Module>) {
-    // First, collect the updates in a vector
-    let mut updates = Vec::new();
-    for (module_name, module) in module_network.iter() {
-        let destination_modules = module.get_destination_modules();
-        for dest_module_name in destination_modules {
-            updates.push((module_name.to_string(), dest_module_name.clone()));
+    /// A hash of the state of `modules`, which is the same whenever they are
+    /// in the same state.
+    pub fn state_hash(&self, modules: &[usize]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for &module in modules {
+            self.on[module].hash(&mut hasher);
+            self.memory[module].hash(&mut hasher);
         }
+        hasher.finish()
     }
 
-    // Then, apply the updates
-    for (module_name, dest_module_name) in updates {
-        if let Some(Module::Conjuction {
-            connected_modules, ..
-        }) = module_network.get_mut(&dest_module_name)
-        {
-            connected_modules.insert(module_name, Pulse::Low);
+    /// Splits the modules into the parts driven by each output of the
+    /// broadcaster. The modules reached from a single output belong to its
+    /// part, and the others to none.
+    fn components(&self) -> Vec<Option<usize>> {
+        let broadcaster = self.ids["broadcaster"];
+        let mut reached_from = vec![Vec::new(); self.modules.len()];
+        for (i, &entry) in self.modules[broadcaster].destinations.iter().enumerate() {
+            let mut queue = VecDeque::from([entry]);
+            while let Some(module) = queue.pop_front() {
+                if reached_from[module].contains(&i) {
+                    continue;
+                }
+                reached_from[module].push(i);
+                queue.extend(self.modules[module].destinations.iter().copied());
+            }
         }
+        reached_from
+            .into_iter()
+            .map(|parts| (parts.len() == 1).then(|| parts[0]))
+            .collect()
     }
-}
 
-fn simulate_button_press<F>(
-    module_network: &mut HashMap<String, Module>,
-    mut callback: F,
-) -> (u32, u32)
-where
-    F: FnMut(&str, Pulse) -> bool,
-{
-    let mut signals_queue = VecDeque::new();
-    let (mut n_low_pulses, mut n_high_pulses) = (0, 0);
-
-    // Initial signal from the button
-    let module_name = module_network.get("button").unwrap().get_name().to_string();
-    // We know that button only have one destination
-    let destination = module_network
-        .get("button")
-        .unwrap()
-        .get_destination_modules()[0]
-        .clone();
-    let input_signal = Pulse::Low;
-    if let Some(output_signal) = module_network
-        .get_mut("button")
-        .unwrap()
-        .receive_signal(input_signal, "".to_owned())
-    {
-        signals_queue.push_back((module_name, output_signal, destination));
-    }
-
-    while let Some((sender_name, input_signal, destination)) = signals_queue.pop_front() {
-        if callback(&sender_name, input_signal) {
-            break;
+    /// Collects what the parts of the network must send for `module` to send
+    /// `pulse`, going back through the modules shared by several parts. With
+    /// `held`, it is enough for `module` to have last sent `pulse` before.
+    fn requirements(
+        &self,
+        requirement: Requirement,
+        components: &[Option<usize>],
+        visiting: &mut HashSet<Requirement>,
+        requirements: &mut Vec<Requirement>,
+    ) -> Result<(), String> {
+        let Module {
+            name, kind, inputs, ..
+        } = &self.modules[requirement.module];
+        let inner = matches!(kind, Kind::Broadcaster | Kind::Button);
+        if components[requirement.module].is_some() || inner {
+            requirements.push(requirement);
+            return Ok(());
         }
-        match input_signal {
-            Pulse::Low => n_low_pulses += 1,
-            Pulse::High => n_high_pulses += 1,
+        if !visiting.insert(requirement) {
+            return Err(format!("{name} depends on itself"));
         }
-
-        if let Some(output_signal) = module_network
-            .get_mut(&destination)
-            .and_then(|module| module.receive_signal(input_signal, sender_name))
-        {
-            if let Some(module) = module_network.get(&destination) {
-                let destination_modules = module.get_destination_modules();
-                for dest in destination_modules {
-                    signals_queue.push_back((destination.clone(), output_signal, dest.to_owned()));
+        match (kind, &inputs[..]) {
+            (Kind::Conjunction, &[input]) => self.requirements(
+                Requirement {
+                    module: input,
+                    pulse: !requirement.pulse,
+                    ..requirement
+                },
+                components,
+                visiting,
+                requirements,
+            )?,
+            // The inputs remember their pulses, so they may be sent during
+            // earlier presses
+            (Kind::Conjunction, inputs) if requirement.pulse == Pulse::Low => {
+                for &input in inputs {
+                    let input = Requirement {
+                        module: input,
+                        pulse: Pulse::High,
+                        held: true,
+                    };
+                    self.requirements(input, components, visiting, requirements)?;
                 }
             }
+            (Kind::Conjunction, _) => {
+                return Err(format!(
+                    "{name} sends a high pulse when any input is low, not when the counters align"
+                ))
+            }
+            (Kind::FlipFlop, _) => {
+                return Err(format!("{name} is a flip-flop fed by several counters"))
+            }
+            (Kind::Output, _) => return Err(format!("{name} never sends a pulse")),
+            (Kind::Broadcaster | Kind::Button, _) => unreachable!(),
         }
+        visiting.remove(&requirement);
+        Ok(())
     }
-    (n_low_pulses, n_high_pulses)
-}
 
-pub fn calculate_pulse_loop_count(
-    input: &HashMap<String, Module>,
-    target_module: &str,
-    target_pulse: Pulse,
-) -> u64 {
-    let mut module_network = input.clone();
-    let mut button_press_count = 0;
-
-    let mut is_target_reached = false;
-
-    while !is_target_reached {
-        simulate_button_press(&mut module_network, |module_name, pulse| {
-            if module_name == target_module && pulse == target_pulse {
-                is_target_reached = true;
-                true
-            } else {
-                false
+    /// Finds the first press during which `target` receives `pulse`, by
+    /// splitting the network into independent counters, finding the cycle of
+    /// each one, and combining their cycles.
+    ///
+    /// The counters are only simulated separately, so this assumes that the
+    /// pulses they send during a press line up the way they need to.
+    pub fn first_press(&self, target: &str, pulse: Pulse) -> Result<u64, String> {
+        let target = self
+            .find(target)
+            .ok_or(format!("there is no module {target}"))?;
+        let &[parent] = self.inputs(target) else {
+            return Err(format!(
+                "{} is not fed by a single module",
+                self.name(target)
+            ));
+        };
+        let components = self.components();
+        let mut requirements = Vec::new();
+        let requirement = Requirement {
+            module: parent,
+            pulse,
+            held: false,
+        };
+        self.requirements(
+            requirement,
+            &components,
+            &mut HashSet::new(),
+            &mut requirements,
+        )?;
+
+        // Group the requirements by counter, along with the modules whose
+        // state is the state of the counter
+        let mut groups = Vec::<(Option<usize>, Vec<Requirement>)>::new();
+        for &requirement in &requirements {
+            let component = components[requirement.module];
+            match groups.iter_mut().find(|(c, _)| *c == component) {
+                Some((_, group)) => group.push(requirement),
+                None => groups.push((component, vec![requirement])),
             }
-        });
+        }
+        let modules = groups
+            .iter()
+            .map(|(component, _)| {
+                (0..self.modules.len())
+                    .filter(|&m| component.is_some() && components[m] == *component)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
 
-        button_press_count += 1;
+        let mut network = self.clone();
+        let mut seen = modules
+            .iter()
+            .map(|modules| HashMap::from([(network.state_hash(modules), 0)]))
+            .collect::<Vec<_>>();
+        let mut counters = vec![None; groups.len()];
+        let mut hits = vec![Vec::new(); groups.len()];
+        let mut last_sent = HashMap::new();
+        while counters.iter().any(Option::is_none) {
+            if network.presses() == MAX_PRESSES {
+                return Err(format!(
+                    "a counter did not repeat within {MAX_PRESSES} presses"
+                ));
+            }
+            let mut history = PulseHistory::new(requirements.iter().map(|r| r.module));
+            network.press_and_settle(&mut history);
+            let press = network.presses();
+            let met = |r: &Requirement| {
+                history.sent(r.module).contains(&(press, r.pulse))
+                    || r.held && last_sent.get(&r.module) == Some(&r.pulse)
+            };
+            for (i, (_, group)) in groups.iter().enumerate() {
+                if counters[i].is_some() {
+                    continue;
+                }
+                if group.iter().all(met) {
+                    hits[i].push(press);
+                }
+                let hash = network.state_hash(&modules[i]);
+                match seen[i].get(&hash) {
+                    Some(&start) => {
+                        counters[i] = Some(Counter {
+                            hits: std::mem::take(&mut hits[i]),
+                            start,
+                            period: press - start,
+                        })
+                    }
+                    None => {
+                        seen[i].insert(hash, press);
+                    }
+                }
+            }
+            for requirement in &requirements {
+                if let Some(&(_, pulse)) = history.sent(requirement.module).last() {
+                    last_sent.insert(requirement.module, pulse);
+                }
+            }
+        }
+        let counters = counters.into_iter().flatten().collect::<Vec<_>>();
+        first_common_hit(&counters).ok_or(format!(
+            "{} never receives a {pulse:?} pulse",
+            self.name(target)
+        ))
     }
-    button_press_count
-}
 
-pub fn find_parent_modules(
-    module_network: &HashMap<String, Module>,
-    target_module: &str,
-) -> Vec<String> {
-    let mut parents = Vec::new();
-
-    // Iterate over all modules in the map
-    for (module_name, module) in module_network.iter() {
-        // Check if the current module's destination_modules contains the target_module
-        if module
-            .get_destination_modules()
-            .contains(&target_module.to_string())
-        {
-            parents.push(module_name.clone());
+    /// Draws the modules with a shape per type, filling the flip-flops that
+    /// are on, and colouring the inputs of conjunctions with the pulse they
+    /// remember.
+    pub fn to_dot(&self) -> Graph {
+        let mut graph = Graph::new("modules");
+        for (id, module) in self.modules.iter().enumerate() {
+            let node = graph.node(&module.name);
+            match module.kind {
+                Kind::FlipFlop => {
+                    node.shape("circle")
+                        .fill(if self.on[id] { "gold" } else { "lightgray" });
+                }
+                Kind::Conjunction => {
+                    node.shape("box");
+                }
+                Kind::Broadcaster => {
+                    node.shape("doubleoctagon");
+                }
+                Kind::Button => {
+                    node.shape("invhouse");
+                }
+                Kind::Output => {
+                    node.shape("doublecircle");
+                }
+            }
         }
+        for (id, module) in self.modules.iter().enumerate() {
+            for &to in &module.destinations {
+                let edge = graph.edge(&module.name, &self.modules[to].name);
+                if self.modules[to].kind == Kind::Conjunction {
+                    let input = self.modules[to].inputs.iter().position(|&m| m == id);
+                    match input.map(|i| self.memory[to][i]) {
+                        Some(Pulse::High) => edge.color("red"),
+                        _ => edge.color("blue"),
+                    };
+                }
+            }
+        }
+        graph
     }
-    parents
 }
 
-fn gcd(a: u64, b: u64) -> u64 {
+fn gcd(a: u128, b: u128) -> u128 {
     if b == 0 {
         a
     } else {
@@ -217,75 +498,265 @@ fn gcd(a: u64, b: u64) -> u64 {
     }
 }
 
-fn lcm(a: u64, b: u64) -> u64 {
-    a / gcd(a, b) * b
+/// Combines `n = r1 (mod m1)` and `n = r2 (mod m2)` into a single congruence,
+/// if they have a common solution.
+fn crt((r1, m1): (u128, u128), (r2, m2): (u128, u128)) -> Option<(u128, u128)> {
+    let g = gcd(m1, m2);
+    if r1 % g != r2 % g {
+        return None;
+    }
+    let lcm = m1 / g * m2;
+    // Step through the solutions of the first congruence, at most m2 / g
+    let step = m1 % lcm;
+    let mut n = r1 % lcm;
+    for _ in 0..m2 / g {
+        if n % m2 == r2 % m2 {
+            return Some((n, lcm));
+        }
+        n = (n + step) % lcm;
+    }
+    None
 }
 
-pub fn generator(input: &str) -> HashMap<String, Module> {
-    let mut module_network: HashMap<String, Module> = input
-        .lines()
-        .map(|l| {
-            let (name_and_type, destination_modules_str) =
-                l.split_once(" -> ").expect("Wrong input format");
-            let destination_modules = destination_modules_str
-                .split(", ")
-                .map(String::from)
-                .collect::<Vec<String>>();
-            let module = match name_and_type.chars().next().unwrap() {
-                '%' => Module::FlipFlop {
-                    name: name_and_type.trim_start_matches('%').to_owned(),
-                    is_on: false,
-                    destination_modules,
-                },
+/// The first press that is a hit of every counter.
+fn first_common_hit(counters: &[Counter]) -> Option<u64> {
+    // Before every counter is in its cycle, try every press
+    let end = counters.iter().map(|c| c.start + c.period).max()?;
+    if let Some(press) = (1..=end).find(|&p| counters.iter().all(|c| c.hits(p))) {
+        return Some(press);
+    }
 
-                '&' => Module::Conjuction {
-                    name: name_and_type.trim_start_matches('&').to_owned(),
-                    connected_modules: HashMap::new(),
-                    destination_modules,
-                },
+    // Then combine the hits of each cycle
+    let mut congruences = vec![(0, 1)];
+    for counter in counters {
+        let residues = counter
+            .hits
+            .iter()
+            .filter(|&&h| h > counter.start)
+            .map(|&h| ((h % counter.period) as u128, counter.period as u128))
+            .collect::<Vec<_>>();
+        congruences = congruences
+            .iter()
+            .flat_map(|&a| residues.iter().filter_map(move |&b| crt(a, b)))
+            .collect();
+    }
+    congruences
+        .into_iter()
+        .map(|(r, m)| {
+            let after = end as u128 + 1;
+            r + after.saturating_sub(r).div_ceil(m) * m
+        })
+        .min()
+        .map(|press| press as u64)
+}
 
-                _ => Module::Broadcaster {
-                    name: "broadcaster".to_owned(),
-                    destination_modules,
-                },
+pub fn generator(input: &str) -> Result<PulseNetwork, ParseError> {
+    let source = Source::new(input);
+    let mut network = PulseNetwork {
+        modules: Vec::new(),
+        ids: HashMap::new(),
+        on: Vec::new(),
+        memory: Vec::new(),
+        queue: VecDeque::new(),
+        presses: 0,
+    };
+    let button = network.add("button");
+    network.modules[button].kind = Kind::Button;
+    let lines = input
+        .lines()
+        .map(|line| {
+            let (name_and_type, destinations) = source.split_once(line, " -> ")?;
+            let (name, kind) = match name_and_type.chars().next() {
+                Some('%') => (&name_and_type[1..], Kind::FlipFlop),
+                Some('&') => (&name_and_type[1..], Kind::Conjunction),
+                _ if name_and_type == "broadcaster" => (name_and_type, Kind::Broadcaster),
+                _ => {
+                    return Err(source.error(
+                        name_and_type,
+                        "a flip-flop, a conjunction or the broadcaster",
+                    ))
+                }
             };
-            (module.get_name(), module)
+            let id = network.add(name);
+            network.modules[id].kind = kind;
+            Ok((id, destinations))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    let broadcaster = network
+        .find("broadcaster")
+        .ok_or_else(|| source.error(input, "a broadcaster"))?;
+    let mut connections = vec![(button, broadcaster)];
+    for (from, destinations) in lines {
+        for destination in destinations.split(", ") {
+            connections.push((from, network.add(destination)));
+        }
+    }
+    for (from, to) in connections {
+        network.modules[from].destinations.push(to);
+        network.modules[to].inputs.push(from);
+    }
+    network.on = vec![false; network.modules.len()];
+    network.memory = network
+        .modules
+        .iter()
+        .map(|module| match module.kind {
+            Kind::Conjunction => vec![Pulse::Low; module.inputs.len()],
+            _ => Vec::new(),
         })
         .collect();
-
-    module_network.insert(
-        "button".to_owned(),
-        Module::Button {
-            name: "button".to_owned(),
-            destination_modules: vec!["broadcaster".to_owned()],
-        },
-    );
-
-    initialize_conjuctions_with_low_pulse(&mut module_network);
-
-    module_network
+    Ok(network)
 }
 
-pub fn part_1(input: &HashMap<String, Module>) -> u32 {
-    let mut module_network = input.clone();
-    let (mut n_low_pulses, mut n_high_pulses) = (0, 0);
+pub fn part_1(network: &PulseNetwork) -> u64 {
+    let mut network = network.clone();
+    let mut counter = PulseCounter::default();
     for _ in 0..1000 {
-        let (low_pulses, high_pulses) = simulate_button_press(&mut module_network, |_, _| false);
-        n_low_pulses += low_pulses;
-        n_high_pulses += high_pulses;
+        network.press_and_settle(&mut counter);
+    }
+    if dot::is_active() {
+        dot::export(network.to_dot());
     }
-    n_low_pulses * n_high_pulses
+    counter.low * counter.high
 }
 
-pub fn part_2(input: &HashMap<String, Module>) -> u64 {
-    let parent_of_rx = find_parent_modules(input, "rx");
-    if parent_of_rx.len() != 1 {
-        panic!("This solution only works for when 'rx' have one parent");
+pub fn part_2(network: &PulseNetwork) -> u64 {
+    network
+        .first_press("rx", Pulse::Low)
+        .unwrap_or_else(|e| panic!("{e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    const INPUT_2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = 32000000),
+        example_2: (INPUT_2, part_1 = 11687500),
+    }
+
+    /// A counter of `bits` flip-flops that resets every `period` presses,
+    /// the way the counters feeding `rx` do, sending a high pulse to `out`.
+    /// Like theirs, `period` must be odd and use the highest bit.
+    fn counter(name: &str, bits: u32, period: u32) -> String {
+        let mut lines = Vec::new();
+        let hub = format!("{name}hub");
+        let mut hub_destinations = vec![format!("{name}inv")];
+        for bit in 0..bits {
+            let mut destinations = Vec::new();
+            if bit + 1 < bits {
+                destinations.push(format!("{name}{}", bit + 1));
+            }
+            if period >> bit & 1 == 1 {
+                destinations.push(hub.clone());
+            }
+            if period >> bit & 1 == 0 || bit == 0 {
+                hub_destinations.push(format!("{name}{bit}"));
+            }
+            lines.push(format!("%{name}{bit} -> {}", destinations.join(", ")));
+        }
+        lines.push(format!("&{hub} -> {}", hub_destinations.join(", ")));
+        lines.push(format!("&{name}inv -> out"));
+        lines.join("\n")
+    }
+
+    fn counters(periods: &[u32]) -> String {
+        let names = ["a", "b", "c", "d"];
+        let entries = (0..periods.len()).map(|i| format!("{}0", names[i]));
+        let mut input = format!(
+            "broadcaster -> {}\n",
+            entries.collect::<Vec<_>>().join(", ")
+        );
+        for (name, &period) in names.iter().zip(periods) {
+            input += &counter(name, 4, period);
+            input += "\n";
+        }
+        input + "&out -> rx\n"
+    }
+
+    /// Presses the button until `rx` receives a low pulse.
+    fn brute_force(network: &PulseNetwork) -> u64 {
+        let mut network = network.clone();
+        let rx = network.find("rx").unwrap();
+        let mut received = false;
+        while !received {
+            network.press_and_settle(&mut |_: &PulseNetwork, event: &Event| {
+                received |= event.to == rx && event.pulse == Pulse::Low
+            });
+        }
+        network.presses()
+    }
+
+    #[test]
+    fn combines_counters() {
+        for periods in [&[9, 11][..], &[9, 15], &[11, 13, 15], &[9, 11, 13, 15]] {
+            let network = generator(&counters(periods)).unwrap();
+            let expected = brute_force(&network);
+            assert_eq!(
+                network.first_press("rx", Pulse::Low),
+                Ok(expected),
+                "{periods:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_counters_of_any_shape() {
+        // Counters of one and two flip-flops, joined through inverters
+        let input = "broadcaster -> a, b
+%a -> ia
+%b -> b2
+%b2 -> ib
+&ia -> out
+&ib -> out
+&out -> rx";
+        let network = generator(input).unwrap();
+        assert_eq!(network.first_press("rx", Pulse::Low), Ok(4));
+        assert_eq!(brute_force(&network), 4);
+
+        // A flip-flop feeding rx directly
+        let network = generator("broadcaster -> a\n%a -> rx").unwrap();
+        assert_eq!(network.first_press("rx", Pulse::Low), Ok(2));
+    }
+
+    #[test]
+    fn rejects_dependent_counters() {
+        let input = "broadcaster -> a, b
+%a -> c
+%b -> c
+&c -> inv
+&inv -> rx";
+        let network = generator(input).unwrap();
+        assert!(network.first_press("rx", Pulse::Low).is_err());
+        assert!(network.first_press("nothing", Pulse::Low).is_err());
+    }
+
+    #[test]
+    fn records_pulse_history() {
+        let mut network = generator(INPUT_2).unwrap();
+        let con = network.find("con").unwrap();
+        let mut history = PulseHistory::new([con]);
+        for _ in 0..2 {
+            network.press_and_settle(&mut history);
+        }
+        let sent = history.sent(con);
+        assert_eq!(sent[0], (1, Pulse::High));
+        assert!(sent.iter().all(|&(press, _)| press <= 2));
+        assert_eq!(network.presses(), 2);
+        assert!(history.sent(network.find("a").unwrap()).is_empty());
     }
-    // Assuming that all parents here is a Conjuction
-    find_parent_modules(input, &parent_of_rx[0])
-        .iter()
-        .fold(1, |acc, target| {
-            lcm(acc, calculate_pulse_loop_count(input, target, Pulse::High))
-        })
 }
@@ -1,6 +1,7 @@
-use core::fmt;
 use std::{collections::HashSet, str::FromStr};
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Block {
     start: Position,
@@ -15,74 +16,26 @@ struct Position {
     z: usize,
 }
 
-#[derive(Debug)]
-pub struct ParseBlockError;
-impl fmt::Display for ParseBlockError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl std::error::Error for ParseBlockError {}
-
 impl FromStr for Block {
-    type Err = ParseBlockError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first_pos_str, second_pos_str) = s.split_once('~').ok_or(ParseBlockError)?;
-        let left_bottom_pos = first_pos_str
-            .parse::<Position>()
-            .map_err(|_| ParseBlockError)?;
-        let right_top_pos = second_pos_str
-            .parse::<Position>()
-            .map_err(|_| ParseBlockError)?;
+        let source = Source::new(s);
+        let (first_pos_str, second_pos_str) = source.split_once(s, "~")?;
         Ok(Self {
-            start: left_bottom_pos,
-            end: right_top_pos,
+            start: source.parse_as(first_pos_str)?,
+            end: source.parse_as(second_pos_str)?,
             id: None,
         })
     }
 }
 
-#[derive(Debug)]
-pub enum ParsePositionError {
-    ParseIntError(std::num::ParseIntError),
-    SplitError { e: &'static str },
-}
-
-impl fmt::Display for ParsePositionError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl std::error::Error for ParsePositionError {}
-
 impl FromStr for Position {
-    type Err = ParsePositionError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(',').collect::<Vec<_>>();
-        if parts.len() != 3 {
-            return Err(ParsePositionError::SplitError {
-                e: "Could not split into three number",
-            });
-        }
-        let x = parts.first().ok_or(ParsePositionError::SplitError {
-            e: "Could not get x position",
-        })?;
-        let y = parts.get(1).ok_or(ParsePositionError::SplitError {
-            e: "Could not get y position",
-        })?;
-        let z = parts.get(2).ok_or(ParsePositionError::SplitError {
-            e: "Could not get z position",
-        })?;
-
-        Ok(Position {
-            x: x.parse().map_err(ParsePositionError::ParseIntError)?,
-            y: y.parse().map_err(ParsePositionError::ParseIntError)?,
-            z: z.parse().map_err(ParsePositionError::ParseIntError)?,
-        })
+        let [x, y, z] = Source::new(s).integers(s)?;
+        Ok(Position { x, y, z })
     }
 }
 
@@ -162,19 +115,20 @@ fn fall_to_ground(blocks: &mut Vec<Block>) -> usize {
     moved_blocks.len()
 }
 
-pub fn generator(input: &str) -> Vec<Block> {
+pub fn generator(input: &str) -> Result<Vec<Block>, ParseError> {
+    let source = Source::new(input);
     let mut blocks = input
         .lines()
         .enumerate()
         .map(|(i, l)| {
-            let mut block = l.parse::<Block>().expect("Wrong input format");
+            let mut block: Block = source.parse_as(l)?;
             block.id = Some(i);
-            block
+            Ok(block)
         })
-        .collect::<Vec<Block>>();
+        .collect::<Result<Vec<Block>, ParseError>>()?;
     blocks.sort_by(|a, b| a.start.z.cmp(&b.start.z));
     fall_to_ground(&mut blocks);
-    blocks
+    Ok(blocks)
 }
 
 pub fn part_1(input: &[Block]) -> usize {
@@ -1,7 +1,21 @@
 use std::collections::HashSet;
 
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|l| l.chars().collect()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let source = Source::new(input);
+    let grid = source.grid(
+        input,
+        |_, c| c.is_ascii_graphic().then_some(c),
+        "a digit, a symbol or '.'",
+    )?;
+    let Some(width) = grid.first().map(Vec::len) else {
+        return Err(source.error_after(input, "an engine schematic"));
+    };
+    if let Some(line) = input.lines().find(|line| line.len() != width) {
+        return Err(source.error(line, &format!("a row of {width} cells")));
+    }
+    Ok(grid)
 }
 
 fn is_adjacent(input: &[Vec<char>], i: usize, j: usize) -> bool {
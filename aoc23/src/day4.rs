@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone, Debug)]
 pub struct Card {
     id: u32,
@@ -8,29 +10,31 @@ pub struct Card {
 }
 
 impl FromStr for Card {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (card, numbers) = s.split_once(':').unwrap();
-        let id = card.strip_prefix("Card ").unwrap().trim().parse().unwrap();
-        let (winning_numbers_s, selected_numbers_s) = numbers.split_once('|').unwrap();
-        let winning_numbers = winning_numbers_s
-            .split_whitespace()
-            .map(|n| n.trim().parse().unwrap())
-            .collect();
-        let selected_numbers = selected_numbers_s
-            .split_whitespace()
-            .map(|n| n.trim().parse().unwrap())
-            .collect();
+        let source = Source::new(s);
+        let (card, numbers) = source.split_once(s, ":")?;
+        let id = card
+            .strip_prefix("Card ")
+            .ok_or_else(|| source.error(card, "\"Card \""))?;
+        let id = source.parse(id.trim(), "a card number")?;
+        let (winning_numbers_s, selected_numbers_s) = source.split_once(numbers, "|")?;
+        let numbers = |list: &str| {
+            list.split_whitespace()
+                .map(|n| source.parse(n, "a number"))
+                .collect::<Result<_, _>>()
+        };
         Ok(Card {
             id,
-            winning_numbers,
-            selected_numbers,
+            winning_numbers: numbers(winning_numbers_s)?,
+            selected_numbers: numbers(selected_numbers_s)?,
         })
     }
 }
-pub fn generator(input: &str) -> Vec<Card> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+pub fn generator(input: &str) -> Result<Vec<Card>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|l| source.parse_as(l)).collect()
 }
 
 pub fn part_1(input: &[Card]) -> u32 {
@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 #[derive(Clone, Debug)]
 pub struct Map {
     destination_range_start: Vec<u64>,
@@ -5,37 +7,34 @@ pub struct Map {
     range_length: Vec<u64>,
 }
 
-pub fn generator(input: &str) -> (Vec<u64>, Vec<Map>) {
-    if let Some((seeds_str, maps_str)) = input.split_once('\n') {
-        let seeds = seeds_str
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-        let maps = ("\n".to_owned() + maps_str)
-            .split("\n\n")
-            .skip(1)
-            .map(|m| {
-                let (mut destination_range_start, mut source_range_start, mut range_length) =
-                    (vec![], vec![], vec![]);
-                m.lines().skip(1).for_each(|numbers| {
-                    let number_vec: Vec<u64> = numbers
-                        .split_whitespace()
-                        .map(|n| n.parse().unwrap())
-                        .collect();
-                    destination_range_start.push(number_vec[0]);
-                    source_range_start.push(number_vec[1]);
-                    range_length.push(number_vec[2]);
-                });
-                Map {
-                    destination_range_start,
-                    source_range_start,
-                    range_length,
-                }
+pub fn generator(input: &str) -> Result<(Vec<u64>, Vec<Map>), ParseError> {
+    let source = Source::new(input);
+    let (seeds_str, maps_str) = source.split_once(input, "\n\n")?;
+    let seeds = seeds_str
+        .strip_prefix("seeds:")
+        .ok_or_else(|| source.error(seeds_str, "\"seeds:\""))?
+        .split_whitespace()
+        .map(|s| source.parse(s, "a seed"))
+        .collect::<Result<_, _>>()?;
+    let maps = maps_str
+        .split("\n\n")
+        .map(|m| {
+            let (mut destination_range_start, mut source_range_start, mut range_length) =
+                (vec![], vec![], vec![]);
+            for numbers in m.lines().skip(1) {
+                let [destination, start, length] = source.integers(numbers)?;
+                destination_range_start.push(destination);
+                source_range_start.push(start);
+                range_length.push(length);
+            }
+            Ok(Map {
+                destination_range_start,
+                source_range_start,
+                range_length,
             })
-            .collect();
-        return (seeds, maps);
-    }
-    (vec![], vec![])
+        })
+        .collect::<Result<_, ParseError>>()?;
+    Ok((seeds, maps))
 }
 
 fn find_destination(source: &u64, map: &Map) -> u64 {
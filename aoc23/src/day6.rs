@@ -1,19 +1,22 @@
-pub fn generator(input: &str) -> Vec<(u64, u64)> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let source = Source::new(input);
     let mut number_lines = input.lines().map(|line| {
-        line.split_once(':')
-            .unwrap()
-            .1
+        let (_, numbers) = source.split_once(line, ":")?;
+        numbers
             .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect::<Vec<u64>>()
+            .map(|n| source.parse(n, "a number"))
+            .collect::<Result<Vec<u64>, _>>()
     });
-    number_lines
-        .next()
-        .unwrap()
-        .iter()
-        .zip(number_lines.next().unwrap().iter())
-        .map(|(&n, &m)| (n, m))
-        .collect()
+    let mut next = |expected| {
+        number_lines
+            .next()
+            .unwrap_or_else(|| Err(source.error_after(input, expected)))
+    };
+    let times = next("a line of times")?;
+    let distances = next("a line of distances")?;
+    Ok(times.into_iter().zip(distances).collect())
 }
 
 pub fn is_winning(time: u64, max_time: u64, max_score: u64) -> bool {
@@ -48,14 +51,19 @@ fn binary_search_last_win(max_time: u64, max_score: u64) -> u64 {
     high
 }
 
+fn count_wins(max_time: u64, max_score: u64) -> u64 {
+    let first_win = binary_search_first_win(max_time, max_score);
+    // The search ends on the last time when no time wins.
+    if !is_winning(first_win, max_time, max_score) {
+        return 0;
+    }
+    binary_search_last_win(max_time, max_score) - first_win + 1
+}
+
 pub fn part_1(input: &[(u64, u64)]) -> u64 {
     input
         .iter()
-        .map(|&(max_time, max_score)| {
-            let first_win = binary_search_first_win(max_time, max_score);
-            let last_win = binary_search_last_win(max_time, max_score);
-            last_win.saturating_sub(first_win) + 1
-        })
+        .map(|&(max_time, max_score)| count_wins(max_time, max_score))
         .product()
 }
 
@@ -69,7 +77,42 @@ pub fn part_2(input: &[(u64, u64)]) -> u64 {
         max_time_str.parse::<u64>().unwrap(),
         max_score_str.parse::<u64>().unwrap(),
     );
-    let first_win = binary_search_first_win(max_time, max_score);
-    let last_win = binary_search_last_win(max_time, max_score);
-    last_win.saturating_sub(first_win) + 1
+    count_wins(max_time, max_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::testing::{check, Rng};
+
+    const INPUT: &str = r#"Time:      7  15   30
+Distance:  9  40  200
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 288, part_2 = 71503),
+    }
+
+    fn race(rng: &mut Rng) -> (u64, u64) {
+        let max_time = rng.range(0..=200) as u64;
+        // Up to a bit more than the best distance, so some races are lost.
+        let max_score = rng.range(0..=(max_time * max_time / 4 + 2) as i64) as u64;
+        (max_time, max_score)
+    }
+
+    #[test]
+    fn counts_wins_like_trying_every_time() {
+        check(2000, |rng| {
+            let (max_time, max_score) = race(rng);
+            let expected = (0..=max_time)
+                .filter(|&time| (max_time - time) * time > max_score)
+                .count() as u64;
+            assert_eq!(
+                count_wins(max_time, max_score),
+                expected,
+                "time {max_time}, distance {max_score}"
+            );
+        });
+    }
 }
@@ -1,4 +1,6 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use aoc_utils::prelude::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum Card {
@@ -19,8 +21,8 @@ enum Card {
 }
 
 impl Card {
-    fn from_char(c: char) -> Self {
-        match c {
+    fn from_char(c: char) -> Option<Self> {
+        Some(match c {
             '2' => Card::Two,
             '3' => Card::Three,
             '4' => Card::Four,
@@ -34,8 +36,8 @@ impl Card {
             'Q' => Card::Queen,
             'K' => Card::King,
             'A' => Card::Ace,
-            _ => panic!("Invalid card character"),
-        }
+            _ => return None,
+        })
     }
 }
 
@@ -57,18 +59,27 @@ pub struct Hand {
     stake: u32,
 }
 
-impl Hand {
-    fn new(hand_str: &str) -> Self {
-        let (hand_str, stake_str) = hand_str.split_once(' ').unwrap();
-        let cards: Vec<_> = hand_str.chars().map(Card::from_char).collect();
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (hand_str, stake_str) = source.split_once(s, " ")?;
+        let cards: Vec<_> = hand_str
+            .char_indices()
+            .map(|(i, c)| {
+                Card::from_char(c)
+                    .ok_or_else(|| source.error(&hand_str[i..i + c.len_utf8()], "a card"))
+            })
+            .collect::<Result<_, _>>()?;
         let hand_type = determine_hand_type(&cards);
-        let stake = stake_str.trim().parse().unwrap();
+        let stake = source.parse(stake_str.trim(), "a bid")?;
 
-        Hand {
+        Ok(Hand {
             cards,
             hand_type,
             stake,
-        }
+        })
     }
 }
 
@@ -146,8 +157,9 @@ impl Ord for Hand {
     }
 }
 
-pub fn generator(input: &str) -> Vec<Hand> {
-    input.lines().map(Hand::new).collect()
+pub fn generator(input: &str) -> Result<Vec<Hand>, ParseError> {
+    let source = Source::new(input);
+    input.lines().map(|line| source.parse_as(line)).collect()
 }
 
 pub fn part_1(input: &[Hand]) -> u32 {
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_utils::prelude::*;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Directions {
     Left,
@@ -7,36 +9,43 @@ pub enum Directions {
 }
 
 impl Directions {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            'L' => Directions::Left,
-            'R' => Directions::Right,
-            _ => panic!("Invalid card character"),
+            'L' => Some(Directions::Left),
+            'R' => Some(Directions::Right),
+            _ => None,
         }
     }
 }
 
-pub fn generator(input: &str) -> (Vec<Directions>, HashMap<String, (String, String)>) {
-    if let Some((instructions_str, map_str)) = input.split_once("\n\n") {
-        let instructions = instructions_str
-            .chars()
-            .map(Directions::from_char)
-            .collect();
-        let mut map = HashMap::new();
+type Network = HashMap<String, (String, String)>;
 
-        map_str.lines().for_each(|l| {
-            let (key, destinations) = l.split_once(" = ").unwrap();
-            let destinations = destinations.replace(['(', ')'], "");
-            let (left_destination, right_destination) = destinations.split_once(", ").unwrap();
-            map.insert(
-                key.to_string(),
-                (left_destination.to_string(), right_destination.to_string()),
-            );
-        });
+pub fn generator(input: &str) -> Result<(Vec<Directions>, Network), ParseError> {
+    let source = Source::new(input);
+    let (instructions_str, map_str) = source.split_once(input, "\n\n")?;
+    let instructions = source
+        .grid(
+            instructions_str,
+            |_, c| Directions::from_char(c),
+            "'L' or 'R'",
+        )?
+        .concat();
+    let mut map = HashMap::new();
 
-        return (instructions, map);
+    for l in map_str.lines() {
+        let (key, destinations) = source.split_once(l, " = ")?;
+        let destinations = destinations
+            .strip_prefix('(')
+            .and_then(|d| d.strip_suffix(')'))
+            .ok_or_else(|| source.error(destinations, "a pair of nodes in parentheses"))?;
+        let (left_destination, right_destination) = source.split_once(destinations, ", ")?;
+        map.insert(
+            key.to_string(),
+            (left_destination.to_string(), right_destination.to_string()),
+        );
     }
-    panic!("Error when parsing input.");
+
+    Ok((instructions, map))
 }
 
 fn gcd(a: u64, b: u64) -> u64 {
@@ -80,7 +89,7 @@ where
     steps
 }
 
-pub fn part_1((instructions, map): &(Vec<Directions>, HashMap<String, (String, String)>)) -> u32 {
+pub fn part_1((instructions, map): &(Vec<Directions>, Network)) -> u32 {
     count_steps_to_end(
         "AAA".to_string(),
         instructions.iter().cycle(),
@@ -89,7 +98,7 @@ pub fn part_1((instructions, map): &(Vec<Directions>, HashMap<String, (String, S
     )
 }
 
-pub fn part_2((instructions, map): &(Vec<Directions>, HashMap<String, (String, String)>)) -> u64 {
+pub fn part_2((instructions, map): &(Vec<Directions>, Network)) -> u64 {
     let instructions_cycle = instructions.iter().cycle();
     let currents = map.keys().filter(|s| s.ends_with('A'));
     let steps = currents
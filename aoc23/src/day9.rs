@@ -1,7 +1,14 @@
-pub fn generator(input: &str) -> Vec<Vec<i32>> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()).collect())
+        .map(|l| {
+            l.split_whitespace()
+                .map(|n| source.parse(n, "a number"))
+                .collect()
+        })
         .collect()
 }
 
@@ -1,5 +1,3 @@
-#![recursion_limit = "256"]
-
 mod day1;
 mod day2;
 mod day3;
@@ -22,7 +20,7 @@ mod day19;
 mod day20;
 mod day22;
 
-aoc_main::main! {
+aoc_utils::main! {
     year 2023;
     day1  : generator => part_1, part_2;
     day2  : generator => part_1, part_2;
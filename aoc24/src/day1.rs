@@ -1,38 +1,39 @@
 use std::collections::HashMap;
 
+use aoc_utils::prelude::*;
+
 type NumberVectors = (Vec<u32>, Vec<u32>);
-type ParseResult = Result<NumberVectors, Box<dyn std::error::Error>>;
 
-pub fn generator(input: &str) -> ParseResult {
+pub fn generator(input: &str) -> Result<NumberVectors, ParseError> {
+    let source = Source::new(input);
     let mut left_numbers = Vec::new();
     let mut right_numbers = Vec::new();
 
     for line in input.lines() {
         let mut numbers = line.split_whitespace();
-        let left_str = numbers.next().ok_or("Missing left number")?;
-        let right_str = numbers.next().ok_or("Missing right number")?;
-        let left_num = left_str.parse()?;
-        let right_num = right_str.parse()?;
-        left_numbers.push(left_num);
-        right_numbers.push(right_num);
+        let left_str = numbers
+            .next()
+            .ok_or_else(|| source.error(line, "a left number"))?;
+        let right_str = numbers
+            .next()
+            .ok_or_else(|| source.error_after(left_str, "a right number"))?;
+        left_numbers.push(source.parse(left_str, "a number")?);
+        right_numbers.push(source.parse(right_str, "a number")?);
     }
 
     Ok((left_numbers, right_numbers))
 }
 
-pub fn part_1(input: &ParseResult) -> u32 {
-    let (ref mut left, ref mut right) = input
-        .as_ref()
-        .expect("Input must be parsable to two number vectors")
-        .clone();
+pub fn part_1(input: &NumberVectors) -> u32 {
+    let (ref mut left, ref mut right) = input.clone();
     left.sort_unstable();
     right.sort_unstable();
 
     left.iter().zip(right).map(|(l, r)| l.abs_diff(*r)).sum()
 }
 
-pub fn part_2(input: &ParseResult) -> u32 {
-    let (left, right) = input.as_ref().expect("Invalid input data");
+pub fn part_2(input: &NumberVectors) -> u32 {
+    let (left, right) = input;
     let mut count_map = HashMap::new();
 
     for &num in right {
@@ -56,17 +57,8 @@ mod tests {
 3   3
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 11);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 31);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 11, part_2 = 31),
     }
 }
@@ -1,10 +1,9 @@
 use std::collections::HashSet;
 
-pub fn generator(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    Source::new(input).grid(input, |_, c| c.to_digit(10), "a height")
 }
 
 fn count_paths(
@@ -45,7 +44,7 @@ fn get_next(
                 && new_point.1 >= 0
                 && new_point.0 < map.len() as isize
                 && new_point.1 < map[0].len() as isize
-                && map[new_point.0 as usize][new_point.1 as usize] - map[point.0][point.1] == 1
+                && map[new_point.0 as usize][new_point.1 as usize] == map[point.0][point.1] + 1
             {
                 return Some((new_point.0 as usize, new_point.1 as usize));
             }
@@ -96,17 +95,8 @@ mod tests {
 10456732
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 36);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 81);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 36, part_2 = 81),
     }
 }
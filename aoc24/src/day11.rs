@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
-pub fn generator(input: &str) -> Vec<u64> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u64>, ParseError> {
+    let source = Source::new(input);
     input
         .split_whitespace()
-        .map(|n| n.parse().unwrap())
+        .map(|n| source.parse(n, "a stone"))
         .collect()
 }
 
@@ -67,21 +70,56 @@ pub fn part_2(input: &[u64]) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::testing::check;
 
     const INPUT: &str = r#"125 17
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 55312);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 55312, part_2 = 65601038650482),
+    }
+
+    /// Applies the rules to every stone of the line, one at a time.
+    fn blink_line(stones: &[u64]) -> Vec<u64> {
+        stones
+            .iter()
+            .flat_map(|&stone| {
+                let digits = stone.to_string();
+                if stone == 0 {
+                    vec![1]
+                } else if digits.len() % 2 == 0 {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    vec![left.parse().unwrap(), right.parse().unwrap()]
+                } else {
+                    vec![stone * 2024]
+                }
+            })
+            .collect()
     }
 
     #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 65601038650482);
+    fn blinks_like_the_line_of_stones() {
+        check(2000, |rng| {
+            let mut line = rng.vec(1..=8, |rng| match rng.range(0..=2) {
+                0 => rng.range(0..=9) as u64,
+                _ => rng.range(0..=99_999) as u64,
+            });
+            let mut stones = HashMap::new();
+            for &stone in &line {
+                *stones.entry(stone).or_default() += 1;
+            }
+            // Few enough blinks for the stones to fit in a u64.
+            for _ in 0..rng.range(1..=4) {
+                line = blink_line(&line);
+                blink(&mut stones);
+            }
+
+            let mut expected = HashMap::new();
+            for stone in line {
+                *expected.entry(stone).or_default() += 1;
+            }
+            assert_eq!(stones, expected);
+        });
     }
 }
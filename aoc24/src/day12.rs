@@ -1,7 +1,13 @@
 use std::collections::HashSet;
 
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| c.is_ascii_uppercase().then_some(c),
+        "a plant type",
+    )
 }
 
 fn traverse(map: &[Vec<char>], node: (usize, usize)) -> (u32, u32, HashSet<(usize, usize)>) {
@@ -112,17 +118,8 @@ MIIISIJEEE
 MMMISSJEEE
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 1930);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 1206);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 1930, part_2 = 1206),
     }
 }
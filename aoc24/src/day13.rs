@@ -1,4 +1,6 @@
-use std::{error::Error, fmt, num::ParseFloatError, str::FromStr};
+use std::str::FromStr;
+
+use aoc_utils::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
 pub struct Point {
@@ -6,44 +8,6 @@ pub struct Point {
     y: f64,
 }
 
-#[derive(Debug)]
-pub enum ParseLineError {
-    UnknownLineType,
-    InvalidFormat(String),
-    DuplicateDefinition(&'static str),
-    MissingComponent(&'static str),
-    ParseFloatError(ParseFloatError),
-}
-
-impl fmt::Display for ParseLineError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseLineError::UnknownLineType => write!(f, "Unknown line type"),
-            ParseLineError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
-            ParseLineError::DuplicateDefinition(comp) => {
-                write!(f, "Duplicate definition: {}", comp)
-            }
-            ParseLineError::MissingComponent(comp) => write!(f, "Missing component: {}", comp),
-            ParseLineError::ParseFloatError(e) => write!(f, "Parse float error: {}", e),
-        }
-    }
-}
-
-impl Error for ParseLineError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match self {
-            ParseLineError::ParseFloatError(e) => Some(e),
-            _ => None,
-        }
-    }
-}
-
-impl From<ParseFloatError> for ParseLineError {
-    fn from(error: ParseFloatError) -> Self {
-        ParseLineError::ParseFloatError(error)
-    }
-}
-
 #[derive(Debug)]
 enum LineType {
     ButtonA(Point),
@@ -59,50 +23,36 @@ pub struct SectionData {
 }
 
 impl FromStr for LineType {
-    type Err = ParseLineError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let point = |coordinates: &str, separator: &str| {
+            let (x_str, y_str) = source.split_once(coordinates, separator)?;
+            Ok::<_, ParseError>(Point {
+                x: source.parse(x_str.trim(), "a coordinate")?,
+                y: source.parse(y_str.trim(), "a coordinate")?,
+            })
+        };
+
         if let Some(button_a_str) = s.strip_prefix("Button A: X+") {
-            let (x_str, y_str) =
-                button_a_str
-                    .split_once(", Y+")
-                    .ok_or(ParseLineError::InvalidFormat(
-                        "Invalid format for Button A".to_string(),
-                    ))?;
-            let x = x_str.trim().parse()?;
-            let y = y_str.trim().parse()?;
-            return Ok(LineType::ButtonA(Point { x, y }));
+            return Ok(LineType::ButtonA(point(button_a_str, ", Y+")?));
         }
 
         if let Some(button_b_str) = s.strip_prefix("Button B: X+") {
-            let (x_str, y_str) =
-                button_b_str
-                    .split_once(", Y+")
-                    .ok_or(ParseLineError::InvalidFormat(
-                        "Invalid format for Button B".to_string(),
-                    ))?;
-            let x = x_str.trim().parse()?;
-            let y = y_str.trim().parse()?;
-            return Ok(LineType::ButtonB(Point { x, y }));
+            return Ok(LineType::ButtonB(point(button_b_str, ", Y+")?));
         }
 
         if let Some(prize_str) = s.strip_prefix("Prize: X=") {
-            let (x_str, y_str) =
-                prize_str
-                    .split_once(", Y=")
-                    .ok_or(ParseLineError::InvalidFormat(
-                        "Invalid format for Prize".to_string(),
-                    ))?;
-            let x = x_str.trim().parse()?;
-            let y = y_str.trim().parse()?;
-            return Ok(LineType::Prize(Point { x, y }));
+            return Ok(LineType::Prize(point(prize_str, ", Y=")?));
         }
 
-        Err(ParseLineError::UnknownLineType)
+        Err(source.error(s, "a button or a prize"))
     }
 }
 
-pub fn generator(input: &str) -> Result<Vec<SectionData>, ParseLineError> {
+pub fn generator(input: &str) -> Result<Vec<SectionData>, ParseError> {
+    let source = Source::new(input);
     input
         .split("\n\n")
         .map(|section| {
@@ -110,33 +60,21 @@ pub fn generator(input: &str) -> Result<Vec<SectionData>, ParseLineError> {
             let mut button_b = None;
             let mut prize = None;
             for line in section.lines().take(3) {
-                let parsed_line = line.parse()?;
-                match parsed_line {
-                    LineType::ButtonA(p) => {
-                        if button_a.is_some() {
-                            return Err(ParseLineError::DuplicateDefinition("Button A"));
-                        }
-                        button_a = Some(p);
-                    }
-                    LineType::ButtonB(p) => {
-                        if button_b.is_some() {
-                            return Err(ParseLineError::DuplicateDefinition("Button B"));
-                        }
-                        button_b = Some(p);
-                    }
-                    LineType::Prize(p) => {
-                        if prize.is_some() {
-                            return Err(ParseLineError::DuplicateDefinition("Prize"));
-                        }
-                        prize = Some(p);
-                    }
+                let (component, name) = match source.parse_as(line)? {
+                    LineType::ButtonA(p) => ((&mut button_a, p), "Button A"),
+                    LineType::ButtonB(p) => ((&mut button_b, p), "Button B"),
+                    LineType::Prize(p) => ((&mut prize, p), "Prize"),
+                };
+                if component.0.replace(component.1).is_some() {
+                    return Err(source.error(line, &format!("a single {name}")));
                 }
             }
 
+            let missing = |name| source.error_after(section, &format!("a {name} line"));
             Ok(SectionData {
-                button_a: button_a.ok_or(ParseLineError::MissingComponent("Button A"))?,
-                button_b: button_b.ok_or(ParseLineError::MissingComponent("Button B"))?,
-                prize: prize.ok_or(ParseLineError::MissingComponent("Prize"))?,
+                button_a: button_a.ok_or_else(|| missing("Button A"))?,
+                button_b: button_b.ok_or_else(|| missing("Button B"))?,
+                prize: prize.ok_or_else(|| missing("Prize"))?,
             })
         })
         .collect()
@@ -157,19 +95,15 @@ fn math(button_a: &Point, button_b: &Point, prize: &Point) -> Option<u64> {
     Some(3 * n.round() as u64 + m.round() as u64)
 }
 
-pub fn part_1(input: &Result<Vec<SectionData>, ParseLineError>) -> u64 {
+pub fn part_1(input: &[SectionData]) -> u64 {
     input
-        .as_ref()
-        .expect("Invalid input")
         .iter()
         .filter_map(|play| math(&play.button_a, &play.button_b, &play.prize))
         .sum()
 }
 
-pub fn part_2(input: &Result<Vec<SectionData>, ParseLineError>) -> u64 {
+pub fn part_2(input: &[SectionData]) -> u64 {
     input
-        .as_ref()
-        .expect("Invalid input")
         .iter()
         .filter_map(move |play| {
             let conversion_error = 10_000_000_000_000.0;
@@ -206,17 +140,8 @@ Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 480);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 875318608908);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 480, part_2 = 875318608908),
     }
 }
@@ -1,46 +1,54 @@
 use aoc_utils::prelude::*;
-use std::collections::{HashMap, HashSet};
+use aoc_utils::viz::{self, Cell, Color, Frame};
+use std::collections::HashSet;
 
 pub struct Robot {
     pos: Vec2<i32>,
     vel: Vec2<i32>,
 }
 
-pub fn generator(input: &str) -> Vec<Robot> {
+pub fn generator(input: &str) -> Result<Vec<Robot>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let &[p_x, p_y, v_x, v_y, ..] = extract_integers::<i32>(line).as_slice() else {
-                panic!("Could not find numbers")
-            };
+            let [p_x, p_y, v_x, v_y] = source.integers::<i32, 4>(line)?;
 
-            Robot {
+            Ok(Robot {
                 pos: (p_x, p_y).into(),
                 vel: (v_x, v_y).into(),
-            }
+            })
         })
         .collect()
 }
 
-#[allow(dead_code)]
-fn print_robots(points: &[Vec2<i32>], width: i32, height: i32) {
-    let mut grid = vec![vec!['.'; width as usize]; height as usize];
-    let mut counts: HashMap<Vec2<i32>, usize> = HashMap::new();
+/// The number of robots on every tile of the room.
+struct Room {
+    counts: Vec<Vec<usize>>,
+}
 
-    // Count robots at each point
-    for point in points {
-        *counts.entry(*point).or_insert(0) += 1;
+impl Room {
+    fn new(points: &[Vec2<i32>], width: i32, height: i32) -> Self {
+        let mut counts = vec![vec![0; width as usize]; height as usize];
+        for point in points {
+            counts[point.i()][point.j()] += 1;
+        }
+        Room { counts }
     }
+}
 
-    // Populate grid based on counts
-    for (point, count) in counts {
-        grid[point.y as usize][point.x as usize] =
-            std::char::from_digit(count as u32, 10).unwrap_or('.');
+impl Frame for Room {
+    fn size(&self) -> (usize, usize) {
+        (self.counts[0].len(), self.counts.len())
     }
 
-    // Print the grid
-    for row in grid {
-        println!("{}", row.iter().collect::<String>());
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        match self.counts[y][x] {
+            0 => Cell::new('.').color(Color::Gray),
+            count => Cell::new(std::char::from_digit(count.min(9) as u32, 10).unwrap())
+                .color(Color::Green)
+                .bold(),
+        }
     }
 }
 
@@ -78,19 +86,24 @@ fn find_quadrants(robots: &[Vec2<i32>], width: i32, height: i32) -> (u32, u32, u
     })
 }
 
-const WIDTH: i32 = 101;
-const HEIGHT: i32 = 103;
+static WIDTH: Param<i32> = Param::new("width", 101);
+static HEIGHT: Param<i32> = Param::new("height", 103);
 
 pub fn part_1(input: &[Robot]) -> u32 {
-    let robot_positions = elapse(input, 100, WIDTH, HEIGHT);
-    let q = find_quadrants(&robot_positions, WIDTH, HEIGHT);
+    let (width, height) = (WIDTH.get(), HEIGHT.get());
+    let robot_positions = elapse(input, 100, width, height);
+    let q = find_quadrants(&robot_positions, width, height);
     q.0 * q.1 * q.2 * q.3
 }
 
 pub fn part_2(input: &[Robot]) -> u32 {
+    let (width, height) = (WIDTH.get(), HEIGHT.get());
     (1..)
         .find_map(|i| {
-            let final_positions = elapse(input, i, WIDTH, HEIGHT);
+            let final_positions = elapse(input, i, width, height);
+            if viz::is_active() {
+                viz::show(&Room::new(&final_positions, width, height));
+            }
             let positions_set: HashSet<_> = final_positions.iter().copied().collect();
 
             positions_set
@@ -126,12 +139,8 @@ p=2,4 v=2,-3
 p=9,5 v=-3,-3
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let robot_positions = elapse(&generator_output, 100, 11, 7);
-        assert_eq!(robot_positions.len(), 12);
-        let q = find_quadrants(&robot_positions, 11, 7);
-        assert_eq!(q.0 * q.1 * q.2 * q.3, 12);
+    aoc_utils::aoc_test! {
+        generator =>
+        example { width = 11, height = 7 }: (INPUT, part_1 = 12),
     }
 }
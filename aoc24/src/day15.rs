@@ -1,10 +1,5 @@
-use std::{
-    io::{self, Write},
-    thread::sleep,
-    time::Duration,
-};
-
 use aoc_utils::prelude::*;
+use aoc_utils::viz::{self, Cell, Color, Frame};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -45,50 +40,47 @@ pub struct Grid {
     expandend: bool,
 }
 
-pub fn generator(input: &str) -> (Grid, Vec<Instruction>) {
-    let (map_str, instructions_str) = input.split_once("\n\n").unwrap();
+pub fn generator(input: &str) -> Result<(Grid, Vec<Instruction>), ParseError> {
+    let source = Source::new(input);
+    let (map_str, instructions_str) = source.split_once(input, "\n\n")?;
     let mut robot = Vec2 { x: 0, y: 0 };
-    let map = map_str
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| match c {
-                    '#' => Tile::Wall,
-                    'O' => Tile::BoxLeft,
-                    '.' => Tile::Void,
-                    '@' => {
-                        robot = (j, i).into();
-                        Tile::Void
-                    }
-                    _ => panic!(),
-                })
-                .collect()
-        })
-        .collect();
-    let instructions = instructions_str
-        .lines()
-        .flat_map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '^' => Instruction::Up,
-                    '>' => Instruction::Right,
-                    '<' => Instruction::Left,
-                    'v' => Instruction::Down,
-                    _ => panic!(),
-                })
-                .collect::<Vec<_>>()
-        })
+    let map = source.grid(
+        map_str,
+        |(j, i), c| match c {
+            '#' => Some(Tile::Wall),
+            'O' => Some(Tile::BoxLeft),
+            '.' => Some(Tile::Void),
+            '@' => {
+                robot = (j, i).into();
+                Some(Tile::Void)
+            }
+            _ => None,
+        },
+        "'#', 'O', '.' or '@'",
+    )?;
+    let instructions = source
+        .grid(
+            instructions_str,
+            |_, c| match c {
+                '^' => Some(Instruction::Up),
+                '>' => Some(Instruction::Right),
+                '<' => Some(Instruction::Left),
+                'v' => Some(Instruction::Down),
+                _ => None,
+            },
+            "a move",
+        )?
+        .into_iter()
+        .flatten()
         .collect();
-    (
+    Ok((
         Grid {
             map,
             robot,
             expandend: false,
         },
         instructions,
-    )
+    ))
 }
 
 impl Grid {
@@ -222,27 +214,16 @@ impl Grid {
         self.expandend = true;
     }
 
-    #[allow(dead_code)]
-    fn print_grid(&self) {
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, tile) in row.iter().enumerate() {
-                if x as i32 == self.robot.x && y as i32 == self.robot.y {
-                    print!("@");
-                } else {
-                    match tile {
-                        Tile::Wall => print!("#"),
-                        Tile::BoxLeft => print!("["),
-                        Tile::BoxRight => print!("]"),
-                        Tile::Void => print!("."),
-                    }
-                }
-            }
-            println!();
-        }
-    }
     fn solve(&mut self, instructions: &[Instruction]) -> u32 {
+        let animate = viz::is_active();
+        if animate {
+            viz::show(self);
+        }
         for instruction in instructions {
             self.move_one(instruction);
+            if animate {
+                viz::show(self);
+            }
         }
         let mut res = 0;
         for (i, row) in self.map.iter().enumerate() {
@@ -256,124 +237,22 @@ impl Grid {
     }
 }
 
-#[allow(dead_code)]
-impl Grid {
-    /// Draws a tile at a specific position
-    fn draw_tile<W: Write>(&self, handle: &mut W, x: i32, y: i32, is_robot: bool) {
-        let symbol = if is_robot {
-            "\x1B[1;93m@\x1B[0m" // Robot in bold yellow
-        } else {
-            match self.map.get(y as usize).and_then(|row| row.get(x as usize)) {
-                Some(Tile::Wall) => "\x1B[34m#\x1B[0m",        // Blue wall
-                Some(Tile::BoxLeft) => "\x1B[32m[\x1B[0m",     // Green box left
-                Some(Tile::BoxRight) => "\x1B[32m]\x1B[0m",    // Green box right
-                Some(Tile::Void) | None => "\x1B[90m.\x1B[0m", // Gray empty or out-of-bounds
-            }
-        };
-
-        write!(handle, "\x1B[{};{}H{}", y + 1, x + 1, symbol).unwrap();
-    }
-
-    /// Draws the entire grid
-    fn draw_full_grid(&self) {
-        let stdout = io::stdout();
-        let mut handle = io::BufWriter::new(stdout.lock());
-
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, _) in row.iter().enumerate() {
-                let is_robot = x as i32 == self.robot.x && y as i32 == self.robot.y;
-                self.draw_tile(&mut handle, x as i32, y as i32, is_robot);
-            }
-            writeln!(handle).unwrap();
-        }
-
-        handle.flush().unwrap();
-    }
-
-    fn redraw_square(&self, sleep_interval: u64) {
-        let stdout = io::stdout();
-        let mut handle = io::BufWriter::new(stdout.lock());
-
-        let radius = 25;
-        let center_x = self.robot.x;
-        let center_y = self.robot.y;
-
-        for dy in -radius..=radius {
-            for dx in -radius..=radius {
-                let x = center_x + dx;
-                let y = center_y + dy;
-
-                let is_robot = x == self.robot.x && y == self.robot.y;
-                if x >= 0 && y >= 0 {
-                    self.draw_tile(&mut handle, x, y, is_robot);
-                }
-            }
-        }
-
-        handle.flush().unwrap();
-        sleep(Duration::from_millis(sleep_interval));
+impl Frame for Grid {
+    fn size(&self) -> (usize, usize) {
+        (self.map[0].len(), self.map.len())
     }
 
-    /// Solve function with animation
-    fn solve_animated(&mut self, instructions: &[Instruction], sleep_interval: u64) -> u32 {
-        // Clear the screen and hide the cursor
-        print!("\x1B[2J\x1B[H\x1B[?25l");
-
-        // Draw the entire grid initially
-        self.draw_full_grid();
-
-        for instruction in instructions {
-            self.move_one(instruction);
-            self.redraw_square(sleep_interval);
-        }
-
-        // Calculate the result
-        let mut res = 0;
-        for (i, row) in self.map.iter().enumerate() {
-            for (j, &tile) in row.iter().enumerate() {
-                if matches!(tile, Tile::BoxLeft) {
-                    res += 100 * i + j;
-                }
-            }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        if (x, y) == (self.robot.j(), self.robot.i()) {
+            return Cell::new('@').color(Color::Yellow).bold();
         }
-
-        // Move the cursor below the grid
-        let grid_height = self.map.len();
-        print!("\x1B[{};1H", grid_height + 3);
-
-        // Frame settings
-        let total_width = 32; // Total width of the frame
-        let title = "FINAL RESULT";
-        let score_text = format!("SCORE: {}", res);
-
-        // Helper to center-align text with odd/even padding handling
-        fn center_text(text: &str, width: usize) -> String {
-            let total_padding = width.saturating_sub(text.len());
-            let left_padding = total_padding / 2;
-            let right_padding = total_padding - left_padding;
-            format!(
-                "{:left$}{}{:right$}",
-                "",
-                text,
-                "",
-                left = left_padding,
-                right = right_padding
-            )
+        match self.map[y][x] {
+            Tile::Wall => Cell::new('#').color(Color::Blue),
+            Tile::BoxLeft if !self.expandend => Cell::new('O').color(Color::Green),
+            Tile::BoxLeft => Cell::new('[').color(Color::Green),
+            Tile::BoxRight => Cell::new(']').color(Color::Green),
+            Tile::Void => Cell::new('.').color(Color::Gray),
         }
-
-        // Print the frame and content
-        println!("\x1B[1;96m{}\x1B[0m", "*".repeat(total_width)); // Top border
-        println!("\x1B[1;96m*{}*\x1B[0m", center_text(title, total_width - 2));
-        println!(
-            "\x1B[1;92m*{}*\x1B[0m",
-            center_text(&score_text, total_width - 2)
-        );
-        println!("\x1B[1;96m{}\x1B[0m", "*".repeat(total_width)); // Bottom border
-
-        // Show the cursor again
-        print!("\x1B[?25h");
-
-        res as u32
     }
 }
 
@@ -429,21 +308,22 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 <^^>>>vv<v>>v<<
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 10092);
-
-        let generator_output = generator(INPUT_2);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 2028);
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = 10092, part_2 = 9021),
+        example_2: (INPUT_2, part_1 = 2028),
     }
 
     #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 9021);
+    fn animates_every_move() {
+        let input = generator(INPUT_2).unwrap();
+        let (score, frames) = viz::record(|| part_1(&input));
+        assert_eq!(score, 2028);
+        assert_eq!(frames.len(), input.1.len() + 1);
+        assert_eq!(frames[0].to_string(), INPUT_2.split_once("\n\n").unwrap().0);
+        assert_eq!(
+            frames[frames.len() - 1].to_string(),
+            "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########"
+        );
     }
 }
@@ -2,8 +2,12 @@ use std::collections::{BinaryHeap, HashSet};
 
 use aoc_utils::prelude::*;
 
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| matches!(c, '#' | '.' | 'S' | 'E').then_some(c),
+        "'#', '.', 'S' or 'E'",
+    )
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -201,25 +205,9 @@ mod tests {
 #################
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 7036);
-
-        let generator_output = generator(INPUT_2);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 11048);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 45);
-
-        let generator_output = generator(INPUT_2);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 64);
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = 7036, part_2 = 45),
+        example_2: (INPUT_2, part_1 = 11048, part_2 = 64),
     }
 }
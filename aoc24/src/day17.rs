@@ -1,94 +1,273 @@
+use std::fmt;
+
 use aoc_utils::prelude::*;
 
-pub fn generator(input: &str) -> (Vec<u64>, Vec<u64>) {
-    let (registers, program) = input.split_once("\n\n").unwrap();
-    let program = extract_integers::<u64>(program);
-    (
-        registers
-            .lines()
-            .map(|line| {
-                let &[x, ..] = extract_integers::<u64>(line).as_slice() else {
-                    panic!("Could not parse integers")
-                };
-                x
-            })
-            .collect(),
-        program,
-    )
+/// Prints the disassembled program to stderr.
+static DISASSEMBLE: Param<bool> = Param::new("disassemble", false);
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+/// Whether `opcode` reads its operand as a combo operand.
+fn takes_combo(opcode: u64) -> bool {
+    matches!(opcode, 0 | 2 | 5 | 6 | 7)
 }
 
-fn run_program(register: &[u64], program: &[u64]) -> Vec<u64> {
-    let mut i = 0;
-    let mut outputs = Vec::new();
-    let mut a_value = register[0];
-    let mut b_value = register[1];
-    let mut c_value = register[2];
-    while i < program.len() - 1 {
-        let opcode = program[i];
-        let literal_operand = program[i + 1];
-        let combo_operand = match literal_operand {
-            0..=3 => literal_operand,
-            4 => a_value,
-            5 => b_value,
-            6 => c_value,
-            _ => unreachable!(),
+/// The name of a combo operand: literals stand for themselves and 4 to 6 for
+/// the registers.
+fn combo_name(operand: u64) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "A".to_string(),
+        5 => "B".to_string(),
+        6 => "C".to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+/// Lists the instructions of `program`, one per line, with their address.
+fn disassemble(program: &[u64]) -> String {
+    let mut lines = Vec::new();
+    for (ip, pair) in program.chunks(2).enumerate() {
+        let mnemonic = MNEMONICS.get(pair[0] as usize).unwrap_or(&"???");
+        let line = match (pair[0], pair.get(1)) {
+            (4, _) | (_, None) => mnemonic.to_string(),
+            (opcode, Some(&operand)) if takes_combo(opcode) => {
+                format!("{mnemonic} {}", combo_name(operand))
+            }
+            (_, Some(operand)) => format!("{mnemonic} {operand}"),
         };
+        lines.push(format!("{:>3}: {line}", 2 * ip));
+    }
+    lines.join("\n")
+}
+
+/// The state of the computer after one instruction, and what it output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub ip: usize,
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub out: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChronospatialComputer {
+    a: u64,
+    b: u64,
+    c: u64,
+    ip: usize,
+    program: Vec<u64>,
+    output: Vec<u64>,
+}
+
+impl ChronospatialComputer {
+    fn with_a(&self, a: u64) -> Self {
+        ChronospatialComputer {
+            a,
+            ip: 0,
+            output: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    fn combo(&self, operand: u64) -> u64 {
+        match operand {
+            0..=3 => operand,
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            _ => panic!("Combo operand 7 is reserved"),
+        }
+    }
+
+    /// A divided by two to the power of the combo operand.
+    fn shifted(&self, operand: u64) -> u64 {
+        self.a.checked_shr(self.combo(operand) as u32).unwrap_or(0)
+    }
+
+    /// Executes the instruction at the instruction pointer, unless the
+    /// computer has halted.
+    fn step(&mut self) -> Option<Snapshot> {
+        let (&opcode, &operand) = (self.program.get(self.ip)?, self.program.get(self.ip + 1)?);
+        let mut out = None;
+        self.ip += 2;
         match opcode {
-            0 => {
-                a_value /= 1u64 << combo_operand;
-            }
-            1 => {
-                b_value ^= literal_operand;
-            }
-            2 => {
-                b_value = combo_operand % 8;
-            }
-            3 => {
-                if a_value != 0 {
-                    i = literal_operand as usize;
-                    continue;
-                }
-            }
-            4 => {
-                b_value ^= c_value;
-            }
-            5 => {
-                outputs.push(combo_operand % 8);
-            }
-            6 => {
-                b_value = a_value / (1u64 << combo_operand);
-            }
-            7 => {
-                c_value = a_value / (1u64 << combo_operand);
-            }
+            0 => self.a = self.shifted(operand),
+            1 => self.b ^= operand,
+            2 => self.b = self.combo(operand) % 8,
+            3 if self.a != 0 => self.ip = operand as usize,
+            3 => (),
+            4 => self.b ^= self.c,
+            5 => out = Some(self.combo(operand) % 8),
+            6 => self.b = self.shifted(operand),
+            7 => self.c = self.shifted(operand),
             _ => unreachable!(),
-        };
-        i += 2;
+        }
+        self.output.extend(out);
+        Some(Snapshot {
+            ip: self.ip,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            out,
+        })
     }
-    outputs
+
+    fn run(mut self) -> Vec<u64> {
+        while self.step().is_some() {}
+        self.output
+    }
+}
+
+/// A value computed by one iteration of the program's loop, in terms of the
+/// register A at the start of the iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    A,
+    /// B or C as left behind by the previous iteration.
+    Previous(char),
+    Literal(u64),
+    Xor(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+    Shr(Box<Expr>, Box<Expr>),
 }
 
-fn find_inital_value(register: &[u64], program: &[u64]) -> u64 {
-    let mut valid = vec![0];
-    for n in (0..program.len()).rev() {
-        let old_valid = valid.clone();
-        valid = Vec::new();
-        for num in old_valid {
-            for offset in 0..8 {
-                let new_num = 8 * num + offset;
-                let output = run_program(&[new_num, register[1], register[2]], program);
-                if output.as_slice() == &program[n..] {
-                    valid.push(new_num)
+impl Expr {
+    fn eval(&self, a: u64) -> u64 {
+        match self {
+            Expr::A => a,
+            Expr::Previous(r) => panic!("{r} is not known at the start of the iteration"),
+            Expr::Literal(x) => *x,
+            Expr::Xor(l, r) => l.eval(a) ^ r.eval(a),
+            Expr::Mod8(x) => x.eval(a) % 8,
+            Expr::Shr(l, r) => l.eval(a).checked_shr(r.eval(a) as u32).unwrap_or(0),
+        }
+    }
+
+    fn uses_previous(&self) -> bool {
+        match self {
+            Expr::Previous(_) => true,
+            Expr::A | Expr::Literal(_) => false,
+            Expr::Mod8(x) => x.uses_previous(),
+            Expr::Xor(l, r) | Expr::Shr(l, r) => l.uses_previous() || r.uses_previous(),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::A => write!(f, "A"),
+            Expr::Previous(r) => write!(f, "{r}'"),
+            Expr::Literal(x) => write!(f, "{x}"),
+            Expr::Xor(l, r) => write!(f, "({l} ^ {r})"),
+            Expr::Mod8(x) => write!(f, "{x} % 8"),
+            Expr::Shr(l, r) => write!(f, "({l} >> {r})"),
+        }
+    }
+}
+
+/// One iteration of a program shaped like the puzzle inputs: a loop ending in
+/// `jnz 0` that shifts A right by a constant, outputs one value computed from
+/// A alone and carries nothing else over to the next iteration.
+#[derive(Debug, PartialEq, Eq)]
+struct Iteration {
+    shift: u32,
+    output: Expr,
+}
+
+impl Iteration {
+    /// Executes the loop body symbolically.
+    fn analyse(program: &[u64]) -> Result<Self, String> {
+        let body = match program {
+            [body @ .., 3, 0] if body.len() % 2 == 0 => body,
+            _ => return Err("the program does not end with jnz 0".to_string()),
+        };
+        let (mut a, mut b, mut c) = (Expr::A, Expr::Previous('B'), Expr::Previous('C'));
+        let mut shift = None;
+        let mut output = None;
+        for pair in body.chunks(2) {
+            let (opcode, operand) = (pair[0], pair[1]);
+            let combo = match operand {
+                0..=3 => Expr::Literal(operand),
+                4 => a.clone(),
+                5 => b.clone(),
+                6 => c.clone(),
+                _ => return Err("the program uses the reserved combo operand".to_string()),
+            };
+            let shifted = |x: &Expr| Expr::Shr(Box::new(x.clone()), Box::new(combo.clone()));
+            match opcode {
+                0 if operand <= 3 && shift.is_none() => {
+                    shift = Some(operand as u32);
+                    a = shifted(&a);
                 }
+                0 => return Err("A is not shifted by one constant".to_string()),
+                1 => b = Expr::Xor(Box::new(b), Box::new(Expr::Literal(operand))),
+                2 => b = Expr::Mod8(Box::new(combo)),
+                3 => return Err("the loop jumps before its end".to_string()),
+                4 => b = Expr::Xor(Box::new(b), Box::new(c.clone())),
+                5 if output.is_none() => output = Some(Expr::Mod8(Box::new(combo))),
+                5 => return Err("the loop outputs more than once".to_string()),
+                6 => b = shifted(&a),
+                7 => c = shifted(&a),
+                _ => return Err(format!("{opcode} is not an opcode")),
             }
         }
+        let shift = shift.filter(|&s| s > 0).ok_or("A is never shifted")?;
+        let output = output.ok_or("the loop outputs nothing")?;
+        if output.uses_previous() {
+            return Err(format!(
+                "the output {output} depends on the previous iteration"
+            ));
+        }
+        Ok(Iteration { shift, output })
     }
-    valid.into_iter().min().unwrap()
+
+    /// The smallest positive A for which the loop outputs `target`, choosing
+    /// the bits shifted out by each iteration from the last output backwards.
+    fn find_input(&self, target: &[u64]) -> Option<u64> {
+        let mut candidates = vec![0];
+        for &value in target.iter().rev() {
+            candidates = candidates
+                .into_iter()
+                .flat_map(|rest| (0..1 << self.shift).map(move |bits| rest << self.shift | bits))
+                .filter(|&a| a != 0 && self.output.eval(a) == value)
+                .collect();
+        }
+        candidates.into_iter().min()
+    }
+}
+
+pub fn generator(input: &str) -> Result<ChronospatialComputer, ParseError> {
+    let source = Source::new(input);
+    let (registers, program) = source.split_once(input, "\n\n")?;
+    let program = extract_integers::<u64>(program);
+    let values = registers
+        .lines()
+        .map(|line| source.integers(line).map(|[x]| x))
+        .collect::<Result<Vec<_>, _>>()?;
+    let [a, b, c] = values[..] else {
+        return Err(source.error(registers, "three registers"));
+    };
+    Ok(ChronospatialComputer {
+        a,
+        b,
+        c,
+        ip: 0,
+        program,
+        output: Vec::new(),
+    })
 }
 
-pub fn part_1(input: &(Vec<u64>, Vec<u64>)) -> String {
-    let (register, program) = input;
-    let outputs = run_program(register, program);
+pub fn part_1(computer: &ChronospatialComputer) -> String {
+    if DISASSEMBLE.get() {
+        eprintln!("{}", disassemble(&computer.program));
+        if let Ok(iteration) = Iteration::analyse(&computer.program) {
+            eprintln!("Outputs {} per iteration", iteration.output);
+        }
+    }
+    let outputs = computer.clone().run();
     outputs
         .iter()
         .map(|x| x.to_string())
@@ -96,9 +275,15 @@ pub fn part_1(input: &(Vec<u64>, Vec<u64>)) -> String {
         .join(",")
 }
 
-pub fn part_2(input: &(Vec<u64>, Vec<u64>)) -> u64 {
-    let (register, program) = input;
-    find_inital_value(register, program)
+pub fn part_2(computer: &ChronospatialComputer) -> u64 {
+    let program = &computer.program;
+    let iteration =
+        Iteration::analyse(program).unwrap_or_else(|e| panic!("Unsupported program: {e}"));
+    let a = iteration
+        .find_input(program)
+        .expect("No value of A outputs the program");
+    assert_eq!(&computer.with_a(a).run(), program);
+    a
 }
 
 #[cfg(test)]
@@ -119,17 +304,101 @@ Register C: 0
 Program: 0,3,5,4,3,0
 "#;
 
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = "4,6,3,5,6,3,5,2,1,0"),
+        example_2: (INPUT_2, part_2 = 117440),
+    }
+
+    /// A program shaped like the puzzle inputs.
+    const LOOP: &str = r#"Register A: 0
+Register B: 0
+Register C: 0
+
+Program: 2,4,1,1,7,5,1,5,4,0,5,5,0,3,3,0
+"#;
+
+    #[test]
+    fn disassembles() {
+        let computer = generator(LOOP).unwrap();
+        let expected = [
+            "  0: bst A",
+            "  2: bxl 1",
+            "  4: cdv B",
+            "  6: bxl 5",
+            "  8: bxc",
+            " 10: out B",
+            " 12: adv 3",
+            " 14: jnz 0",
+        ];
+        assert_eq!(disassemble(&computer.program), expected.join("\n"));
+    }
+
+    #[test]
+    fn steps() {
+        let mut computer = generator(INPUT_1).unwrap();
+        let snapshots = std::iter::from_fn(|| computer.step()).collect::<Vec<_>>();
+        assert_eq!(snapshots.len(), 30);
+        assert_eq!(
+            snapshots[..3],
+            [
+                Snapshot {
+                    ip: 2,
+                    a: 364,
+                    b: 0,
+                    c: 0,
+                    out: None
+                },
+                Snapshot {
+                    ip: 4,
+                    a: 364,
+                    b: 0,
+                    c: 0,
+                    out: Some(4)
+                },
+                Snapshot {
+                    ip: 0,
+                    a: 364,
+                    b: 0,
+                    c: 0,
+                    out: None
+                },
+            ]
+        );
+        assert_eq!(computer.step(), None);
+    }
+
     #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_1(&generator_output);
-        assert_eq!(result, "4,6,3,5,6,3,5,2,1,0");
+    fn analyses_iterations() {
+        let computer = generator(LOOP).unwrap();
+        let iteration = Iteration::analyse(&computer.program).unwrap();
+        assert_eq!(iteration.shift, 3);
+        assert_eq!(
+            iteration.output.to_string(),
+            "(((A % 8 ^ 1) ^ 5) ^ (A >> (A % 8 ^ 1))) % 8"
+        );
+        for a in [1, 8, 1234, 987654321] {
+            let first = computer.with_a(a).run()[0];
+            assert_eq!(iteration.output.eval(a), first);
+        }
+
+        let program = generator(INPUT_1).unwrap().program;
+        let iteration = Iteration::analyse(&program).unwrap();
+        assert_eq!(iteration.shift, 1);
+        assert_eq!(iteration.output.to_string(), "(A >> 1) % 8");
+
+        assert!(Iteration::analyse(&[0, 1, 5, 4]).is_err());
+        assert!(Iteration::analyse(&[1, 1, 5, 5, 0, 3, 3, 0]).is_err());
     }
 
     #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT_2);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 117440);
+    fn finds_quines() {
+        let computer = generator(LOOP).unwrap();
+        let quine = part_2(&computer);
+        assert_eq!(computer.with_a(quine).run(), computer.program);
+        let smaller = (0..quine).rev().take(10_000);
+        assert!(smaller
+            .into_iter()
+            .all(|a| computer.with_a(a).run() != computer.program));
     }
 }
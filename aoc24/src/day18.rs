@@ -5,23 +5,22 @@ use std::{
 
 use aoc_utils::prelude::*;
 
-pub const WIDTH: i32 = if cfg!(test) { 6 } else { 70 };
-pub const HEIGHT: i32 = if cfg!(test) { 6 } else { 70 };
-pub const PART_1_MEMORY_SPACE: usize = if cfg!(test) { 12 } else { 1024 };
+static SIZE: Param<i32> = Param::new("size", 70);
+static BYTES: Param<usize> = Param::new("bytes", 1024);
 
-pub fn generator(input: &str) -> Vec<Vec2<i32>> {
+pub fn generator(input: &str) -> Result<Vec<Vec2<i32>>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let &[x, y, ..] = extract_integers::<i32>(line).as_slice() else {
-                panic!("Could not parse integers")
-            };
-            (x, y).into()
+            let [x, y] = source.integers::<i32, 2>(line)?;
+            Ok((x, y).into())
         })
         .collect()
 }
 
 fn dijkstra(map: &HashSet<Vec2<i32>>, end: &Vec2<i32>) -> Option<u32> {
+    let size = end.x;
     let mut heap = BinaryHeap::new();
     let mut visited = HashSet::new();
     let start: Vec2<i32> = (0, 0).into();
@@ -39,7 +38,7 @@ fn dijkstra(map: &HashSet<Vec2<i32>>, end: &Vec2<i32>) -> Option<u32> {
 
         visited.insert(pos);
 
-        for new_pos in get_neighbours(map, pos) {
+        for new_pos in get_neighbours(map, pos, size) {
             heap.push(cmp::Reverse((score + 1, new_pos)));
         }
     }
@@ -50,14 +49,15 @@ fn dijkstra(map: &HashSet<Vec2<i32>>, end: &Vec2<i32>) -> Option<u32> {
 fn get_neighbours(
     map: &HashSet<Vec2<i32>>,
     pos: Vec2<i32>,
+    size: i32,
 ) -> impl Iterator<Item = Vec2<i32>> + use<'_> {
     [(-1, 0), (1, 0), (0, -1), (0, 1)]
         .into_iter()
         .filter_map(move |(di, dj)| {
             let new_pos = pos + (dj, di);
-            if new_pos.x > WIDTH
+            if new_pos.x > size
                 || new_pos.x < 0
-                || new_pos.y > HEIGHT
+                || new_pos.y > size
                 || new_pos.y < 0
                 || map.contains(&new_pos)
             {
@@ -68,14 +68,14 @@ fn get_neighbours(
 }
 
 pub fn part_1(input: &[Vec2<i32>]) -> u32 {
-    let corrupted_bytes: HashSet<_> = input.iter().take(PART_1_MEMORY_SPACE).copied().collect();
+    let corrupted_bytes: HashSet<_> = input.iter().take(BYTES.get()).copied().collect();
 
-    let end: Vec2<i32> = (HEIGHT, WIDTH).into();
+    let end: Vec2<i32> = (SIZE.get(), SIZE.get()).into();
     dijkstra(&corrupted_bytes, &end).unwrap()
 }
 
 pub fn part_2(input: &[Vec2<i32>]) -> String {
-    let end: Vec2<i32> = (HEIGHT, WIDTH).into();
+    let end: Vec2<i32> = (SIZE.get(), SIZE.get()).into();
     let mut left = 0;
     let mut right = input.len();
     let mut mid = 0;
@@ -123,17 +123,8 @@ mod tests {
 2,0
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 22);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, "6,1");
+    aoc_utils::aoc_test! {
+        generator =>
+        example { size = 6, bytes = 12 }: (INPUT, part_1 = 22, part_2 = "6,1"),
     }
 }
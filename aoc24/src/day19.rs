@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
-pub fn generator(input: &str) -> (Vec<&str>, Vec<&str>) {
-    let (patterns_str, designs_str) = input.split_once("\n\n").expect("Invalid input");
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<(Vec<&str>, Vec<&str>), ParseError> {
+    let (patterns_str, designs_str) = Source::new(input).split_once(input, "\n\n")?;
     let patterns = patterns_str.split(", ").collect();
     let designs = designs_str.lines().collect();
-    (patterns, designs)
+    Ok((patterns, designs))
 }
 
 fn find_combinations<'a>(
@@ -71,17 +73,8 @@ brgr
 bbrgwb
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 6);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 16);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 6, part_2 = 16),
     }
 }
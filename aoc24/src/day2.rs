@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 fn is_safe_iter<'a, I>(mut iter: I) -> bool
 where
     I: Iterator<Item = &'a u32>,
@@ -29,13 +31,14 @@ where
     true
 }
 
-pub fn generator(input: &str) -> Vec<Vec<u32>> {
+pub fn generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|level| {
             level
                 .split_whitespace()
-                .map(|n| n.parse().unwrap())
+                .map(|n| source.parse(n, "a level"))
                 .collect()
         })
         .collect()
@@ -80,17 +83,8 @@ mod tests {
 1 3 6 7 9
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 2);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 4);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 2, part_2 = 4),
     }
 }
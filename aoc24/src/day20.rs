@@ -6,30 +6,23 @@ pub const PART_1_SAVING: u32 = if cfg!(test) { 1 } else { 100 };
 pub const PART_2_SAVING: u32 = if cfg!(test) { 50 } else { 100 };
 
 type Map = Vec<Vec<char>>;
-pub fn generator(input: &str) -> (Map, Vec2<i32>, Vec2<i32>) {
+pub fn generator(input: &str) -> Result<(Map, Vec2<i32>, Vec2<i32>), ParseError> {
     let mut start = (0, 0).into();
     let mut end = (0, 0).into();
-    let map = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(j, c)| match c {
-                    'S' => {
-                        start = (j, i).into();
-                        c
-                    }
-                    'E' => {
-                        end = (j, i).into();
-                        c
-                    }
-                    _ => c,
-                })
-                .collect()
-        })
-        .collect();
-    (map, start, end)
+    let map = Source::new(input).grid(
+        input,
+        |pos, c| {
+            match c {
+                'S' => start = pos.into(),
+                'E' => end = pos.into(),
+                '#' | '.' => {}
+                _ => return None,
+            }
+            Some(c)
+        },
+        "'#', '.', 'S' or 'E'",
+    )?;
+    Ok((map, start, end))
 }
 
 fn flood_fill(map: &[Vec<char>], start: &Vec2<i32>, end: &Vec2<i32>) -> Option<Vec<Vec2<i32>>> {
@@ -110,17 +103,8 @@ mod tests {
 ###############
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 44);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 285);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 44, part_2 = 285),
     }
 }
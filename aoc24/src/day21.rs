@@ -5,13 +5,11 @@ use std::{
 
 use aoc_utils::prelude::*;
 
-pub fn generator(input: &str) -> Vec<(&str, u64)> {
+pub fn generator(input: &str) -> Result<Vec<(&str, u64)>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|line| {
-            let nums = extract_integers::<u64>(line);
-            (line, nums[0])
-        })
+        .map(|line| source.integers(line).map(|[num]| (line, num)))
         .collect()
 }
 
@@ -158,17 +156,9 @@ mod tests {
 379A
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 126384);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator("029A");
-        let result = part_2(&generator_output);
-        assert_eq!(result, 2379451789590);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 126384),
+        single_code: ("029A", part_2 = 2379451789590),
     }
 }
@@ -1,9 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn generator(input: &str) -> Vec<u64> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<u64>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
-        .map(|line| line.parse().expect("Input musy be parsable to a number"))
+        .map(|line| source.parse(line, "a secret number"))
         .collect()
 }
 
@@ -66,17 +69,9 @@ mod tests {
 3
 2024
 "#;
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 37327623);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT_2);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 23);
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = 37327623),
+        example_2: (INPUT_2, part_2 = 23),
     }
 }
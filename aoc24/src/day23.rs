@@ -1,12 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn generator(input: &str) -> HashMap<&str, HashSet<&str>> {
-    input.lines().fold(HashMap::new(), |mut acc, line| {
-        let (a, b) = line.split_once("-").unwrap();
-        acc.entry(a).or_default().insert(b);
-        acc.entry(b).or_default().insert(a);
-        acc
-    })
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<HashMap<&str, HashSet<&str>>, ParseError> {
+    let source = Source::new(input);
+    let mut connections: HashMap<_, HashSet<_>> = HashMap::new();
+    for line in input.lines() {
+        let (a, b) = source.split_once(line, "-")?;
+        connections.entry(a).or_default().insert(b);
+        connections.entry(b).or_default().insert(a);
+    }
+    Ok(connections)
 }
 
 fn bron_kerbosch<'a>(
@@ -137,17 +141,8 @@ tb-vc
 td-yn
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 7);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, "co,de,ka,ta".to_string());
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 7, part_2 = "co,de,ka,ta".to_string()),
     }
 }
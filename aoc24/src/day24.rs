@@ -1,197 +1,379 @@
-use std::{collections::HashMap, fmt::Display};
+use aoc_utils::circuit::{Circuit, Gate, Netlist, Signal, Values, Wire};
+use aoc_utils::dot;
+use aoc_utils::prelude::*;
+use aoc_utils::testing::Rng;
 
-#[derive(Clone, Copy)]
-pub enum Operator {
-    And,
-    Or,
-    Xor,
+/// Prints why each pair of swapped wires was found.
+static EXPLAIN: Param<bool> = Param::new("explain", false);
+
+/// The wires given a value in the input, with that value.
+type InitialValues = Vec<(Wire, u64)>;
+
+pub fn generator(input: &str) -> Result<(Circuit, InitialValues), ParseError> {
+    let source = Source::new(input);
+    let (values, operators) = source.split_once(input, "\n\n")?;
+    let mut circuit = Circuit::new(1);
+    let values = values
+        .lines()
+        .map(|line| {
+            let (name, value) = source.split_once(line, ": ")?;
+            Ok((circuit.wire(name), source.parse(value, "a wire value")?))
+        })
+        .collect::<Result<_, ParseError>>()?;
+    for line in operators.lines() {
+        let mut parts = line.split_whitespace();
+        let mut next = |previous: &str, expected| {
+            parts
+                .next()
+                .ok_or_else(|| source.error_after(previous, expected))
+        };
+        let first_operand = next(line, "a wire")?;
+        let operator_str = next(first_operand, "a gate")?;
+        let second_operand = next(operator_str, "a wire")?;
+        let arrow = next(second_operand, "\"->\"")?;
+        let assign = next(arrow, "a wire")?;
+        let a = Signal::Wire(circuit.wire(first_operand));
+        let b = Signal::Wire(circuit.wire(second_operand));
+        let gate = match operator_str {
+            "AND" => Gate::And(a, b),
+            "OR" => Gate::Or(a, b),
+            "XOR" => Gate::Xor(a, b),
+            _ => return Err(source.error(operator_str, "AND, OR or XOR")),
+        };
+        let output = circuit.wire(assign);
+        circuit.connect(output, gate);
+    }
+    Ok((circuit, values))
+}
+
+fn evaluate_circuit(
+    x: Option<u64>,
+    y: Option<u64>,
+    circuit: &Circuit,
+    netlist: &Netlist,
+    initial: &InitialValues,
+) -> Values {
+    let mut values = netlist.values();
+    for &(wire, value) in initial {
+        values.set(wire, value);
+    }
+    if let Some(x) = x {
+        values.write_bus(&circuit.bus("x"), x);
+    }
+    if let Some(y) = y {
+        values.write_bus(&circuit.bus("y"), y);
+    }
+    netlist.evaluate(&mut values);
+    values
+}
+
+/// A pair of gate outputs swapped in the circuit, and how it deviates from
+/// the adder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fault {
+    swap: (String, String),
+    reason: String,
+}
+
+/// What a circuit is meant to compute from its `x` and `y` buses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Intent {
+    Add,
+    /// One gate per pair of input bits, such as the AND of the example.
+    Bitwise(&'static str),
 }
 
-impl Display for Operator {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Intent {
+    /// Recognises adders by their extra output bit, and bitwise circuits by
+    /// their single operator.
+    fn of(circuit: &Circuit) -> Result<Intent, String> {
+        let (bits, z) = (circuit.bus("x").len(), circuit.bus("z").len());
+        if bits == 0 || circuit.bus("y").len() != bits {
+            return Err("the x and y buses differ in size".to_string());
+        }
+        let mut operators = circuit
+            .wires()
+            .filter_map(|w| circuit.driver(w))
+            .map(Gate::operator);
+        match (z == bits + 1, operators.next()) {
+            (true, _) => Ok(Intent::Add),
+            (false, Some(op)) if z == bits && operators.all(|o| o == op) => Ok(Intent::Bitwise(op)),
+            _ => Err("the circuit is neither an adder nor a bitwise operation".to_string()),
+        }
+    }
+
+    fn apply(self, x: u64, y: u64) -> u64 {
         match self {
-            Operator::And => write!(f, " & "),
-            Operator::Or => write!(f, " | "),
-            Operator::Xor => write!(f, " ^ "),
+            Intent::Add => x + y,
+            Intent::Bitwise("AND") => x & y,
+            Intent::Bitwise("OR") => x | y,
+            Intent::Bitwise(_) => x ^ y,
         }
     }
 }
 
-type CircuitGates<'a> = HashMap<&'a str, (&'a str, Operator, &'a str)>;
-type WireValues<'a> = HashMap<&'a str, u8>;
+/// Checks a circuit against a ripple-carry adder, bit by bit from the lowest,
+/// and undoes the swapped outputs it finds on the way. Every bit but the
+/// first should be a full adder:
+///
+/// ```text
+/// sum     = x XOR y        z       = sum XOR carry
+/// both    = x AND y        through = sum AND carry
+///                          carry'  = both OR through
+/// ```
+struct Verifier {
+    circuit: Circuit,
+    faults: Vec<Fault>,
+    /// The carry into the next bit to check.
+    carry: Option<Wire>,
+}
 
-pub fn generator(input: &str) -> (WireValues, CircuitGates) {
-    let (values, operators) = input.split_once("\n\n").unwrap();
-    let values = values
-        .lines()
-        .map(|line| {
-            let (name, value) = line.split_once(": ").unwrap();
-            let value = value.parse().unwrap();
-            (name, value)
+impl Verifier {
+    fn gates(&self) -> impl Iterator<Item = (Wire, [Wire; 2], &'static str)> + '_ {
+        self.circuit.wires().filter_map(|wire| {
+            let gate = self.circuit.driver(wire)?;
+            let mut inputs = gate.inputs();
+            Some((wire, [inputs.next()?, inputs.next()?], gate.operator()))
         })
-        .collect();
-    let operations = operators
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let first_operand = parts.next().unwrap();
-            let operator = match parts.next().unwrap() {
-                "AND" => Operator::And,
-                "OR" => Operator::Or,
-                "XOR" => Operator::Xor,
-                _ => unreachable!(),
-            };
-            let second_operand = parts.next().unwrap();
-            parts.next();
-            let assign = parts.next().unwrap();
-            (assign, (first_operand, operator, second_operand))
+    }
+
+    /// The output of the gate `op` reading `a` and `b`.
+    fn find(&self, op: &str, a: Wire, b: Wire) -> Option<Wire> {
+        self.gates()
+            .find(|(_, inputs, o)| *o == op && (inputs == &[a, b] || inputs == &[b, a]))
+            .map(|(output, ..)| output)
+    }
+
+    /// The output and other input of a gate `op` reading `a`.
+    fn find_with(&self, op: &str, a: Wire) -> Option<(Wire, Wire)> {
+        self.gates().find_map(|(output, [p, q], o)| match o == op {
+            true if p == a => Some((output, q)),
+            true if q == a => Some((output, p)),
+            _ => None,
         })
-        .collect();
-    (values, operations)
-}
+    }
 
-fn compute_value<'a>(node: &'a str, values: &mut WireValues<'a>, gates: &CircuitGates<'a>) -> u8 {
-    if let Some(&val) = values.get(node) {
-        return val;
+    fn name(&self, wire: Wire) -> String {
+        self.circuit.name(wire).to_string()
     }
 
-    let (left, op, right) = gates[node];
+    fn operator(&self, wire: Wire) -> &'static str {
+        self.circuit
+            .driver(wire)
+            .map_or("nothing", |g| g.operator())
+    }
 
-    let left_val = compute_value(left, values, gates);
-    let right_val = compute_value(right, values, gates);
+    fn swap(&mut self, a: Wire, b: Wire, reason: String) {
+        self.circuit.swap_drivers(a, b);
+        let swap = (self.name(a), self.name(b));
+        self.faults.push(Fault { swap, reason });
+    }
 
-    let val = match op {
-        Operator::And => left_val & right_val,
-        Operator::Or => left_val | right_val,
-        Operator::Xor => left_val ^ right_val,
-    };
+    /// Checks bit `i` and moves on to the carry out of it, or returns `false`
+    /// if a swap was undone and the bit should be checked again.
+    fn check_bit(&mut self, i: usize) -> Result<bool, String> {
+        let bus = |prefix| self.circuit.bus(prefix);
+        let (x, y, z) = (bus("x")[i], bus("y")[i], bus("z")[i]);
+        let (xn, yn, zn) = (self.name(x), self.name(y), self.name(z));
+        let sum = self
+            .find("XOR", x, y)
+            .ok_or(format!("{xn} and {yn} are not XORed"))?;
+        let both = self
+            .find("AND", x, y)
+            .ok_or(format!("{xn} and {yn} are not ANDed"))?;
 
-    values.insert(node, val);
-    val
-}
+        let Some(carry) = self.carry else {
+            if sum != z {
+                let op = self.operator(z);
+                let reason = format!("{zn} is produced by {op}, expected XOR of {xn} and {yn}");
+                self.swap(z, sum, reason);
+                return Ok(false);
+            }
+            self.carry = Some(both);
+            return Ok(true);
+        };
 
-fn resolve_all_gates<'a>(values: &mut WireValues<'a>, gates: &CircuitGates<'a>) {
-    for node in gates.keys() {
-        compute_value(node, values, gates);
+        let (sn, cn) = (self.name(sum), self.name(carry));
+        match self.find_with("XOR", carry) {
+            Some((output, _)) if output != z => {
+                let op = self.operator(z);
+                let reason = format!("{zn} is produced by {op}, expected XOR of {sn} and {cn}");
+                self.swap(z, output, reason);
+                return Ok(false);
+            }
+            Some((_, other)) if other != sum => {
+                let on = self.name(other);
+                let reason = format!("{zn} reads {on}, expected {sn}, the XOR of {xn} and {yn}");
+                self.swap(sum, other, reason);
+                return Ok(false);
+            }
+            Some(_) => (),
+            None => {
+                let (_, other) = self
+                    .find_with("XOR", sum)
+                    .ok_or(format!("nothing XORs {sn} with the carry {cn}"))?;
+                let on = self.name(other);
+                let reason = format!("{zn} reads {on}, expected the carry {cn} into bit {i}");
+                self.swap(carry, other, reason);
+                // The gate producing the carry now drives `other`
+                self.carry = Some(other);
+                return Ok(false);
+            }
+        }
+
+        let through = self
+            .find("AND", sum, carry)
+            .ok_or(format!("{sn} and {cn} are not ANDed"))?;
+        let (bn, tn) = (self.name(both), self.name(through));
+        if let Some((output, other)) = self.find_with("OR", through) {
+            if other == both {
+                self.carry = Some(output);
+                return Ok(true);
+            }
+            let (on, outn) = (self.name(other), self.name(output));
+            let reason = format!("{outn} reads {on}, expected {bn}, the AND of {xn} and {yn}");
+            self.swap(both, other, reason);
+            return Ok(false);
+        }
+        let (output, other) = self
+            .find_with("OR", both)
+            .ok_or(format!("{bn} and {tn} are not ORed into a carry"))?;
+        let (on, outn) = (self.name(other), self.name(output));
+        let reason = format!("{outn} reads {on}, expected {tn}, the AND of {sn} and {cn}");
+        self.swap(through, other, reason);
+        Ok(false)
     }
-}
 
-fn swap_map_entries<K, V>(map: &mut HashMap<K, V>, k1: K, k2: K)
-where
-    K: Eq + std::hash::Hash,
-    V: Clone,
-{
-    if let (Some(v1), Some(v2)) = (map.remove(&k1), map.remove(&k2)) {
-        map.insert(k1, v2);
-        map.insert(k2, v1);
+    /// Finds the swaps turning `circuit` back into an adder.
+    fn verify(circuit: &Circuit) -> Result<Vec<Fault>, String> {
+        let mut verifier = Verifier {
+            circuit: circuit.clone(),
+            faults: Vec::new(),
+            carry: None,
+        };
+        let (bits, z) = (circuit.bus("x").len(), circuit.bus("z"));
+        if bits == 0 || circuit.bus("y").len() != bits || z.len() != bits + 1 {
+            return Err("the buses do not have the sizes of an adder".to_string());
+        }
+        for i in 0..bits {
+            // Undoing a swap may reveal another one in the same bit
+            let mut attempts = 0;
+            while !verifier.check_bit(i)? {
+                attempts += 1;
+                if attempts == 8 {
+                    return Err(format!("bit {i} is still faulty after undoing swaps"));
+                }
+            }
+        }
+        let carry = verifier.carry.unwrap();
+        if carry != z[bits] {
+            let (zn, cn) = (verifier.name(z[bits]), verifier.name(carry));
+            let op = verifier.operator(z[bits]);
+            let reason = format!("{zn} is produced by {op}, expected the carry {cn}");
+            verifier.swap(z[bits], carry, reason);
+        }
+        Ok(verifier.faults)
     }
-}
 
-fn evaluate_circuit<'a>(
-    x: Option<u64>,
-    y: Option<u64>,
-    values: &WireValues<'a>,
-    gates: &CircuitGates<'a>,
-) -> u64 {
-    let mut values = values.clone();
-    if let Some(x) = x {
-        values
-            .iter_mut()
-            .filter(|(k, _)| k.starts_with("x"))
-            .for_each(|(k, v)| {
-                let idx = k.strip_prefix("x").unwrap().parse::<u64>().unwrap();
-                *v = ((x >> idx) & 1) as u8;
-            })
-    };
-    if let Some(y) = y {
-        values
-            .iter_mut()
-            .filter(|(k, _)| k.starts_with("y"))
-            .for_each(|(k, v)| {
-                let idx = k.strip_prefix("y").unwrap().parse::<u64>().unwrap();
-                *v = ((y >> idx) & 1) as u8;
-            })
-    };
-
-    resolve_all_gates(&mut values, gates);
-    values
-        .iter()
-        .filter(|(k, _)| k.starts_with("z"))
-        .fold(0u64, |acc, (k, v)| {
-            let raise = k.strip_prefix('z').unwrap().parse::<u64>().unwrap();
-            if *v == 1 {
-                acc + (1 << raise)
-            } else {
-                acc
+    /// Finds the swaps making every bit of `z` the `op` of the same bits of
+    /// `x` and `y`.
+    fn verify_bitwise(circuit: &Circuit, op: &str) -> Result<Vec<Fault>, String> {
+        let mut verifier = Verifier {
+            circuit: circuit.clone(),
+            faults: Vec::new(),
+            carry: None,
+        };
+        let bus = |prefix| circuit.bus(prefix);
+        for (i, ((x, y), z)) in bus("x").into_iter().zip(bus("y")).zip(bus("z")).enumerate() {
+            let (xn, yn, zn) = (verifier.name(x), verifier.name(y), verifier.name(z));
+            let output = verifier
+                .find(op, x, y)
+                .ok_or(format!("{xn} and {yn} are not combined by {op}"))?;
+            if output != z {
+                let inputs = verifier
+                    .circuit
+                    .driver(z)
+                    .map(|g| g.inputs().collect::<Vec<_>>());
+                let reads = match inputs.as_deref() {
+                    Some(&[a, b]) => format!("{} and {}", verifier.name(a), verifier.name(b)),
+                    _ => "something else".to_string(),
+                };
+                let reason = format!("{zn} reads {reads}, expected bit {i} of x and y");
+                verifier.swap(z, output, reason);
             }
-        })
+        }
+        Ok(verifier.faults)
+    }
 }
 
-fn trace_wire<'a>(
-    node: &str,
-    values: &WireValues<'a>,
-    gates: &CircuitGates<'a>,
-    depth: usize,
-) -> String {
-    if depth >= 4 {
-        return "".to_string();
-    }
-    if values.contains_key(node) {
-        return format!("{:>depth$}{}\n", "", node, depth = depth * 4);
+fn swapped(circuit: &Circuit, faults: &[Fault]) -> Circuit {
+    let mut circuit = circuit.clone();
+    for Fault { swap: (a, b), .. } in faults {
+        let (a, b) = (circuit.wire(a), circuit.wire(b));
+        circuit.swap_drivers(a, b);
     }
-
-    let (left, operator, right) = gates[node];
-    let op1 = trace_wire(left, values, gates, depth + 1);
-    let op2 = trace_wire(right, values, gates, depth + 1);
-
-    let operator_str = match operator {
-        Operator::And => "&",
-        Operator::Or => "|",
-        Operator::Xor => "^",
-    };
-
-    // Ensure every argument is properly formatted and included
-    format!(
-        "{:>depth$}{}: {} {} {}\n{}{}",
-        "",
-        node,
-        left,
-        operator_str,
-        right,
-        op1,
-        op2,
-        depth = depth * 4
-    )
+    circuit
 }
 
-pub fn part_1((values, gates): &(WireValues, CircuitGates)) -> u64 {
-    evaluate_circuit(None, None, values, gates)
+pub fn part_1((circuit, initial): &(Circuit, InitialValues)) -> u64 {
+    let netlist = circuit.netlist().unwrap_or_else(|e| panic!("{e}"));
+    let values = evaluate_circuit(None, None, circuit, &netlist, initial);
+    if dot::is_active() {
+        dot::export(circuit.to_dot("gates", Some(&values)));
+    }
+    values.read_bus(&circuit.bus("z"))
 }
 
-pub fn part_2((values, gates): &(WireValues, CircuitGates)) -> String {
-    let mut gates = gates.clone();
-    // swap_map_entries(&mut gates, "z10", "vcf");
-    swap_map_entries(&mut gates, "z17", "fhg");
-    swap_map_entries(&mut gates, "dvb", "fsq");
-    swap_map_entries(&mut gates, "z39", "tnc");
-    for i in 0..45 {
-        for j in 0..45 {
-            let x = 1 << i;
-            let y = 1 << j;
-            let z = evaluate_circuit(Some(x), Some(y), values, &gates);
-            assert_eq!(
-                z,
-                x + y,
-                "Faulty gate z{j:0>2}:\n{}\n{}\n{}",
-                trace_wire(&format!("z{:0>2}", j - 1), values, &gates, 0),
-                trace_wire(&format!("z{:0>2}", j), values, &gates, 0),
-                trace_wire(&format!("z{:0>2}", j + 1), values, &gates, 0),
-            );
+pub fn part_2((circuit, initial): &(Circuit, InitialValues)) -> String {
+    let intent = Intent::of(circuit).unwrap_or_else(|e| panic!("{e}"));
+    let faults = match intent {
+        Intent::Add => Verifier::verify(circuit),
+        Intent::Bitwise(op) => Verifier::verify_bitwise(circuit, op),
+    }
+    .unwrap_or_else(|e| panic!("Cannot repair the circuit: {e}"));
+    if EXPLAIN.get() {
+        for Fault {
+            swap: (a, b),
+            reason,
+        } in &faults
+        {
+            eprintln!("Swapping {a} and {b}: {reason}");
         }
     }
-    let mut a = ["z10", "vcf", "z17", "fhg", "fsq", "dvb", "z39", "tnc"];
-    a.sort_unstable();
-    a.join(",")
+    let fixed = swapped(circuit, &faults);
+    let netlist = fixed.netlist().unwrap_or_else(|e| panic!("{e}"));
+
+    // Check the repaired circuit on random inputs
+    let bits = circuit.bus("x").len() as u32;
+    let mut rng = Rng::new(bits as u64);
+    for _ in 0..100 {
+        let (x, y) = (rng.next_u64() >> (64 - bits), rng.next_u64() >> (64 - bits));
+        let values = evaluate_circuit(Some(x), Some(y), &fixed, &netlist, initial);
+        let z = values.read_bus(&fixed.bus("z"));
+        let expected = intent.apply(x, y);
+        assert_eq!(
+            z, expected,
+            "{intent:?} of {x} and {y} after swapping {faults:?}"
+        );
+    }
+
+    if dot::is_active() {
+        // The repaired circuit, outlining the wires whose gates were swapped
+        let mut graph = fixed.to_dot("repaired", None);
+        for Fault { swap: (a, b), .. } in &faults {
+            for wire in [a, b] {
+                graph.node(wire).set("color", "red").set("penwidth", "3");
+            }
+        }
+        dot::export(graph);
+    }
+
+    let mut wires = faults
+        .into_iter()
+        .flat_map(|Fault { swap: (a, b), .. }| [a, b])
+        .collect::<Vec<_>>();
+    wires.sort_unstable();
+    wires.join(",")
 }
 
 #[cfg(test)]
@@ -279,21 +461,133 @@ x03 AND y03 -> z03
 x04 AND y04 -> z04
 x05 AND y05 -> z00
 "#;
+    aoc_utils::aoc_test! {
+        generator =>
+        example_1: (INPUT_1, part_1 = 4),
+        example_2: (INPUT_2, part_1 = 2024),
+        example_3: (INPUT_3, part_2 = "z00,z01,z02,z05".to_string()),
+    }
+
+    /// A ripple-carry adder of `bits` bits, with the outputs of each pair of
+    /// `swaps` exchanged.
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines = Vec::new();
+        for bus in ["x", "y"] {
+            lines.extend((0..bits).map(|i| format!("{bus}{i:02}: 1")));
+        }
+        lines.push(String::new());
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("x00 AND y00 -> c01".to_string());
+        for i in 1..bits {
+            let carry = format!("c{i:02}");
+            let carry_out = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                format!("c{:02}", i + 1)
+            };
+            lines.extend([
+                format!("x{i:02} XOR y{i:02} -> s{i:02}"),
+                format!("x{i:02} AND y{i:02} -> a{i:02}"),
+                format!("s{i:02} XOR {carry} -> z{i:02}"),
+                format!("{carry} AND s{i:02} -> t{i:02}"),
+                format!("a{i:02} OR t{i:02} -> {carry_out}"),
+            ]);
+        }
+        for line in &mut lines {
+            for &(a, b) in swaps {
+                if let Some(gate) = line.strip_suffix(&format!(" -> {a}")) {
+                    *line = format!("{gate} -> {b}");
+                } else if let Some(gate) = line.strip_suffix(&format!(" -> {b}")) {
+                    *line = format!("{gate} -> {a}");
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn faults(swaps: &[(&str, &str)]) -> Vec<Fault> {
+        let (circuit, _) = generator(&adder(8, swaps)).unwrap();
+        Verifier::verify(&circuit).unwrap()
+    }
+
+    #[test]
+    fn verifies_adders() {
+        let (circuit, initial) = generator(&adder(8, &[])).unwrap();
+        assert_eq!(Verifier::verify(&circuit), Ok(vec![]));
+        assert_eq!(part_1(&(circuit, initial)), 510);
+    }
+
+    /// Checks that swapping `a` and `b` is found, and why.
+    fn explains(a: &str, b: &str, reason: &str) {
+        let faults = faults(&[(a, b)]);
+        assert_eq!(faults.len(), 1, "{faults:?}");
+        let (x, y) = &faults[0].swap;
+        assert!((x, y) == (&a.into(), &b.into()) || (y, x) == (&a.into(), &b.into()));
+        assert_eq!(faults[0].reason, reason);
+    }
+
+    #[test]
+    fn explains_faults() {
+        explains(
+            "z00",
+            "c01",
+            "z00 is produced by AND, expected XOR of x00 and y00",
+        );
+        explains(
+            "z03",
+            "t03",
+            "z03 is produced by AND, expected XOR of s03 and c03",
+        );
+        explains(
+            "z04",
+            "c05",
+            "z04 is produced by OR, expected XOR of s04 and c04",
+        );
+        explains(
+            "s05",
+            "a05",
+            "z05 reads s05, expected a05, the XOR of x05 and y05",
+        );
+        explains(
+            "c06",
+            "t07",
+            "z06 reads c06, expected the carry t07 into bit 6",
+        );
+        explains(
+            "a06",
+            "a07",
+            "c07 reads a06, expected a07, the AND of x06 and y06",
+        );
+        explains(
+            "z08",
+            "c07",
+            "z07 reads c07, expected the carry z08 into bit 7",
+        );
+    }
+
     #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT_1);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 4);
-
-        let generator_output = generator(INPUT_2);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 2024);
+    fn repairs_several_swaps() {
+        let swaps = [
+            ("z01", "t01"),
+            ("s03", "a03"),
+            ("a04", "a05"),
+            ("z06", "c07"),
+        ];
+        let input = adder(8, &swaps);
+        let answer = part_2(&generator(&input).unwrap());
+        assert_eq!(answer, "a03,a04,a05,c07,s03,t01,z01,z06");
     }
 
     #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT_3);
-        let result = part_2(&generator_output);
-        assert_eq!(result, "z00,z01,z02,z05".to_string());
+    fn repairs_bitwise_circuits() {
+        let (circuit, _) = generator(INPUT_3).unwrap();
+        assert_eq!(Intent::of(&circuit), Ok(Intent::Bitwise("AND")));
+        let faults = Verifier::verify_bitwise(&circuit, "AND").unwrap();
+        assert_eq!(faults.len(), 2);
+        assert_eq!(faults[0].swap, ("z00".into(), "z05".into()));
+        assert_eq!(
+            faults[0].reason,
+            "z00 reads x05 and y05, expected bit 0 of x and y"
+        );
     }
 }
@@ -1,9 +1,18 @@
+use aoc_utils::prelude::*;
+
 const COLUMNS: usize = 5;
 
-pub fn generator(input: &str) -> Vec<Vec<Vec<char>>> {
+pub fn generator(input: &str) -> Result<Vec<Vec<Vec<char>>>, ParseError> {
+    let source = Source::new(input);
     input
         .split("\n\n")
-        .map(|grid| grid.lines().map(|line| line.chars().collect()).collect())
+        .map(|grid| {
+            source.grid(
+                grid,
+                |_, c| matches!(c, '#' | '.').then_some(c),
+                "'#' or '.'",
+            )
+        })
         .collect()
 }
 
@@ -81,10 +90,8 @@ mod tests {
 #####
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 3);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 3),
     }
 }
@@ -1,10 +1,12 @@
+use aoc_utils::prelude::*;
+
 pub enum Operator {
     Mul(u32, u32),
     Do,
     DoNot,
 }
 
-pub fn generator(input: &str) -> Vec<Operator> {
+pub fn generator(input: &str) -> Result<Vec<Operator>, ParseError> {
     let mut chars = input.chars().peekable();
     let mut operators = Vec::new();
 
@@ -75,7 +77,7 @@ pub fn generator(input: &str) -> Vec<Operator> {
             }
         }
     }
-    operators
+    Ok(operators)
 }
 
 pub fn part_1(input: &[Operator]) -> u32 {
@@ -116,17 +118,9 @@ mod tests {
     const INPUT2: &str =
         r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 161);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT2);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 48);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 161),
+        example_2: (INPUT2, part_2 = 48),
     }
 }
@@ -1,5 +1,11 @@
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| matches!(c, 'X' | 'M' | 'A' | 'S').then_some(c),
+        "one of 'X', 'M', 'A' or 'S'",
+    )
 }
 
 pub fn part_1(input: &[Vec<char>]) -> u32 {
@@ -128,17 +134,8 @@ MAMMMXMMMM
 MXMXAXMASX
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 18);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 9);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 18, part_2 = 9),
     }
 }
@@ -5,6 +5,8 @@ use std::{
     rc::Rc,
 };
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone, Eq)]
 pub struct OrderedItem<T>
 where
@@ -51,32 +53,32 @@ where
 
 type Updates<'a, T> = Vec<Vec<OrderedItem<T>>>;
 
-pub fn generator(input: &str) -> Updates<u32> {
-    let (ordering_rules_str, update_str) = input.split_once("\n\n").unwrap();
-
-    let ordering_rules = Rc::new(ordering_rules_str.lines().fold(
-        HashMap::new(),
-        |mut acc: HashMap<_, HashSet<_>>, line| {
-            let (left, right) = line.split_once('|').unwrap();
-            let n = left.parse::<u32>().unwrap();
-            let m = right.parse::<u32>().unwrap();
-            acc.entry(m).or_default().insert(n);
-            acc
-        },
-    ));
-
-    let updates = update_str
+pub fn generator(input: &str) -> Result<Updates<u32>, ParseError> {
+    let source = Source::new(input);
+    let (ordering_rules_str, update_str) = source.split_once(input, "\n\n")?;
+
+    let mut ordering_rules: HashMap<_, HashSet<_>> = HashMap::new();
+    for line in ordering_rules_str.lines() {
+        let (left, right) = source.split_once(line, "|")?;
+        let n = source.parse::<u32>(left, "a page number")?;
+        let m = source.parse::<u32>(right, "a page number")?;
+        ordering_rules.entry(m).or_default().insert(n);
+    }
+    let ordering_rules = Rc::new(ordering_rules);
+
+    update_str
         .lines()
         .map(|line| {
             line.split(',')
-                .map(|n| OrderedItem {
-                    value: n.parse::<u32>().unwrap(),
-                    ordering_rules: Rc::clone(&ordering_rules),
+                .map(|n| {
+                    Ok(OrderedItem {
+                        value: source.parse::<u32>(n, "a page number")?,
+                        ordering_rules: Rc::clone(&ordering_rules),
+                    })
                 })
                 .collect()
         })
-        .collect();
-    updates
+        .collect()
 }
 
 pub fn part_1(input: &Updates<u32>) -> u32 {
@@ -135,17 +137,8 @@ mod tests {
 97,13,75,29,47
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 143);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 123);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 143, part_2 = 123),
     }
 }
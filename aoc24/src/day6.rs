@@ -1,3 +1,5 @@
+use aoc_utils::prelude::*;
+
 #[derive(Clone)]
 pub struct Map {
     grid: Vec<Vec<u8>>,
@@ -20,49 +22,34 @@ enum StepResult {
     Step,
 }
 
-pub fn generator(input: &str) -> Map {
-    let mut guard_dir = Direction::North;
-    let mut guard_pos = (0, 0);
-    let grid = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            line.bytes()
-                .enumerate()
-                .map(|(j, c)| match c {
-                    b'^' => {
-                        guard_dir = Direction::North;
-                        guard_pos = (j, i);
-                        b'.'
-                    }
-                    b'v' => {
-                        guard_dir = Direction::South;
-                        guard_pos = (j, i);
-                        b'.'
-                    }
-                    b'>' => {
-                        guard_dir = Direction::East;
-                        guard_pos = (j, i);
-                        b'.'
-                    }
-                    b'<' => {
-                        guard_dir = Direction::West;
-                        guard_pos = (j, i);
-                        b'.'
-                    }
-                    _ => c,
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+pub fn generator(input: &str) -> Result<Map, ParseError> {
+    let source = Source::new(input);
+    let mut guard = None;
+    let grid = source.grid(
+        input,
+        |pos, c| {
+            let dir = match c {
+                '.' | '#' => return Some(c as u8),
+                '^' => Direction::North,
+                'v' => Direction::South,
+                '>' => Direction::East,
+                '<' => Direction::West,
+                _ => return None,
+            };
+            guard = Some((pos, dir));
+            Some(b'.')
+        },
+        "'.', '#' or a guard",
+    )?;
+    let (guard_pos, guard_dir) = guard.ok_or_else(|| source.error(input, "a guard"))?;
     let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
     visited[guard_pos.1][guard_pos.0] = true;
-    Map {
+    Ok(Map {
         grid,
         guard_dir,
         guard_pos,
         visited,
-    }
+    })
 }
 
 impl Map {
@@ -216,17 +203,8 @@ mod tests {
 ......#...
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 41);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 6);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 41, part_2 = 6),
     }
 }
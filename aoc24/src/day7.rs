@@ -1,16 +1,17 @@
-pub fn generator(input: &str) -> Vec<(u64, Vec<u64>)> {
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    let source = Source::new(input);
     input
         .lines()
         .map(|line| {
-            let (val_str, operands_str) = line.split_once(": ").expect("Invalid input");
-            let val = val_str
-                .parse()
-                .expect("Target must be parsable to a number");
+            let (val_str, operands_str) = source.split_once(line, ": ")?;
+            let val = source.parse(val_str, "a test value")?;
             let operands = operands_str
                 .split_whitespace()
-                .map(|n| n.parse().expect("Operands must be parsable to a number"))
-                .collect();
-            (val, operands)
+                .map(|n| source.parse(n, "an operand"))
+                .collect::<Result<_, _>>()?;
+            Ok((val, operands))
         })
         .collect()
 }
@@ -120,17 +121,8 @@ mod tests {
 292: 11 6 16 20
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 3749);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 11387);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 3749, part_2 = 11387),
     }
 }
@@ -1,7 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Source::new(input).grid(
+        input,
+        |_, c| (c == '.' || c.is_ascii_alphanumeric()).then_some(c),
+        "'.' or an antenna",
+    )
 }
 
 fn find_unique_antennas(antennas: &[Vec<char>]) -> HashMap<char, Vec<(usize, usize)>> {
@@ -86,7 +92,7 @@ pub fn part_2(input: &[Vec<char>]) -> u32 {
 mod tests {
     use super::*;
 
-    const INPUT: &str = r#""............
+    const INPUT: &str = r#"............
 ........0...
 .....0......
 .......0....
@@ -100,17 +106,8 @@ mod tests {
 ............
 "#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 14);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 34);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 14, part_2 = 34),
     }
 }
@@ -1,24 +1,30 @@
 use std::cmp::Ordering;
 
+use aoc_utils::prelude::*;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DiskEntry {
     File { id: usize, size: usize },
     Blank { size: usize },
 }
 
-pub fn generator(input: &str) -> Vec<DiskEntry> {
+pub fn generator(input: &str) -> Result<Vec<DiskEntry>, ParseError> {
+    let source = Source::new(input);
     input
         .char_indices()
         .map(|(i, c)| {
-            let size = c.to_digit(10).unwrap() as usize;
+            let size = c
+                .to_digit(10)
+                .ok_or_else(|| source.error(&input[i..i + c.len_utf8()], "a digit"))?
+                as usize;
             if i % 2 == 0 {
                 let id = i / 2;
-                DiskEntry::File { id, size }
+                Ok(DiskEntry::File { id, size })
             } else {
-                DiskEntry::Blank { size }
+                Ok(DiskEntry::Blank { size })
             }
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 fn calculate_checksum(memory: &[DiskEntry]) -> u64 {
@@ -114,17 +120,8 @@ mod tests {
 
     const INPUT: &str = r#"2333133121414131402"#;
 
-    #[test]
-    fn part_1_test() {
-        let generator_output = generator(INPUT);
-        let result = part_1(&generator_output);
-        assert_eq!(result, 1928);
-    }
-
-    #[test]
-    fn part_2_test() {
-        let generator_output = generator(INPUT);
-        let result = part_2(&generator_output);
-        assert_eq!(result, 2858);
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 1928, part_2 = 2858),
     }
 }
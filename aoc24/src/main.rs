@@ -1,5 +1,3 @@
-#![recursion_limit = "256"]
-
 mod day1;
 mod day2;
 mod day3;
@@ -26,7 +24,7 @@ mod day23;
 mod day24;
 mod day25;
 
-aoc_main::main! {
+aoc_utils::main! {
     year 2024;
     day1  : generator => part_1, part_2;
     day2  : generator => part_1, part_2;
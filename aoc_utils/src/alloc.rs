@@ -0,0 +1,180 @@
+//! Allocation accounting for the solvers.
+//!
+//! With the `alloc-stats` feature, [`Counting`] is installed as the global
+//! allocator and the runner reports, for every stage, how many allocations it
+//! made, how many bytes they requested and the peak of memory it held at once.
+//! Each year forwards the feature, so a day is inspected with:
+//!
+//! ```shell
+//! cargo run --release --features alloc-stats -- -d 17
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, counting every allocation.
+pub struct Counting;
+
+impl Counting {
+    /// Records a block going from `old` to `new` bytes, counting only the
+    /// difference so that blocks resized in place are not counted twice.
+    fn record(&self, old: usize, new: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        if new >= old {
+            let grown = new - old;
+            ALLOCATED.fetch_add(grown, Relaxed);
+            let live = LIVE.fetch_add(grown, Relaxed) + grown;
+            PEAK.fetch_max(live, Relaxed);
+        } else {
+            LIVE.fetch_sub(old - new, Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record(layout.size(), new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The allocations made while running a stage.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Number of allocations and reallocations.
+    pub allocations: usize,
+    /// Bytes requested by these allocations.
+    pub bytes: usize,
+    /// Largest amount of memory held at once, above what was live before.
+    pub peak: usize,
+}
+
+/// Returns whether [`Counting`] is the global allocator.
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Runs `f`, returning the allocations it made when the counting allocator is
+/// installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let allocated = ALLOCATED.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+
+    let result = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED.load(Relaxed) - allocated,
+        peak: PEAK.load(Relaxed).saturating_sub(live),
+    };
+    (result, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit.
+fn human_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.;
+    let mut unit = 0;
+    while value >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            human_bytes(self.bytes),
+            human_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(human_bytes(0), "0 B");
+        assert_eq!(human_bytes(1023), "1023 B");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(3 << 30), "3.0 GiB");
+
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 4096,
+            peak: 2048,
+        };
+        assert_eq!(stats.to_string(), "3 allocs, 4.0 KiB, peak 2.0 KiB");
+    }
+
+    #[test]
+    fn counts_allocations() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let allocations = ALLOCATIONS.load(Relaxed);
+        let allocated = ALLOCATED.load(Relaxed);
+
+        unsafe {
+            let ptr = Counting.alloc(layout);
+            let ptr = Counting.realloc(ptr, layout, 128);
+            let grown = Layout::from_size_align(128, 8).unwrap();
+            let ptr = Counting.realloc(ptr, grown, 32);
+            Counting.dealloc(ptr, Layout::from_size_align(32, 8).unwrap());
+        }
+
+        // Growing from 64 to 128 bytes only allocates 64 more
+        assert!(ALLOCATIONS.load(Relaxed) - allocations >= 3);
+        assert!(ALLOCATED.load(Relaxed) - allocated >= 128);
+        assert!(PEAK.load(Relaxed) >= 128);
+    }
+}
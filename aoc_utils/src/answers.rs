@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// The expected answers for one year, as recorded in its `answers.toml`.
+///
+/// The file lists one `[[answer]]` table per solved part:
+///
+/// ```toml
+/// year = 2015
+///
+/// [[answer]]
+/// day = 1
+/// part = 1
+/// expected = 138
+/// ```
+///
+/// `expected` may be an integer or a string. Multi-line answers (such as
+/// letters drawn in ASCII art) are compared after trimming surrounding
+/// whitespace.
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub year: u16,
+    expected: HashMap<(u8, u8), String>,
+}
+
+/// The outcome of checking one solver against the recorded answers.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The solver produced the recorded answer.
+    Pass,
+    /// The solver produced something else.
+    Fail { expected: String, actual: String },
+    /// No answer is recorded for this part yet.
+    Missing,
+}
+
+#[derive(Deserialize)]
+struct AnswersFile {
+    year: u16,
+    #[serde(default)]
+    answer: Vec<Answer>,
+}
+
+#[derive(Deserialize)]
+struct Answer {
+    day: u8,
+    part: u8,
+    expected: Expected,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Integer(i64),
+    Text(String),
+}
+
+/// An error raised while reading an `answers.toml` file.
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Duplicate { day: u8, part: u8 },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(err) => write!(f, "could not read answers: {err}"),
+            AnswersError::Toml(err) => write!(f, "invalid answers file: {err}"),
+            AnswersError::Duplicate { day, part } => {
+                write!(f, "day {day} part {part} is listed more than once")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl Answers {
+    /// Reads the answers stored at `path`.
+    ///
+    /// A missing file is not an error: it simply yields an empty registry for
+    /// `year`, so every part is reported as missing.
+    pub fn load(path: &Path, year: u16) -> Result<Self, AnswersError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => content.parse(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Answers {
+                year,
+                ..Default::default()
+            }),
+            Err(err) => Err(AnswersError::Io(err)),
+        }
+    }
+
+    /// Returns the recorded answer for `part` of `day`, if any.
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.expected.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compares `actual` with the recorded answer for `part` of `day`.
+    pub fn check(&self, day: u8, part: u8, actual: &str) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected.trim() == actual.trim() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.trim().to_string(),
+                actual: actual.trim().to_string(),
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: AnswersFile = toml::from_str(s).map_err(AnswersError::Toml)?;
+        let mut expected = HashMap::new();
+        for Answer {
+            day,
+            part,
+            expected: value,
+        } in file.answer
+        {
+            let value = match value {
+                Expected::Integer(n) => n.to_string(),
+                Expected::Text(s) => s,
+            };
+            if expected.insert((day, part), value).is_some() {
+                return Err(AnswersError::Duplicate { day, part });
+            }
+        }
+
+        Ok(Answers {
+            year: file.year,
+            expected,
+        })
+    }
+}
+
+/// Returns the puzzle part a solver answers, based on its name.
+///
+/// Solvers are named `part_1`, `part_2` or variants such as
+/// `part_1_solution_2`. Any other name (like a lone `solution` on day 25)
+/// answers part 1.
+pub fn part_of(solver: &str) -> u8 {
+    solver
+        .strip_prefix("part_")
+        .and_then(|rest| rest.split('_').next())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+year = 2022
+
+[[answer]]
+day = 1
+part = 1
+expected = 24000
+
+[[answer]]
+day = 1
+part = 2
+expected = "45000"
+
+[[answer]]
+day = 10
+part = 2
+expected = """
+##..
+#..#
+"""
+"#;
+
+    #[test]
+    fn parses_integers_and_strings() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.year, 2022);
+        assert_eq!(answers.get(1, 1), Some("24000"));
+        assert_eq!(answers.get(1, 2), Some("45000"));
+        assert_eq!(answers.get(2, 1), None);
+    }
+
+    #[test]
+    fn check_reports_verdicts() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.check(1, 1, "24000"), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 2, "44999"),
+            Verdict::Fail {
+                expected: "45000".to_string(),
+                actual: "44999".to_string()
+            }
+        );
+        assert_eq!(answers.check(3, 1, "1"), Verdict::Missing);
+    }
+
+    #[test]
+    fn multi_line_answers_ignore_surrounding_whitespace() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.check(10, 2, "##..\n#..#\n\n"), Verdict::Pass);
+    }
+
+    #[test]
+    fn duplicate_answers_are_rejected() {
+        let duplicated = format!("{ANSWERS}\n[[answer]]\nday = 1\npart = 1\nexpected = 1\n");
+        assert!(matches!(
+            duplicated.parse::<Answers>(),
+            Err(AnswersError::Duplicate { day: 1, part: 1 })
+        ));
+    }
+
+    #[test]
+    fn missing_file_is_empty() {
+        let answers = Answers::load(Path::new("does/not/exist.toml"), 2021).unwrap();
+        assert_eq!(answers.year, 2021);
+        assert_eq!(answers.check(1, 1, "7"), Verdict::Missing);
+    }
+
+    #[test]
+    fn part_from_solver_name() {
+        assert_eq!(part_of("part_1"), 1);
+        assert_eq!(part_of("part_2"), 2);
+        assert_eq!(part_of("part_1_solution_2"), 1);
+        assert_eq!(part_of("solution"), 1);
+    }
+}
@@ -0,0 +1,266 @@
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+
+use aoc_main::clap::{value_parser, Arg, ArgMatches, Command};
+use aoc_utils::examples::{self, Update};
+use aoc_utils::submit::{self, Answer, Attempts, Client, Outcome};
+use aoc_utils::{run_all, scaffold, watch};
+
+fn year_arg() -> Arg {
+    Arg::new("year")
+        .help("The puzzle year, either in full or as its last two digits")
+        .value_parser(value_parser!(u16))
+}
+
+fn full_year(year: u16) -> u16 {
+    match year {
+        short @ 0..=99 => 2000 + short,
+        year => year,
+    }
+}
+
+fn day_arg() -> Arg {
+    Arg::new("day")
+        .required(true)
+        .value_parser(value_parser!(u8).range(1..=25))
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn main() -> ExitCode {
+    let matches = Command::new("aoc")
+        .about("Tools for working on the Advent of Code solutions")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("new")
+                .about("Create, register and add an input placeholder for a new day")
+                .arg(year_arg().required(true))
+                .arg(day_arg()),
+        )
+        .subcommand(
+            Command::new("examples")
+                .about("Write the examples of a saved puzzle page as the tests of its day")
+                .long_about(
+                    "Write the examples of a saved puzzle page as the tests of its day.\n\n\
+                     The page is read from puzzles/<year>/<day>.html, and its examples \
+                     replace the generated INPUT constants and example entries of the \
+                     day's aoc_test! invocation.",
+                )
+                .arg(year_arg().required(true))
+                .arg(day_arg()),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Submit an answer, unless it is already known to be wrong")
+                .arg(year_arg().required(true))
+                .arg(day_arg())
+                .arg(
+                    Arg::new("part")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=2)),
+                )
+                .arg(Arg::new("answer").help("The answer to submit, read from stdin if omitted"))
+                .arg(
+                    Arg::new("url")
+                        .long("url")
+                        .default_value(submit::BASE_URL)
+                        .help("Base URL of the site to submit to"),
+                ),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Run the tests and the input of a day again whenever its files change")
+                .arg(year_arg().required(true))
+                .arg(day_arg()),
+        )
+        .subcommand(
+            Command::new("run-all")
+                .about("Run every registered day concurrently and summarise the timings")
+                .arg(
+                    year_arg()
+                        .num_args(0..)
+                        .help("The years to run, all of them by default"),
+                )
+                .arg(
+                    Arg::new("jobs")
+                        .short('j')
+                        .long("jobs")
+                        .value_parser(value_parser!(usize))
+                        .help("Number of days run at once [default: available cores]"),
+                )
+                .arg(
+                    Arg::new("top")
+                        .long("top")
+                        .value_parser(value_parser!(usize))
+                        .default_value("10")
+                        .help("Number of slowest days listed"),
+                ),
+        )
+        .get_matches();
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc_utils lives in the workspace");
+
+    match matches.subcommand() {
+        Some(("new", args)) => new(root, args),
+        Some(("examples", args)) => examples(root, args),
+        Some(("submit", args)) => submit(root, args),
+        Some(("watch", args)) => {
+            let year = full_year(*args.get_one::<u16>("year").unwrap());
+            let day = *args.get_one::<u8>("day").unwrap();
+            if let Err(err) = watch::watch(root, year, day) {
+                eprintln!("error: {err}");
+            }
+            ExitCode::FAILURE
+        }
+        Some(("run-all", args)) => run_all(root, args),
+        _ => unreachable!("clap requires a subcommand"),
+    }
+}
+
+fn new(root: &Path, args: &ArgMatches) -> ExitCode {
+    let year = full_year(*args.get_one::<u16>("year").unwrap());
+    let day = *args.get_one::<u8>("day").unwrap();
+    match scaffold::new_day(root, year, day) {
+        Ok(created) => {
+            println!("Created {}", relative(root, &created.module));
+            println!("Registered day{day} in {}", relative(root, &created.main));
+            if let Some(input) = created.input {
+                println!("Created input placeholder {}", relative(root, &input));
+            }
+            // The examples are a bonus, the day is usable without them.
+            if examples::page_path(root, year, day).is_file() {
+                match examples::update_day(root, year, day) {
+                    Ok(update) => print_update(root, &update),
+                    Err(err) => eprintln!("warning: {err}"),
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn examples(root: &Path, args: &ArgMatches) -> ExitCode {
+    let year = full_year(*args.get_one::<u16>("year").unwrap());
+    let day = *args.get_one::<u8>("day").unwrap();
+    match examples::update_day(root, year, day) {
+        Ok(update) => {
+            print_update(root, &update);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_update(root: &Path, update: &Update) {
+    println!(
+        "Wrote the examples of {} to {}",
+        relative(root, &update.page),
+        relative(root, &update.module)
+    );
+    for (i, example) in update.examples.iter().enumerate() {
+        let answers: Vec<String> = example
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part_{part} = {answer}"))
+            .collect();
+        let lines = example.input.lines().count();
+        println!("  example {}: {lines} lines, {}", i + 1, answers.join(", "));
+    }
+}
+
+fn submit(root: &Path, args: &ArgMatches) -> ExitCode {
+    let year = full_year(*args.get_one::<u16>("year").unwrap());
+    let value = match args.get_one::<String>("answer") {
+        Some(value) => value.clone(),
+        None => {
+            let mut value = String::new();
+            if let Err(err) = io::stdin().read_line(&mut value) {
+                eprintln!("error: could not read the answer: {err}");
+                return ExitCode::FAILURE;
+            }
+            value
+        }
+    };
+    let answer = Answer {
+        year,
+        day: *args.get_one::<u8>("day").unwrap(),
+        part: *args.get_one::<u8>("part").unwrap(),
+        value: value.trim().to_string(),
+    };
+    if answer.value.is_empty() {
+        eprintln!("error: the answer is empty");
+        return ExitCode::FAILURE;
+    }
+
+    let Some(session) = submit::session_token() else {
+        eprintln!("error: no session token, set AOC_SESSION or run a day to be asked for it");
+        return ExitCode::FAILURE;
+    };
+    let client = Client::new(args.get_one::<String>("url").unwrap(), &session);
+    let submitted = Attempts::load(&Attempts::path(root, year), year)
+        .and_then(|mut attempts| submit::submit(&client, &mut attempts, &answer));
+    match submitted {
+        Ok(submission) => {
+            let note = if submission.known {
+                " (known from a previous attempt, not submitted)"
+            } else {
+                ""
+            };
+            println!(
+                "Day {} part {}: {}{note}",
+                answer.day, answer.part, submission.outcome
+            );
+            if submission.outcome == Outcome::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_all(root: &Path, args: &ArgMatches) -> ExitCode {
+    let years = match args.get_many::<u16>("year") {
+        Some(years) => years.copied().map(full_year).collect(),
+        None => run_all::years(root).expect("could not list the workspace"),
+    };
+    let jobs = args
+        .get_one::<usize>("jobs")
+        .copied()
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |jobs| jobs.get()));
+
+    match run_all::run_years(root, &years, jobs) {
+        Ok(summary) => {
+            summary.print(*args.get_one::<usize>("top").unwrap());
+            if summary.failures().is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,496 @@
+//! Boolean circuits of named wires connected by gates.
+//!
+//! A [`Circuit`] interns the names of its wires and records the gate driving
+//! each of them. Wires without a gate are inputs. Before being evaluated, the
+//! circuit is sorted into a [`Netlist`], which fails if the gates form a
+//! cycle, and then evaluates the gates in order on a set of [`Values`]:
+//!
+//! ```
+//! use aoc_utils::circuit::{Circuit, Gate, Signal};
+//!
+//! let mut circuit = Circuit::new(16);
+//! let (x, y, z) = (circuit.wire("x"), circuit.wire("y"), circuit.wire("z"));
+//! circuit.connect(z, Gate::And(Signal::Wire(x), Signal::Wire(y)));
+//!
+//! let netlist = circuit.netlist().unwrap();
+//! let mut values = netlist.values();
+//! values.set(x, 123);
+//! values.set(y, 456);
+//! netlist.evaluate(&mut values);
+//! assert_eq!(values.get(z), 72);
+//! ```
+//!
+//! Numbered wires such as `x00`, `x01`, ... form buses, which are read and
+//! written as a single number with [`Values::read_bus`] and
+//! [`Values::write_bus`]. [`Circuit::to_dot`] draws the circuit, with the
+//! values of its wires once evaluated.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::dot::Graph;
+
+/// A wire of a [`Circuit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Wire(u32);
+
+impl Wire {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The input of a gate: a wire or a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    Wire(Wire),
+    Value(u64),
+}
+
+/// A gate, driving the wire it is connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    /// Copies its input.
+    Buffer(Signal),
+    Not(Signal),
+    And(Signal, Signal),
+    Or(Signal, Signal),
+    Xor(Signal, Signal),
+    /// Shifts its input left by a number of bits.
+    Shl(Signal, u32),
+    /// Shifts its input right by a number of bits.
+    Shr(Signal, u32),
+}
+
+impl Gate {
+    /// The signals read by the gate.
+    fn signals(&self) -> impl Iterator<Item = Signal> {
+        let (a, b) = match *self {
+            Gate::Buffer(a) | Gate::Not(a) | Gate::Shl(a, _) | Gate::Shr(a, _) => (a, None),
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::Xor(a, b) => (a, Some(b)),
+        };
+        [Some(a), b].into_iter().flatten()
+    }
+
+    /// The wires read by the gate.
+    pub fn inputs(&self) -> impl Iterator<Item = Wire> {
+        self.signals().filter_map(|s| match s {
+            Signal::Wire(w) => Some(w),
+            Signal::Value(_) => None,
+        })
+    }
+
+    /// The name of the operation, as written in the puzzles.
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Gate::Buffer(_) => "",
+            Gate::Not(_) => "NOT",
+            Gate::And(..) => "AND",
+            Gate::Or(..) => "OR",
+            Gate::Xor(..) => "XOR",
+            Gate::Shl(..) => "LSHIFT",
+            Gate::Shr(..) => "RSHIFT",
+        }
+    }
+}
+
+/// The gates of a circuit could not be ordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The wires, in order, of a loop of gates feeding each other.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::Cycle(wires) => {
+                write!(f, "the gates form a cycle through {}", wires.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// Named wires and the gates driving them, on values `width` bits wide.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    width: u32,
+    names: Vec<String>,
+    ids: HashMap<String, Wire>,
+    drivers: Vec<Option<Gate>>,
+}
+
+impl Circuit {
+    /// An empty circuit carrying values of `width` bits, at most 64.
+    pub fn new(width: u32) -> Self {
+        assert!((1..=64).contains(&width), "Unsupported bit width {width}");
+        Circuit {
+            width,
+            names: Vec::new(),
+            ids: HashMap::new(),
+            drivers: Vec::new(),
+        }
+    }
+
+    /// Returns the wire called `name`, adding it if needed.
+    pub fn wire(&mut self, name: &str) -> Wire {
+        if let Some(&wire) = self.ids.get(name) {
+            return wire;
+        }
+        let wire = Wire(self.names.len() as u32);
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), wire);
+        self.drivers.push(None);
+        wire
+    }
+
+    /// Returns the wire called `name`, if the circuit has one.
+    pub fn find(&self, name: &str) -> Option<Wire> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, wire: Wire) -> &str {
+        &self.names[wire.index()]
+    }
+
+    /// Every wire, in the order they were added.
+    pub fn wires(&self) -> impl Iterator<Item = Wire> {
+        (0..self.names.len() as u32).map(Wire)
+    }
+
+    /// Drives `output` with `gate`, replacing the gate driving it before.
+    pub fn connect(&mut self, output: Wire, gate: Gate) {
+        self.drivers[output.index()] = Some(gate);
+    }
+
+    /// The gate driving `wire`, or `None` for inputs.
+    pub fn driver(&self, wire: Wire) -> Option<&Gate> {
+        self.drivers[wire.index()].as_ref()
+    }
+
+    /// Exchanges the gates driving `a` and `b`.
+    pub fn swap_drivers(&mut self, a: Wire, b: Wire) {
+        self.drivers.swap(a.index(), b.index());
+    }
+
+    /// The wires called `prefix` followed by a number, ordered by that number,
+    /// which is the bit they carry.
+    pub fn bus(&self, prefix: &str) -> Vec<Wire> {
+        let mut bus = self
+            .wires()
+            .filter_map(|wire| {
+                let bit = self.name(wire).strip_prefix(prefix)?.parse::<u32>().ok()?;
+                Some((bit, wire))
+            })
+            .collect::<Vec<_>>();
+        bus.sort_unstable();
+        bus.into_iter().map(|(_, wire)| wire).collect()
+    }
+
+    /// Sorts the gates so that each is evaluated after the gates driving its
+    /// inputs.
+    pub fn netlist(&self) -> Result<Netlist, CircuitError> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Visiting,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.names.len()];
+        let mut order = Vec::new();
+        for root in self.wires() {
+            if marks[root.index()] != Mark::New {
+                continue;
+            }
+            // Depth-first search, keeping the path to report cycles
+            let mut path = vec![(root, self.inputs(root))];
+            marks[root.index()] = Mark::Visiting;
+            while let Some((wire, inputs)) = path.last_mut() {
+                let wire = *wire;
+                match inputs.pop() {
+                    Some(input) if marks[input.index()] == Mark::New => {
+                        marks[input.index()] = Mark::Visiting;
+                        path.push((input, self.inputs(input)));
+                    }
+                    Some(input) if marks[input.index()] == Mark::Visiting => {
+                        let start = path.iter().position(|(w, _)| *w == input).unwrap();
+                        let mut cycle = path[start..]
+                            .iter()
+                            .map(|(w, _)| self.name(*w).to_string())
+                            .collect::<Vec<_>>();
+                        cycle.push(self.name(input).to_string());
+                        return Err(CircuitError::Cycle(cycle));
+                    }
+                    Some(_) => (),
+                    None => {
+                        marks[wire.index()] = Mark::Done;
+                        if let Some(&gate) = self.driver(wire) {
+                            order.push((wire, gate));
+                        }
+                        path.pop();
+                    }
+                }
+            }
+        }
+        Ok(Netlist {
+            mask: u64::MAX >> (64 - self.width),
+            wires: self.names.len(),
+            order,
+        })
+    }
+
+    /// Draws a node per wire, shaped after the gate driving it, with an edge
+    /// from every input of the gate. With `values`, the nodes and edges
+    /// carrying a non-zero value are green and the others grey.
+    pub fn to_dot(&self, name: &str, values: Option<&Values>) -> Graph {
+        let mut graph = Graph::new(name);
+        for wire in self.wires() {
+            let name = self.name(wire);
+            let (shape, mut label) = match self.driver(wire) {
+                None => ("invhouse", name.to_string()),
+                Some(gate) => {
+                    let shape = match gate {
+                        Gate::Buffer(_) => "ellipse",
+                        Gate::Not(_) => "invtriangle",
+                        Gate::And(..) => "box",
+                        Gate::Or(..) => "hexagon",
+                        Gate::Xor(..) => "diamond",
+                        Gate::Shl(..) => "larrow",
+                        Gate::Shr(..) => "rarrow",
+                    };
+                    let operator = match gate {
+                        Gate::Shl(_, n) | Gate::Shr(_, n) => format!("{} {n}", gate.operator()),
+                        _ => gate.operator().to_string(),
+                    };
+                    (shape, format!("{name}\n{operator}").trim_end().to_string())
+                }
+            };
+            if let Some(values) = values {
+                label += &format!("\n= {}", values.get(wire));
+            }
+            let node = graph.node(name).shape(shape).label(&label);
+            if let Some(values) = values {
+                node.fill(if values.get(wire) != 0 {
+                    "palegreen"
+                } else {
+                    "lightgray"
+                });
+            }
+        }
+        for wire in self.wires() {
+            let Some(gate) = self.driver(wire) else {
+                continue;
+            };
+            for signal in gate.signals() {
+                let (from, value) = match signal {
+                    Signal::Wire(input) => {
+                        (self.name(input).to_string(), values.map(|v| v.get(input)))
+                    }
+                    Signal::Value(value) => {
+                        let id = value.to_string();
+                        graph.node(&id).shape("plain");
+                        (id, Some(value))
+                    }
+                };
+                let edge = graph.edge(&from, self.name(wire));
+                if let (Some(_), Some(value)) = (values, value) {
+                    edge.color(if value != 0 { "darkgreen" } else { "gray" });
+                }
+            }
+        }
+        graph
+    }
+
+    fn inputs(&self, wire: Wire) -> Vec<Wire> {
+        self.driver(wire)
+            .map(|gate| gate.inputs().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The gates of a [`Circuit`] in an order they can be evaluated in.
+#[derive(Debug, Clone)]
+pub struct Netlist {
+    mask: u64,
+    wires: usize,
+    order: Vec<(Wire, Gate)>,
+}
+
+impl Netlist {
+    /// Values for every wire of the circuit, all zero.
+    pub fn values(&self) -> Values {
+        Values(vec![0; self.wires])
+    }
+
+    /// Sets every wire driven by a gate from the values of its inputs.
+    pub fn evaluate(&self, values: &mut Values) {
+        for &(wire, gate) in &self.order {
+            let get = |signal| match signal {
+                Signal::Wire(w) => values.get(w),
+                Signal::Value(v) => v,
+            };
+            let value = match gate {
+                Gate::Buffer(a) => get(a),
+                Gate::Not(a) => !get(a),
+                Gate::And(a, b) => get(a) & get(b),
+                Gate::Or(a, b) => get(a) | get(b),
+                Gate::Xor(a, b) => get(a) ^ get(b),
+                Gate::Shl(a, n) => get(a).checked_shl(n).unwrap_or(0),
+                Gate::Shr(a, n) => get(a).checked_shr(n).unwrap_or(0),
+            };
+            values.set(wire, value & self.mask);
+        }
+    }
+}
+
+/// The value carried by every wire of a circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Values(Vec<u64>);
+
+impl Values {
+    pub fn get(&self, wire: Wire) -> u64 {
+        self.0[wire.index()]
+    }
+
+    pub fn set(&mut self, wire: Wire, value: u64) {
+        self.0[wire.index()] = value;
+    }
+
+    /// Reads the lowest bit of each wire of `bus` as the bits of a number.
+    pub fn read_bus(&self, bus: &[Wire]) -> u64 {
+        bus.iter()
+            .enumerate()
+            .fold(0, |acc, (bit, &wire)| acc | (self.get(wire) & 1) << bit)
+    }
+
+    /// Sets each wire of `bus` to one bit of `value`.
+    pub fn write_bus(&mut self, bus: &[Wire], value: u64) {
+        for (bit, &wire) in bus.iter().enumerate() {
+            self.set(wire, value >> bit & 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ripple-carry adder of `bits` bits, on the buses `x`, `y` and `z`.
+    fn adder(bits: usize) -> Circuit {
+        let mut circuit = Circuit::new(1);
+        let mut carry = None;
+        for i in 0..bits {
+            let x = Signal::Wire(circuit.wire(&format!("x{i:02}")));
+            let y = Signal::Wire(circuit.wire(&format!("y{i:02}")));
+            let z = circuit.wire(&format!("z{i:02}"));
+            let half = circuit.wire(&format!("half{i:02}"));
+            circuit.connect(half, Gate::Xor(x, y));
+            let Some(c) = carry else {
+                circuit.connect(z, Gate::Buffer(Signal::Wire(half)));
+                let out = circuit.wire(&format!("carry{i:02}"));
+                circuit.connect(out, Gate::And(x, y));
+                carry = Some(Signal::Wire(out));
+                continue;
+            };
+            circuit.connect(z, Gate::Xor(Signal::Wire(half), c));
+            let both = circuit.wire(&format!("both{i:02}"));
+            circuit.connect(both, Gate::And(x, y));
+            let through = circuit.wire(&format!("through{i:02}"));
+            circuit.connect(through, Gate::And(Signal::Wire(half), c));
+            let out = circuit.wire(&format!("carry{i:02}"));
+            circuit.connect(out, Gate::Or(Signal::Wire(both), Signal::Wire(through)));
+            carry = Some(Signal::Wire(out));
+        }
+        circuit
+    }
+
+    #[test]
+    fn adds_through_buses() {
+        let circuit = adder(8);
+        let netlist = circuit.netlist().unwrap();
+        let (x, y, z) = (circuit.bus("x"), circuit.bus("y"), circuit.bus("z"));
+        assert_eq!(z.len(), 8);
+        let mut values = netlist.values();
+        for (a, b) in [(0, 0), (1, 1), (100, 27), (200, 55), (255, 255)] {
+            values.write_bus(&x, a);
+            values.write_bus(&y, b);
+            netlist.evaluate(&mut values);
+            assert_eq!(values.read_bus(&z), (a + b) & 0xff);
+        }
+    }
+
+    #[test]
+    fn masks_to_the_width() {
+        let mut circuit = Circuit::new(16);
+        let (a, b, c) = (circuit.wire("a"), circuit.wire("b"), circuit.wire("c"));
+        circuit.connect(a, Gate::Not(Signal::Value(0)));
+        circuit.connect(b, Gate::Shl(Signal::Wire(a), 4));
+        circuit.connect(c, Gate::Shr(Signal::Wire(b), 8));
+        let netlist = circuit.netlist().unwrap();
+        let mut values = netlist.values();
+        netlist.evaluate(&mut values);
+        assert_eq!(values.get(a), 0xffff);
+        assert_eq!(values.get(b), 0xfff0);
+        assert_eq!(values.get(c), 0x00ff);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let mut circuit = Circuit::new(1);
+        let (a, b, c) = (circuit.wire("a"), circuit.wire("b"), circuit.wire("c"));
+        circuit.connect(a, Gate::Buffer(Signal::Wire(b)));
+        circuit.connect(b, Gate::Or(Signal::Wire(c), Signal::Wire(a)));
+        let error = circuit.netlist().unwrap_err();
+        assert_eq!(
+            error,
+            CircuitError::Cycle(vec!["a".into(), "b".into(), "a".into()])
+        );
+
+        circuit.swap_drivers(a, c);
+        let error = circuit.netlist().unwrap_err();
+        assert_eq!(
+            error,
+            CircuitError::Cycle(vec!["b".into(), "c".into(), "b".into()])
+        );
+
+        circuit.connect(c, Gate::Buffer(Signal::Value(1)));
+        assert!(circuit.netlist().is_ok());
+    }
+
+    #[test]
+    fn draws_wires_and_gates() {
+        let mut circuit = Circuit::new(8);
+        let (a, b, c) = (circuit.wire("a"), circuit.wire("b"), circuit.wire("c"));
+        circuit.connect(b, Gate::Shl(Signal::Wire(a), 2));
+        circuit.connect(c, Gate::And(Signal::Wire(b), Signal::Value(3)));
+        let dot = circuit.to_dot("shifts", None).to_string();
+        assert!(dot.contains("\"a\" [shape=\"invhouse\", label=\"a\"];"));
+        assert!(dot.contains("\"b\" [shape=\"larrow\", label=\"b\\nLSHIFT 2\"];"));
+        assert!(dot.contains("\"3\" [shape=\"plain\"];"));
+        assert!(dot.contains("\"3\" -> \"c\";"));
+
+        let netlist = circuit.netlist().unwrap();
+        let mut values = netlist.values();
+        values.set(a, 1);
+        netlist.evaluate(&mut values);
+        let dot = circuit.to_dot("shifts", Some(&values)).to_string();
+        assert!(dot.contains("label=\"c\\nAND\\n= 0\", style=\"filled\", fillcolor=\"lightgray\""));
+        assert!(dot.contains("\"a\" -> \"b\" [color=\"darkgreen\"];"));
+        assert!(dot.contains("\"b\" -> \"c\" [color=\"darkgreen\"];"));
+        assert!(dot.contains("\"3\" -> \"c\" [color=\"darkgreen\"];"));
+    }
+
+    #[test]
+    fn interns_wires() {
+        let mut circuit = Circuit::new(8);
+        let a = circuit.wire("a");
+        assert_eq!(circuit.wire("a"), a);
+        assert_eq!(circuit.find("a"), Some(a));
+        assert_eq!(circuit.find("b"), None);
+        assert_eq!(circuit.name(a), "a");
+        assert_eq!(circuit.driver(a), None);
+    }
+}
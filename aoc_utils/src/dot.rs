@@ -0,0 +1,336 @@
+//! Graphviz exports of the graph-shaped puzzle inputs.
+//!
+//! A day describes its circuit or network as a [`Graph`], picking a shape for
+//! each kind of node and colouring nodes and edges with the values they ended
+//! up with, and hands it to [`export`]. Nothing is written unless the runner
+//! was started with `--dot <FILE>`, in which case the graphs exported by every
+//! solver are written to that file, one `digraph` after another:
+//!
+//! ```
+//! use aoc_utils::dot::Graph;
+//!
+//! let mut graph = Graph::new("example");
+//! graph.node("x").shape("invhouse");
+//! graph.node("z").shape("box").label("z\nAND").fill("palegreen");
+//! graph.edge("x", "z").color("darkgreen");
+//! assert!(graph.to_string().contains("\"x\" -> \"z\" [color=\"darkgreen\"];"));
+//! ```
+//!
+//! The file can hold several graphs, so render it with `dot -Tsvg -O`, which
+//! writes one image per graph.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::Mutex;
+
+/// Attributes of a node or an edge, in the order they were set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Attributes(Vec<(&'static str, String)>);
+
+impl Attributes {
+    fn set(&mut self, key: &'static str, value: &str) {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.0.push((key, value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, " [")?;
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}={}", Quoted(value))?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// A string written as a DOT identifier between double quotes.
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+/// A node of a [`Graph`], styled through chained setters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    id: String,
+    attributes: Attributes,
+}
+
+impl Node {
+    /// Sets any Graphviz attribute of the node.
+    pub fn set(&mut self, key: &'static str, value: &str) -> &mut Self {
+        self.attributes.set(key, value);
+        self
+    }
+
+    /// The text shown in the node, its id by default.
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.set("label", label)
+    }
+
+    /// A Graphviz node shape, such as `box`, `circle` or `diamond`.
+    pub fn shape(&mut self, shape: &str) -> &mut Self {
+        self.set("shape", shape)
+    }
+
+    /// Fills the node with a Graphviz colour.
+    pub fn fill(&mut self, color: &str) -> &mut Self {
+        self.set("style", "filled").set("fillcolor", color)
+    }
+}
+
+/// An edge of a [`Graph`], styled through chained setters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    from: String,
+    to: String,
+    attributes: Attributes,
+}
+
+impl Edge {
+    /// Sets any Graphviz attribute of the edge.
+    pub fn set(&mut self, key: &'static str, value: &str) -> &mut Self {
+        self.attributes.set(key, value);
+        self
+    }
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.set("label", label)
+    }
+
+    pub fn color(&mut self, color: &str) -> &mut Self {
+        self.set("color", color)
+    }
+}
+
+/// A directed graph, written in the DOT language by its `Display` impl.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    name: String,
+    nodes: Vec<Node>,
+    ids: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn new(name: &str) -> Self {
+        Graph {
+            name: name.to_string(),
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the node called `id`, adding it if needed.
+    pub fn node(&mut self, id: &str) -> &mut Node {
+        let index = *self.ids.entry(id.to_string()).or_insert_with(|| {
+            self.nodes.push(Node {
+                id: id.to_string(),
+                attributes: Attributes::default(),
+            });
+            self.nodes.len() - 1
+        });
+        &mut self.nodes[index]
+    }
+
+    /// Adds an edge from `from` to `to`. Nodes it mentions but that were not
+    /// added are drawn with the default style.
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Edge {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            attributes: Attributes::default(),
+        });
+        self.edges.last_mut().unwrap()
+    }
+
+    pub fn nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edges(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {} {{", Quoted(&self.name))?;
+        for node in &self.nodes {
+            writeln!(f, "  {}{};", Quoted(&node.id), node.attributes)?;
+        }
+        for edge in &self.edges {
+            writeln!(
+                f,
+                "  {} -> {}{};",
+                Quoted(&edge.from),
+                Quoted(&edge.to),
+                edge.attributes
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// The graphs written by a stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exported {
+    pub path: PathBuf,
+    pub graphs: usize,
+}
+
+impl fmt::Display for Exported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.graphs == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} graph{plural} written to {}",
+            self.graphs,
+            self.path.display()
+        )
+    }
+}
+
+/// The file graphs are written to, and those of the current stage.
+struct Output {
+    path: PathBuf,
+    file: Option<File>,
+    stage: String,
+    pending: Vec<Graph>,
+}
+
+/// Whether graphs are written, checked before taking the lock.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static OUTPUT: Mutex<Option<Output>> = Mutex::new(None);
+
+/// Writes the graphs exported from now on to `path`, replacing the file.
+pub fn start(path: PathBuf) {
+    *OUTPUT.lock().unwrap() = Some(Output {
+        path,
+        file: None,
+        stage: String::new(),
+        pending: Vec::new(),
+    });
+    ACTIVE.store(true, Relaxed);
+}
+
+/// Returns whether graphs are written, for days whose graphs are costly to
+/// build.
+pub fn is_active() -> bool {
+    ACTIVE.load(Relaxed)
+}
+
+/// Starts a stage of a day, such as `day24_part_1`, prefixing the names of
+/// the graphs it exports.
+pub fn begin(stage: &str) {
+    if let Some(output) = OUTPUT.lock().unwrap().as_mut() {
+        output.stage = stage.to_string();
+        output.pending.clear();
+    }
+}
+
+/// Exports a graph of the running stage.
+pub fn export(mut graph: Graph) {
+    if !is_active() {
+        return;
+    }
+    if let Some(output) = OUTPUT.lock().unwrap().as_mut() {
+        if !output.stage.is_empty() {
+            graph.name = format!("{}_{}", output.stage, graph.name);
+        }
+        output.pending.push(graph);
+    }
+}
+
+/// Ends the current stage, appending its graphs to the file. Returns the
+/// graphs written, if any.
+pub fn finish() -> io::Result<Option<Exported>> {
+    let mut output = OUTPUT.lock().unwrap();
+    let Some(output) = output.as_mut() else {
+        return Ok(None);
+    };
+    if output.pending.is_empty() {
+        return Ok(None);
+    }
+    let file = match &mut output.file {
+        Some(file) => file,
+        file => file.insert(
+            OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&output.path)?,
+        ),
+    };
+    for graph in &output.pending {
+        write!(file, "{graph}")?;
+    }
+    file.flush()?;
+    Ok(Some(Exported {
+        path: output.path.clone(),
+        graphs: std::mem::take(&mut output.pending).len(),
+    }))
+}
+
+/// Stops writing graphs.
+pub fn stop() {
+    ACTIVE.store(false, Relaxed);
+    OUTPUT.lock().unwrap().take();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_dot() {
+        let mut graph = Graph::new("day 1");
+        graph.node("a").shape("box").fill("red");
+        graph.node("b").label("say \"b\"\nnow");
+        graph.node("a").fill("blue");
+        graph.edge("a", "b").color("gray").label("1");
+        graph.edge("b", "c");
+        assert_eq!((graph.nodes(), graph.edges()), (2, 2));
+        assert_eq!(
+            graph.to_string(),
+            concat!(
+                "digraph \"day 1\" {\n",
+                "  \"a\" [shape=\"box\", style=\"filled\", fillcolor=\"blue\"];\n",
+                "  \"b\" [label=\"say \\\"b\\\"\\nnow\"];\n",
+                "  \"a\" -> \"b\" [color=\"gray\", label=\"1\"];\n",
+                "  \"b\" -> \"c\";\n",
+                "}\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,541 @@
+//! Example tests taken from saved puzzle pages, behind
+//! `aoc examples <year> <day>`.
+//!
+//! A puzzle page saved as `puzzles/<year>/<day>.html` holds an article per
+//! unlocked part. The first `<pre><code>` block of an article is taken as its
+//! example input and the last emphasised answer, in `<code><em>`, as the
+//! answer to it. Part 2 reuses the example of part 1 unless it shows a new one.
+//!
+//! [`update_tests`] writes them as the `aoc_test!` examples of the day's test
+//! module, replacing the `INPUT` constants and the `example` entries generated
+//! before while keeping the other tests.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::scaffold;
+
+/// An example input and the answers the puzzle gives for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    /// The answers as written on the page, by part number.
+    pub answers: Vec<(u8, String)>,
+}
+
+impl Example {
+    /// The name of the example in `aoc_test!` and of its input constant,
+    /// numbered from the second one on.
+    fn names(index: usize) -> (String, String) {
+        match index {
+            0 => ("example".to_string(), "INPUT".to_string()),
+            i => (format!("example_{}", i + 1), format!("INPUT_{}", i + 1)),
+        }
+    }
+}
+
+/// An error raised while generating the example tests of a day.
+#[derive(Debug)]
+pub enum ExamplesError {
+    Io(PathBuf, io::Error),
+    /// The page has no example followed by an answer.
+    NoExamples(PathBuf),
+    /// The test module could not be understood.
+    InvalidTests(String),
+}
+
+impl fmt::Display for ExamplesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExamplesError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            ExamplesError::NoExamples(path) => {
+                write!(f, "found no example with an answer in {}", path.display())
+            }
+            ExamplesError::InvalidTests(reason) => write!(f, "invalid test module: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ExamplesError {}
+
+/// Returns where the page of a puzzle is saved below the workspace `root`.
+pub fn page_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join("puzzles")
+        .join(year.to_string())
+        .join(format!("{day}.html"))
+}
+
+/// Extracts the examples of every part on a puzzle page.
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (part, article) in (1..).zip(elements(html, "<article", "</article>")) {
+        let blocks = elements(article, "<pre><code>", "</code></pre>");
+        let Some(answer) = answer(article) else {
+            continue;
+        };
+        let input = blocks.first().map(|block| decode(&strip_tags(block)));
+        let example = match input {
+            Some(input) => match examples.iter().position(|e| e.input == input) {
+                Some(i) => i,
+                None => {
+                    examples.push(Example {
+                        input,
+                        answers: Vec::new(),
+                    });
+                    examples.len() - 1
+                }
+            },
+            None if !examples.is_empty() => 0,
+            None => continue,
+        };
+        examples[example].answers.push((part, answer));
+    }
+    examples
+}
+
+/// Returns the contents of the elements opened by `open` and closed by
+/// `close`, which must not nest.
+fn elements<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            break;
+        };
+        found.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+    found
+}
+
+/// Returns the last emphasised code of an article, outside of its blocks.
+fn answer(article: &str) -> Option<String> {
+    let mut prose = String::new();
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre>") {
+        prose.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("</pre>")
+            .map_or("", |end| &rest[start + end..]);
+    }
+    prose.push_str(rest);
+    elements(&prose, "<code><em>", "</em></code>")
+        .last()
+        .map(|answer| decode(&strip_tags(answer)))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Decodes the character references of HTML text.
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest.find(';').map(|end| (&rest[1..end], end));
+        let c = reference.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = match name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => name.strip_prefix('#')?.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (c, reference) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Writes `input` as a raw string literal, ending with a newline like the
+/// inputs of the puzzles.
+fn raw_literal(input: &str) -> String {
+    let hashes = (1..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !input.contains(&format!("\"{hashes}")))
+        .unwrap();
+    let newline = if input.ends_with('\n') { "" } else { "\n" };
+    format!("r{hashes}\"{input}{newline}\"{hashes}")
+}
+
+/// Writes an answer as an integer when it is one, and as a string otherwise.
+fn answer_literal(answer: &str) -> String {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// Returns the end of the string literal starting at `start`, if any.
+fn literal_end(src: &str, start: usize) -> Option<usize> {
+    let rest = &src[start..];
+    if let Some(raw) = rest.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let body = 1 + hashes + 1;
+        return rest[body..]
+            .find(&closing)
+            .map(|end| start + body + end + closing.len());
+    }
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Some(start + i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the index of the bracket closing the one at `open`, skipping over
+/// string literals and comments.
+fn closing(src: &str, open: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'(' | b'[' => depth += 1,
+            b'}' | b')' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            b'"' => i = literal_end(src, i)? - 1,
+            b'r' if matches!(bytes.get(i + 1), Some(b'"' | b'#'))
+                && !bytes[i - 1].is_ascii_alphanumeric() =>
+            {
+                i = literal_end(src, i)? - 1
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += src[i..].find('\n')?;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Splits the entries of a macro invocation at its top-level commas.
+fn entries(body: &str) -> Result<Vec<&str>, ExamplesError> {
+    let mut entries = Vec::new();
+    let mut rest = body.trim();
+    while !rest.is_empty() {
+        let open = rest.find('(').ok_or_else(|| {
+            ExamplesError::InvalidTests(format!("expected an example in {rest:?}"))
+        })?;
+        let close = closing(rest, open).ok_or_else(|| {
+            ExamplesError::InvalidTests("unclosed example in aoc_test!".to_string())
+        })?;
+        entries.push(&rest[..=close]);
+        rest = rest[close + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Ok(entries)
+}
+
+/// Whether an `aoc_test!` entry is one of the examples generated from a page.
+fn is_generated(entry: &str) -> bool {
+    let name = entry.split([' ', '{', ':']).next().unwrap_or_default();
+    name == "example"
+        || name
+            .strip_prefix("example_")
+            .is_some_and(|n| n.parse::<usize>().is_ok())
+}
+
+/// Rewrites the test module of a day's `source` with `examples`.
+///
+/// The `INPUT` constants of the examples are replaced where they are defined,
+/// and the generated entries of `aoc_test!` replaced by the new ones. The
+/// test module and the invocation are added if the day has none.
+pub fn update_tests(source: &str, examples: &[Example]) -> Result<String, ExamplesError> {
+    let invalid = |reason: &str| ExamplesError::InvalidTests(reason.to_string());
+    let prefix = if source.contains("pub fn generator(") {
+        "        generator =>\n"
+    } else {
+        ""
+    };
+    let mut new_entries = Vec::new();
+    let mut constants = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        let (name, constant) = Example::names(i);
+        let answers: Vec<String> = example
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part_{part} = {}", answer_literal(answer)))
+            .collect();
+        new_entries.push(format!("{name}: ({constant}, {})", answers.join(", ")));
+        constants.push((constant, raw_literal(&example.input)));
+    }
+
+    let Some(module) = source.find("mod tests {") else {
+        let mut source = source.to_string();
+        let constants: String = constants
+            .iter()
+            .map(|(name, literal)| format!("    const {name}: &str = {literal};\n\n"))
+            .collect();
+        let entries: String = new_entries
+            .iter()
+            .map(|entry| format!("        {entry},\n"))
+            .collect();
+        source.push_str(&format!(
+            "\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{constants}    \
+             aoc_utils::aoc_test! {{\n{prefix}{entries}    }}\n}}\n"
+        ));
+        return Ok(source);
+    };
+
+    let mut tests = source[module..].to_string();
+    let mut missing = Vec::new();
+    for (name, literal) in constants {
+        let definition = format!("const {name}: &str = ");
+        match tests.find(&definition) {
+            Some(start) => {
+                let start = start + definition.len();
+                let end = literal_end(&tests, start)
+                    .ok_or_else(|| invalid(&format!("unterminated {name}")))?;
+                tests.replace_range(start..end, &literal);
+            }
+            None => missing.push(format!("const {name}: &str = {literal};\n\n    ")),
+        }
+    }
+
+    let invocation = "aoc_utils::aoc_test! {";
+    let (start, end, kept) = match tests.find(invocation) {
+        Some(start) => {
+            let open = start + invocation.len() - 1;
+            let end = closing(&tests, open).ok_or_else(|| invalid("unclosed aoc_test!"))?;
+            let body = tests[open + 1..end].trim();
+            let body = body.strip_prefix("generator =>").unwrap_or(body);
+            let kept: Vec<&str> = entries(body)?
+                .into_iter()
+                .filter(|entry| !is_generated(entry))
+                .collect();
+            (start, end + 1, kept.join(",\n        "))
+        }
+        None => {
+            let end = tests
+                .trim_end()
+                .strip_suffix('}')
+                .ok_or_else(|| invalid("the test module is not at the end of the file"))?
+                .trim_end()
+                .len();
+            tests.insert_str(end, "\n\n    ");
+            (end + 6, end + 6, String::new())
+        }
+    };
+    if !kept.is_empty() {
+        new_entries.push(kept);
+    }
+    let entries: String = new_entries
+        .iter()
+        .map(|entry| format!("        {entry},\n"))
+        .collect();
+    tests.replace_range(
+        start..end,
+        &format!("{}{invocation}\n{prefix}{entries}    }}", missing.concat()),
+    );
+
+    Ok(format!("{}{tests}", &source[..module]))
+}
+
+/// The examples written by [`update_day`].
+#[derive(Debug)]
+pub struct Update {
+    pub page: PathBuf,
+    pub module: PathBuf,
+    pub examples: Vec<Example>,
+}
+
+/// Extracts the examples of a saved puzzle page and writes them as the tests
+/// of the day, as created by [`scaffold::new_day`].
+pub fn update_day(root: &Path, year: u16, day: u8) -> Result<Update, ExamplesError> {
+    let page = page_path(root, year, day);
+    let module = scaffold::module_path(root, year, day);
+    let html = fs::read_to_string(&page).map_err(|err| ExamplesError::Io(page.clone(), err))?;
+    let examples = extract(&html);
+    if examples.is_empty() {
+        return Err(ExamplesError::NoExamples(page));
+    }
+
+    let source =
+        fs::read_to_string(&module).map_err(|err| ExamplesError::Io(module.clone(), err))?;
+    let updated = update_tests(&source, &examples)?;
+    fs::write(&module, updated).map_err(|err| ExamplesError::Io(module.clone(), err))?;
+    Ok(Update {
+        page,
+        module,
+        examples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sums ---</h2>
+<p>For example:</p>
+<pre><code>1 &lt; 2
+<em>3</em> &amp; 4
+</code></pre>
+<p>Adding them gives <code>1</code>, then <code><em>10</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>1 &lt; 2
+</code></pre>
+<p>In this example the answer is <code><em>CMZ</em></code>.</p>
+</article>
+</main>"#;
+
+    const DAY: &str = r##"pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+"#;
+
+    #[test]
+    fn parses() {
+        assert!(generator(INPUT).is_ok());
+    }
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 7, part_2 = 5),
+        small { size = 3 }: ("1 (2)\n", part_1 = 1),
+    }
+}
+"##;
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Example {
+                    input: "1 < 2\n3 & 4\n".to_string(),
+                    answers: vec![(1, "10".to_string())],
+                },
+                Example {
+                    input: "1 < 2\n".to_string(),
+                    answers: vec![(2, "CMZ".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shares_the_example_of_part_1() {
+        let page = PAGE.replace("<pre><code>1 &lt; 2\n</code></pre>\n", "");
+        let examples = extract(&page);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples[0].answers,
+            vec![(1, "10".to_string()), (2, "CMZ".to_string())]
+        );
+    }
+
+    #[test]
+    fn decodes_references() {
+        assert_eq!(decode("&lt;&#62;&#x26;&nbsp;a & b"), "<>&&nbsp;a & b");
+    }
+
+    #[test]
+    fn picks_enough_hashes() {
+        assert_eq!(raw_literal("a\n"), "r#\"a\n\"#");
+        assert_eq!(raw_literal("\"#"), "r##\"\"#\n\"##");
+    }
+
+    #[test]
+    fn updates_the_test_module() {
+        let updated = update_tests(DAY, &extract(PAGE)).unwrap();
+        assert_eq!(
+            &updated[updated.find("mod tests").unwrap()..],
+            r##"mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"1 < 2
+3 & 4
+"#;
+
+    #[test]
+    fn parses() {
+        assert!(generator(INPUT).is_ok());
+    }
+
+    const INPUT_2: &str = r#"1 < 2
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 10),
+        example_2: (INPUT_2, part_2 = "CMZ"),
+        small { size = 3 }: ("1 (2)\n", part_1 = 1),
+    }
+}
+"##
+        );
+        assert_eq!(update_tests(&updated, &extract(PAGE)).unwrap(), updated);
+    }
+
+    #[test]
+    fn adds_missing_tests() {
+        let examples = [Example {
+            input: "abc\n".to_string(),
+            answers: vec![(1, "-3".to_string())],
+        }];
+        let expected = "pub fn part_1(input: &str) -> i32 {\n    0\n}\n\n\
+            #[cfg(test)]\nmod tests {\n    use super::*;\n\n    \
+            const INPUT: &str = r#\"abc\n\"#;\n\n    \
+            aoc_utils::aoc_test! {\n        example: (INPUT, part_1 = -3),\n    }\n}\n";
+        let day = "pub fn part_1(input: &str) -> i32 {\n    0\n}\n";
+        assert_eq!(update_tests(day, &examples).unwrap(), expected);
+
+        let empty = format!("{day}\n#[cfg(test)]\nmod tests {{\n    use super::*;\n}}\n");
+        assert_eq!(update_tests(&empty, &examples).unwrap(), expected);
+    }
+}
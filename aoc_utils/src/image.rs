@@ -0,0 +1,741 @@
+//! Images of the frames shown by the simulations, encoded as PPM, PNG or
+//! animated GIF without any dependency.
+//!
+//! A [`Canvas`] is turned into a [`Raster`] by painting every cell as a square
+//! of pixels in the colour given by a [`Palette`]; the symbols of the cells are
+//! not drawn. Rasters keep the index of the colour of each pixel, so PNG and
+//! GIF store them with the palette rather than as RGB.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::viz::{Canvas, Color, Frame};
+
+/// The RGB value of every [`Color`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: [[u8; 3]; Palette::LEN],
+}
+
+impl Palette {
+    const LEN: usize = 10;
+
+    /// Returns the palette with `color` drawn as `rgb`.
+    pub fn with(mut self, color: Color, rgb: [u8; 3]) -> Self {
+        self.colors[color as usize] = rgb;
+        self
+    }
+
+    pub fn rgb(&self, color: Color) -> [u8; 3] {
+        self.colors[color as usize]
+    }
+}
+
+/// Draws on a black background, with the empty cells in dark gray.
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            colors: [[0; 3]; Palette::LEN],
+        }
+        .with(Color::Default, [0x10, 0x10, 0x18])
+        .with(Color::Black, [0x00, 0x00, 0x00])
+        .with(Color::Red, [0xe0, 0x4f, 0x4f])
+        .with(Color::Green, [0x4f, 0xc0, 0x5a])
+        .with(Color::Yellow, [0xf0, 0xd0, 0x40])
+        .with(Color::Blue, [0x4f, 0x7f, 0xe0])
+        .with(Color::Magenta, [0xc0, 0x5f, 0xd0])
+        .with(Color::Cyan, [0x4f, 0xc8, 0xd8])
+        .with(Color::White, [0xf0, 0xf0, 0xf0])
+        .with(Color::Gray, [0x30, 0x30, 0x3a])
+    }
+}
+
+/// The pixels of a canvas, as indices into a [`Palette`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Paints every cell of `canvas` as a `scale` by `scale` square.
+    pub fn new(canvas: &Canvas, scale: usize) -> Self {
+        let (columns, rows) = canvas.size();
+        let (width, height) = (columns * scale, rows * scale);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| canvas.cell(x / scale, y / scale).color as u8)
+            .collect();
+        Raster {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+}
+
+/// Encodes the raster as a binary PPM (`P6`) image.
+pub fn ppm(raster: &Raster, palette: &Palette) -> Vec<u8> {
+    let mut out = format!("P6\n{} {}\n255\n", raster.width, raster.height).into_bytes();
+    for &pixel in &raster.pixels {
+        out.extend(palette.colors[pixel as usize]);
+    }
+    out
+}
+
+/// Encodes the raster as an indexed PNG image.
+pub fn png(raster: &Raster, palette: &Palette) -> Vec<u8> {
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::new();
+    header.extend((raster.width as u32).to_be_bytes());
+    header.extend((raster.height as u32).to_be_bytes());
+    // 8 bits per pixel, indexed colour, default compression, filter and interlacing.
+    header.extend([8, 3, 0, 0, 0]);
+    png_chunk(&mut out, b"IHDR", &header);
+    png_chunk(&mut out, b"PLTE", palette.colors.as_flattened());
+
+    // Every row is prefixed with its filter, none here.
+    let mut scanlines = Vec::with_capacity(raster.pixels.len() + raster.height);
+    for row in raster.rows() {
+        scanlines.push(0);
+        scanlines.extend(row);
+    }
+    png_chunk(&mut out, b"IDAT", &zlib(&scanlines));
+    png_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Wraps the deflated `data` in a zlib stream.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    out.extend(deflate(data));
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+/// Writes bits starting from the least significant one, as deflate and GIF
+/// expect.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        self.buffer |= value << self.len;
+        self.len += bits;
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    /// Writes a Huffman code, whose most significant bit comes first.
+    fn write_code(&mut self, code: u32, bits: u32) {
+        self.write(code.reverse_bits() >> (32 - bits), bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const WINDOW: usize = 1 << 15;
+const MAX_MATCH: usize = 258;
+
+/// Compresses `data` as a single deflate block with the fixed Huffman codes,
+/// matching each position against the last one starting with the same three
+/// bytes. Rasters are mostly long runs, which this finds well enough.
+fn deflate(data: &[u8]) -> Vec<u8> {
+    fn literal(bits: &mut BitWriter, symbol: u32) {
+        match symbol {
+            0..=143 => bits.write_code(0x30 + symbol, 8),
+            144..=255 => bits.write_code(0x190 + symbol - 144, 9),
+            256..=279 => bits.write_code(symbol - 256, 7),
+            _ => bits.write_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn hash(bytes: &[u8]) -> usize {
+        ((bytes[0] as usize) << 10 ^ (bytes[1] as usize) << 5 ^ bytes[2] as usize) & (WINDOW - 1)
+    }
+
+    let mut bits = BitWriter::default();
+    // Final block, compressed with the fixed codes.
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let mut last = vec![usize::MAX; WINDOW];
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if i + 3 <= data.len() {
+            let h = hash(&data[i..]);
+            let candidate = last[h];
+            last[h] = i;
+            if candidate != usize::MAX && i - candidate <= WINDOW {
+                let max = MAX_MATCH.min(data.len() - i);
+                length = (0..max)
+                    .take_while(|&k| data[candidate + k] == data[i + k])
+                    .count();
+                distance = i - candidate;
+            }
+        }
+
+        if length < 3 {
+            literal(&mut bits, data[i] as u32);
+            i += 1;
+            continue;
+        }
+
+        let code = LENGTH_BASES.partition_point(|&base| base as usize <= length) - 1;
+        literal(&mut bits, 257 + code as u32);
+        bits.write(
+            (length - LENGTH_BASES[code] as usize) as u32,
+            LENGTH_EXTRA[code] as u32,
+        );
+        let code = DISTANCE_BASES.partition_point(|&base| base as usize <= distance) - 1;
+        bits.write_code(code as u32, 5);
+        bits.write(
+            (distance - DISTANCE_BASES[code] as usize) as u32,
+            DISTANCE_EXTRA[code] as u32,
+        );
+
+        for j in i + 1..(i + length).min(data.len().saturating_sub(2)) {
+            last[hash(&data[j..])] = j;
+        }
+        i += length;
+    }
+    literal(&mut bits, 256);
+    bits.finish()
+}
+
+/// Bits of the colour indices of a GIF, enough for the 16 entries of its
+/// colour table.
+const GIF_DEPTH: u32 = 4;
+const GIF_MAX_CODE: u16 = 4095;
+
+/// An animated GIF, assembled once every frame is known since the size of the
+/// animation is the size of its largest frame.
+pub struct Gif {
+    palette: Palette,
+    /// Time each frame is shown, in hundredths of a second.
+    delay: u16,
+    frames: Vec<GifFrame>,
+}
+
+struct GifFrame {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl Gif {
+    pub fn new(palette: Palette, delay: u16) -> Self {
+        Gif {
+            palette,
+            delay,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Compresses the raster as the next frame.
+    pub fn push(&mut self, raster: &Raster) {
+        self.frames.push(GifFrame {
+            width: raster.width,
+            height: raster.height,
+            data: lzw(&raster.pixels),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes the frames, looping forever.
+    pub fn encode(&self) -> Vec<u8> {
+        let width = self.frames.iter().map(|f| f.width).max().unwrap_or(0);
+        let height = self.frames.iter().map(|f| f.height).max().unwrap_or(0);
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend((width as u16).to_le_bytes());
+        out.extend((height as u16).to_le_bytes());
+        // A global colour table of 2^GIF_DEPTH entries, with 8 bits per channel.
+        out.extend([0xf0 | (GIF_DEPTH as u8 - 1), 0, 0]);
+        for i in 0..1 << GIF_DEPTH {
+            out.extend(self.palette.colors.get(i).unwrap_or(&[0; 3]));
+        }
+        out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            // Frames smaller than the next one are cleared before it is drawn.
+            let resized = self
+                .frames
+                .get(i + 1)
+                .is_some_and(|next| (next.width, next.height) != (frame.width, frame.height));
+            let disposal: u8 = if resized { 2 } else { 1 };
+            out.extend([0x21, 0xf9, 4, disposal << 2]);
+            out.extend(self.delay.to_le_bytes());
+            out.extend([0, 0]);
+
+            out.extend([0x2c, 0, 0, 0, 0]);
+            out.extend((frame.width as u16).to_le_bytes());
+            out.extend((frame.height as u16).to_le_bytes());
+            out.extend([0, GIF_DEPTH as u8]);
+            for block in frame.data.chunks(255) {
+                out.push(block.len() as u8);
+                out.extend(block);
+            }
+            out.push(0);
+        }
+        out.push(0x3b);
+        out
+    }
+}
+
+/// Compresses colour indices with the variable length LZW codes of GIF.
+fn lzw(pixels: &[u8]) -> Vec<u8> {
+    let clear: u16 = 1 << GIF_DEPTH;
+    let end = clear + 1;
+
+    let mut bits = BitWriter::default();
+    // The code extending each code by each colour, 0 when there is none yet.
+    let mut table = vec![[0u16; 1 << GIF_DEPTH]; GIF_MAX_CODE as usize + 1];
+    let mut next = end + 1;
+    let mut size = GIF_DEPTH + 1;
+
+    /// Writes `code`, widening the codes once `next` no longer fits.
+    fn emit(bits: &mut BitWriter, code: u16, next: u16, size: &mut u32) {
+        bits.write(code as u32, *size);
+        if next >= 1 << *size && *size < 12 {
+            *size += 1;
+        }
+    }
+
+    emit(&mut bits, clear, next, &mut size);
+    let Some((&first, rest)) = pixels.split_first() else {
+        emit(&mut bits, end, next, &mut size);
+        return bits.finish();
+    };
+    let mut prefix = first as u16;
+    for &pixel in rest {
+        let extended = table[prefix as usize][pixel as usize];
+        if extended != 0 {
+            prefix = extended;
+            continue;
+        }
+
+        emit(&mut bits, prefix, next, &mut size);
+        if next >= GIF_MAX_CODE {
+            emit(&mut bits, clear, next, &mut size);
+            table
+                .iter_mut()
+                .for_each(|entry| *entry = [0; 1 << GIF_DEPTH]);
+            next = end + 1;
+            size = GIF_DEPTH + 1;
+        } else {
+            table[prefix as usize][pixel as usize] = next;
+            next += 1;
+        }
+        prefix = pixel as u16;
+    }
+    emit(&mut bits, prefix, next, &mut size);
+    emit(&mut bits, end, next, &mut size);
+    bits.finish()
+}
+
+/// How the frames of a stage are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A single animation per stage.
+    Gif,
+    /// A numbered image per frame.
+    Png,
+    Ppm,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Format::Gif),
+            "png" => Ok(Format::Png),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(format!(
+                "unknown image format {s:?}, expected gif, png or ppm"
+            )),
+        }
+    }
+}
+
+/// Time each frame of an animation is shown, in hundredths of a second.
+const GIF_DELAY: u16 = 5;
+
+/// The frames written for a stage.
+#[derive(Debug)]
+pub struct Rendered {
+    pub frames: usize,
+    /// The animation, or the directory holding the images.
+    pub path: PathBuf,
+}
+
+impl fmt::Display for Rendered {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} frames written to {}",
+            self.frames,
+            self.path.display()
+        )
+    }
+}
+
+/// Writes the frames shown during each stage to files named after it.
+pub struct Renderer {
+    dir: PathBuf,
+    format: Format,
+    scale: usize,
+    palette: Palette,
+    stage: String,
+    frames: usize,
+    gif: Gif,
+    /// The first error met while writing the frames of the stage.
+    error: Option<io::Error>,
+}
+
+impl Renderer {
+    pub fn new(dir: PathBuf, format: Format, scale: usize, palette: Palette) -> Self {
+        Renderer {
+            dir,
+            format,
+            scale: scale.max(1),
+            gif: Gif::new(palette.clone(), GIF_DELAY),
+            palette,
+            stage: "frames".to_string(),
+            frames: 0,
+            error: None,
+        }
+    }
+
+    /// Names the files of the frames shown from now on.
+    pub fn begin(&mut self, stage: &str) {
+        self.stage = stage.to_string();
+    }
+
+    pub fn show(&mut self, canvas: &Canvas) {
+        if self.error.is_some() {
+            return;
+        }
+        let raster = Raster::new(canvas, self.scale);
+        let image = match self.format {
+            Format::Gif => {
+                self.gif.push(&raster);
+                self.frames += 1;
+                return;
+            }
+            Format::Png => png(&raster, &self.palette),
+            Format::Ppm => ppm(&raster, &self.palette),
+        };
+        let extension = if self.format == Format::Png {
+            "png"
+        } else {
+            "ppm"
+        };
+        let dir = self.dir.join(&self.stage);
+        let written = std::fs::create_dir_all(&dir).and_then(|()| {
+            std::fs::write(dir.join(format!("{:05}.{extension}", self.frames)), image)
+        });
+        match written {
+            Ok(()) => self.frames += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    /// Writes the animation of the stage, returning what was written if it
+    /// showed any frame.
+    pub fn finish(&mut self) -> io::Result<Option<Rendered>> {
+        let frames = std::mem::take(&mut self.frames);
+        let gif = std::mem::replace(&mut self.gif, Gif::new(self.palette.clone(), GIF_DELAY));
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        if frames == 0 {
+            return Ok(None);
+        }
+
+        let path = match self.format {
+            Format::Gif => {
+                std::fs::create_dir_all(&self.dir)?;
+                let path = self.dir.join(format!("{}.gif", self.stage));
+                std::fs::write(&path, gif.encode())?;
+                path
+            }
+            Format::Png | Format::Ppm => self.dir.join(&self.stage),
+        };
+        Ok(Some(Rendered { frames, path }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::viz::Cell;
+
+    fn canvas() -> Canvas {
+        struct Checker;
+
+        impl Frame for Checker {
+            fn size(&self) -> (usize, usize) {
+                (3, 2)
+            }
+
+            fn cell(&self, x: usize, y: usize) -> Cell {
+                let color = if (x + y).is_multiple_of(2) {
+                    Color::Red
+                } else {
+                    Color::Blue
+                };
+                Cell::new('#').color(color)
+            }
+        }
+
+        Canvas::capture(&Checker)
+    }
+
+    /// Decodes a deflate stream using fixed Huffman codes and stored blocks.
+    fn inflate(data: &[u8]) -> Vec<u8> {
+        let mut pos = 0;
+        let mut bit = |n: u32| {
+            let mut value = 0;
+            for i in 0..n {
+                value |= ((data[pos / 8] >> (pos % 8)) as u32 & 1) << i;
+                pos += 1;
+            }
+            value
+        };
+        assert_eq!(bit(3), 0b011, "a single block with fixed codes");
+
+        let mut out = Vec::new();
+        loop {
+            let mut code = 0;
+            let mut len = 0;
+            let symbol = loop {
+                code = (code << 1) | bit(1);
+                len += 1;
+                match (len, code) {
+                    (7, 0..=23) => break code + 256,
+                    (8, 0x30..=0xbf) => break code - 0x30,
+                    (8, 0xc0..=0xc7) => break code - 0xc0 + 280,
+                    (9, 0x190..=0x1ff) => break code - 0x190 + 144,
+                    _ => {}
+                }
+            };
+            match symbol {
+                0..=255 => out.push(symbol as u8),
+                256 => return out,
+                _ => {
+                    let code = symbol as usize - 257;
+                    let length =
+                        LENGTH_BASES[code] as usize + bit(LENGTH_EXTRA[code] as u32) as usize;
+                    let code = (0..5).fold(0, |code, _| (code << 1) | bit(1)) as usize;
+                    let distance =
+                        DISTANCE_BASES[code] as usize + bit(DISTANCE_EXTRA[code] as u32) as usize;
+                    for _ in 0..length {
+                        out.push(out[out.len() - distance]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decodes the LZW codes of a GIF frame.
+    fn unlzw(data: &[u8]) -> Vec<u8> {
+        let clear = 1 << GIF_DEPTH;
+        let mut pos = 0;
+        let mut read = |n: u32| {
+            let mut value = 0usize;
+            for i in 0..n {
+                value |= ((data[pos / 8] >> (pos % 8)) as usize & 1) << i;
+                pos += 1;
+            }
+            value
+        };
+
+        let mut out = Vec::new();
+        let mut size = GIF_DEPTH + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(size);
+            if code == clear {
+                table = (0..clear).map(|c| vec![c as u8]).collect();
+                table.extend([vec![], vec![]]);
+                size = GIF_DEPTH + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match table.get(code) {
+                Some(entry) => entry.clone(),
+                None => {
+                    let mut entry = previous.clone().unwrap();
+                    entry.push(entry[0]);
+                    entry
+                }
+            };
+            if let Some(mut previous) = previous {
+                previous.push(entry[0]);
+                table.push(previous);
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            out.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn paints_cells_as_squares() {
+        let raster = Raster::new(&canvas(), 2);
+        assert_eq!((raster.width, raster.height), (6, 4));
+        let red = Color::Red as u8;
+        let blue = Color::Blue as u8;
+        assert_eq!(&raster.pixels[..6], [red, red, blue, blue, red, red]);
+        assert_eq!(&raster.pixels[12..18], [blue, blue, red, red, blue, blue]);
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let raster = Raster::new(&canvas(), 1);
+        let palette = Palette::default();
+        let image = ppm(&raster, &palette);
+        assert!(image.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(image.len(), 11 + 3 * 6);
+        assert_eq!(image[11..14], palette.rgb(Color::Red));
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn deflates_losslessly() {
+        let runs: Vec<u8> = (0..5000).map(|i| (i / 37 % 5) as u8).collect();
+        let text = b"abcabcabcabcabd the quick brown fox jumps over the lazy dog".repeat(20);
+        for data in [&runs[..], &text, b"", b"ab", &[7; 1000]] {
+            let compressed = deflate(data);
+            assert_eq!(inflate(&compressed), data);
+        }
+        assert!(deflate(&runs).len() < runs.len() / 4);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let raster = Raster::new(&canvas(), 4);
+        let image = png(&raster, &Palette::default());
+        assert!(image.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x0c\0\0\0\x08\x08\x03"));
+        assert!(image.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+
+        let idat = image.windows(4).position(|w| w == b"IDAT").unwrap();
+        let len = u32::from_be_bytes(image[idat - 4..idat].try_into().unwrap()) as usize;
+        let zlib = &image[idat + 4..idat + 4 + len];
+        let scanlines = inflate(&zlib[2..zlib.len() - 4]);
+        assert_eq!(scanlines.len(), 8 * 13);
+        assert_eq!(scanlines[0], 0);
+        assert_eq!(&scanlines[1..13], &raster.pixels[..12]);
+    }
+
+    #[test]
+    fn compresses_gif_frames() {
+        let pixels: Vec<u8> = (0..20_000).map(|i| ((i * i) / 7 % 11) as u8).collect();
+        for data in [&pixels[..], &[3; 5000], &[1], &[]] {
+            assert_eq!(unlzw(&lzw(data)), data);
+        }
+    }
+
+    #[test]
+    fn encodes_gif() {
+        let mut gif = Gif::new(Palette::default(), 5);
+        gif.push(&Raster::new(&canvas(), 1));
+        gif.push(&Raster::new(&canvas(), 2));
+        assert_eq!(gif.len(), 2);
+
+        let image = gif.encode();
+        assert!(image.starts_with(b"GIF89a\x06\0\x04\0\xf3"));
+        assert_eq!(image.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(image.last(), Some(&0x3b));
+        // The first frame is cleared since the second one is larger.
+        let control = image.windows(4).position(|w| w == [0x21, 0xf9, 4, 2 << 2]);
+        assert!(control.is_some());
+    }
+}
@@ -1,6 +1,23 @@
 // Declare modules
+pub mod alloc;
+pub mod answers;
+pub mod circuit;
+pub mod dot;
+pub mod examples;
+pub mod image;
+pub mod ocr;
+pub mod params;
 pub mod parsing;
+pub mod report;
+pub mod run_all;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod testing;
 pub mod vec;
+pub mod viz;
+pub mod vm;
+pub mod watch;
 
 // Re-export the prelude for convenience
 pub mod prelude;
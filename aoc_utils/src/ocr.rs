@@ -0,0 +1,149 @@
+//! Reading the capital letters that some puzzles draw as their answer.
+//!
+//! The letters are 6 pixels tall and 4 wide, and drawn one column apart, so
+//! a [`Bitmap`] of the answer can be turned back into text with
+//! [`Bitmap::read`].
+
+use std::fmt;
+
+/// The known letters, with `#` for lit pixels.
+const GLYPHS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+/// A black and white picture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// A bitmap with every pixel off.
+    pub fn new(width: usize, height: usize) -> Self {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels[y * self.width + x] = lit;
+    }
+
+    /// Reads the letters drawn in the bitmap, or `None` if it is not 6 pixels
+    /// tall or holds anything but known letters.
+    pub fn read(&self) -> Option<String> {
+        if self.height != LETTER_HEIGHT {
+            return None;
+        }
+        let columns = (0..self.width)
+            .map(|x| (0..self.height).any(|y| self.get(x, y)))
+            .collect::<Vec<_>>();
+        let mut text = String::new();
+        let mut x = 0;
+        while x < self.width {
+            if !columns[x] {
+                x += 1;
+                continue;
+            }
+            if x + LETTER_WIDTH > self.width {
+                return None;
+            }
+            let glyph = (0..LETTER_HEIGHT)
+                .flat_map(|y| (x..x + LETTER_WIDTH).map(move |x| (x, y)))
+                .map(|(x, y)| if self.get(x, y) { '#' } else { '.' })
+                .collect::<String>();
+            let (letter, _) = GLYPHS.iter().find(|(_, g)| *g == glyph)?;
+            text.push(*letter);
+            x += LETTER_WIDTH;
+        }
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+impl fmt::Display for Bitmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` the way the puzzles do, one letter every five columns.
+    fn draw(text: &str) -> Bitmap {
+        let mut bitmap = Bitmap::new(5 * text.len(), LETTER_HEIGHT);
+        for (i, c) in text.chars().enumerate() {
+            let (_, glyph) = GLYPHS.iter().find(|(l, _)| *l == c).unwrap();
+            for (j, pixel) in glyph.chars().enumerate() {
+                let (x, y) = (5 * i + j % LETTER_WIDTH, j / LETTER_WIDTH);
+                bitmap.set(x, y, pixel == '#');
+            }
+        }
+        bitmap
+    }
+
+    #[test]
+    fn reads_every_letter() {
+        let letters = GLYPHS.iter().map(|(c, _)| *c).collect::<String>();
+        assert_eq!(draw(&letters).read().as_deref(), Some(letters.as_str()));
+        assert_eq!(draw("ZERO").read().as_deref(), Some("ZERO"));
+    }
+
+    #[test]
+    fn rejects_other_pictures() {
+        let mut bitmap = draw("HELLO");
+        bitmap.set(2, 0, true);
+        assert_eq!(bitmap.read(), None);
+        assert_eq!(Bitmap::new(10, 6).read(), None);
+        assert_eq!(Bitmap::new(10, 7).read(), None);
+    }
+
+    #[test]
+    fn displays_pixels() {
+        let mut bitmap = Bitmap::new(3, 2);
+        bitmap.set(1, 0, true);
+        bitmap.set(2, 1, true);
+        assert_eq!(bitmap.to_string(), ".#.\n..#\n");
+    }
+}
@@ -0,0 +1,190 @@
+//! Puzzle parameters that differ between the examples and the real input.
+//!
+//! Some puzzles give constants outside of the input, such as the size of a
+//! grid or the row to inspect, and use smaller ones in their examples. A day
+//! declares each of them as a [`Param`] holding the value of the real input:
+//!
+//! ```ignore
+//! static WIDTH: Param<i32> = Param::new("width", 101);
+//!
+//! pub fn part_1(input: &[Robot]) -> u32 {
+//!     let width = WIDTH.get();
+//!     ...
+//! }
+//! ```
+//!
+//! The runner overrides them with `--param width=11`, and tests with
+//! [`with_params`] or the parameters of an [`aoc_test!`](crate::aoc_test)
+//! example. Reading a parameter takes a lock, so solvers read them once rather
+//! than in their inner loops.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A constant of a puzzle, with the value used by the real input.
+pub struct Param<T> {
+    name: &'static str,
+    default: T,
+}
+
+impl<T> Param<T> {
+    pub const fn new(name: &'static str, default: T) -> Self {
+        Param { name, default }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Param<T>
+where
+    T: Clone + FromStr,
+    T::Err: Display,
+{
+    /// Returns the overridden value of the parameter, or its default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the override is not a valid `T`.
+    pub fn get(&self) -> T {
+        match lookup(self.name) {
+            Some(value) => value.parse().unwrap_or_else(|err| {
+                panic!("invalid value {value:?} for parameter {}: {err}", self.name)
+            }),
+            None => self.default.clone(),
+        }
+    }
+}
+
+/// A value given on the command line, and whether a day has read it.
+struct Override {
+    name: String,
+    value: String,
+    used: bool,
+}
+
+static OVERRIDES: Mutex<Vec<Override>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static SCOPED: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn lookup(name: &str) -> Option<String> {
+    let scoped = SCOPED.with_borrow(|scoped| {
+        scoped
+            .iter()
+            .rev()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.clone())
+    });
+    scoped.or_else(|| {
+        let mut value = None;
+        for o in OVERRIDES.lock().unwrap().iter_mut() {
+            if o.name == name {
+                o.used = true;
+                value = Some(o.value.clone());
+            }
+        }
+        value
+    })
+}
+
+/// Overrides the parameter `name` for every day run afterwards.
+pub fn set(name: &str, value: &str) {
+    OVERRIDES.lock().unwrap().push(Override {
+        name: name.to_string(),
+        value: value.to_string(),
+        used: false,
+    });
+}
+
+/// Returns the overrides given to [`set`] that no day has read so far.
+pub fn unused() -> Vec<String> {
+    let mut names: Vec<String> = OVERRIDES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|o| !o.used)
+        .map(|o| o.name.clone())
+        .collect();
+    names.dedup();
+    names
+}
+
+/// Runs `f` with the parameters overridden on the current thread only, so
+/// tests running in parallel do not see each other's values.
+pub fn with_params<R>(params: &[(&str, String)], f: impl FnOnce() -> R) -> R {
+    /// Restores the previous overrides, even if `f` panics.
+    struct Restore(usize);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.with_borrow_mut(|scoped| scoped.truncate(self.0));
+        }
+    }
+
+    let _restore = SCOPED.with_borrow_mut(|scoped| {
+        let restore = Restore(scoped.len());
+        scoped.extend(
+            params
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone())),
+        );
+        restore
+    });
+    f()
+}
+
+/// Parses a `name=value` assignment, as given to `--param`.
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    match assignment.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected NAME=VALUE, found {assignment:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SIZE: Param<u32> = Param::new("size", 70);
+    static ROW: Param<i64> = Param::new("row", 2_000_000);
+
+    #[test]
+    fn defaults_to_the_real_input() {
+        assert_eq!(SIZE.get(), 70);
+        assert_eq!(SIZE.name(), "size");
+    }
+
+    #[test]
+    fn overrides_are_scoped() {
+        with_params(&[("size", "6".to_string())], || {
+            assert_eq!(SIZE.get(), 6);
+            with_params(&[("size", "3".to_string())], || assert_eq!(SIZE.get(), 3));
+            assert_eq!(SIZE.get(), 6);
+            assert_eq!(ROW.get(), 2_000_000);
+        });
+        assert_eq!(SIZE.get(), 70);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value \"wide\" for parameter size")]
+    fn rejects_invalid_values() {
+        with_params(&[("size", "wide".to_string())], || SIZE.get());
+    }
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(
+            parse_assignment("width = 11"),
+            Ok(("width".to_string(), "11".to_string()))
+        );
+        assert!(parse_assignment("width").is_err());
+        assert!(parse_assignment("=11").is_err());
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 /// Extracts integers from a string.
 ///
 /// This function scans the input string for integer-like sequences and returns
@@ -82,6 +85,176 @@ where
     numbers
 }
 
+/// An error found while parsing a puzzle input, pointing at where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    /// What the parser expected to find.
+    pub expected: String,
+    /// What was found instead, or `None` at the end of the line.
+    pub found: Option<String>,
+}
+
+/// Longest excerpt of the input quoted in an error.
+const EXCERPT_LEN: usize = 20;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        match &self.found {
+            Some(found) => write!(f, "found {found:?}"),
+            None => write!(f, "found end of line"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The complete text being parsed, used to locate errors in its slices.
+///
+/// Generators split their input with the usual `str` methods, which return
+/// slices of it. When one of them is invalid, [`Source::error`] turns it back
+/// into a line and column:
+///
+/// ```
+/// # use aoc_utils::parsing::Source;
+/// let input = "1,2\n3;4\n";
+/// let source = Source::new(input);
+/// let line = input.lines().nth(1).unwrap();
+/// let error = source.split_once(line, ",").unwrap_err();
+/// assert_eq!(error.to_string(), r#"line 2, column 1: expected ",", found "3;4""#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Source { text }
+    }
+
+    /// Returns the byte offset of `part`, which must be a slice of the source.
+    fn offset(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        assert!(
+            offset + part.len() <= self.text.len(),
+            "{part:?} is not a slice of the parsed input"
+        );
+        offset
+    }
+
+    fn error_at(&self, offset: usize, len: usize, expected: &str) -> ParseError {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let rest = self.text[offset..].lines().next().unwrap_or("");
+        let found: String = match len {
+            0 => rest.chars().take(EXCERPT_LEN).collect(),
+            len => self.text[offset..offset + len]
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(EXCERPT_LEN)
+                .collect(),
+        };
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.to_string(),
+            found: (!found.is_empty()).then_some(found),
+        }
+    }
+
+    /// Reports that `part` is not what was `expected`.
+    pub fn error(&self, part: &str, expected: &str) -> ParseError {
+        self.error_at(self.offset(part), part.len(), expected)
+    }
+
+    /// Reports that `expected` is missing right after `part`.
+    pub fn error_after(&self, part: &str, expected: &str) -> ParseError {
+        self.error_at(self.offset(part) + part.len(), 0, expected)
+    }
+
+    /// Parses `token`, reporting `expected` if it is invalid.
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, expected))
+    }
+
+    /// Parses `token` with a parser reporting errors relative to `token`, and
+    /// moves them to their position in the source.
+    pub fn parse_as<T: FromStr<Err = ParseError>>(&self, token: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|error: ParseError| {
+            let start = self.error_at(self.offset(token), 0, "");
+            ParseError {
+                line: start.line + error.line - 1,
+                column: match error.line {
+                    1 => start.column + error.column - 1,
+                    _ => error.column,
+                },
+                ..error
+            }
+        })
+    }
+
+    /// Splits `part` around the first `delimiter`.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, &format!("{delimiter:?}")))
+    }
+
+    /// Returns the first `N` integers found in `part`, as extracted by
+    /// [`extract_integers`].
+    pub fn integers<T, const N: usize>(&self, part: &str) -> Result<[T; N], ParseError>
+    where
+        T: TryFrom<i64> + fmt::Debug,
+        <T as TryFrom<i64>>::Error: fmt::Debug,
+    {
+        let mut integers = extract_integers(part);
+        if integers.len() < N {
+            return Err(self.error(part, &format!("{N} integers")));
+        }
+        integers.truncate(N);
+        Ok(integers.try_into().expect("exactly N integers are left"))
+    }
+
+    /// Converts every character of the lines of `part` with `cell`, which is
+    /// given its `(x, y)` position in the grid. Characters it rejects are
+    /// reported as not being `expected`.
+    pub fn grid<T>(
+        &self,
+        part: &str,
+        mut cell: impl FnMut((usize, usize), char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Vec<Vec<T>>, ParseError> {
+        part.lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.char_indices()
+                    .enumerate()
+                    .map(|(x, (i, c))| {
+                        cell((x, y), c)
+                            .ok_or_else(|| self.error(&line[i..i + c.len_utf8()], expected))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,4 +306,78 @@ mod tests {
         let result = extract_integers::<i64>("-1 -2 -3 -4");
         assert_eq!(result, vec![-1, -2, -3, -4]);
     }
+
+    const INPUT: &str = "12 ab\nx: 7\n#.#?\n";
+
+    #[test]
+    fn test_error_position() {
+        let source = Source::new(INPUT);
+        let token = INPUT.split_whitespace().nth(1).unwrap();
+        let error = source.parse::<u32>(token, "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.found.as_deref(), Some("ab"));
+        assert_eq!(
+            error.to_string(),
+            r#"line 1, column 4: expected a number, found "ab""#
+        );
+    }
+
+    #[test]
+    fn test_error_after() {
+        let source = Source::new(INPUT);
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = source.error_after(line, "a semicolon");
+        assert_eq!((error.line, error.column, error.found), (2, 5, None));
+    }
+
+    #[test]
+    fn test_source_integers() {
+        let source = Source::new(INPUT);
+        let [a] = source.integers::<u32, 1>(INPUT).unwrap();
+        assert_eq!(a, 12);
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = source.integers::<u32, 2>(line).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (2, "2 integers"));
+    }
+
+    #[test]
+    fn test_source_grid() {
+        let source = Source::new(INPUT);
+        let grid = INPUT.lines().nth(2).unwrap();
+        let cell = |_, c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let error = source.grid(grid, cell, "'#' or '.'").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(
+            source.grid(&grid[..3], cell, "'#' or '.'"),
+            Ok(vec![vec![true, false, true]])
+        );
+    }
+
+    #[derive(Debug)]
+    struct Assignment(u32);
+
+    impl FromStr for Assignment {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let source = Source::new(s);
+            let (_, value) = source.split_once(s, ": ")?;
+            source.parse(value, "a value").map(Assignment)
+        }
+    }
+
+    #[test]
+    fn test_parse_as_relocates_errors() {
+        let input = "a: 1\nb: x\n";
+        let source = Source::new(input);
+        let line = input.lines().next().unwrap();
+        assert_eq!(source.parse_as::<Assignment>(line).unwrap().0, 1);
+        let line = input.lines().nth(1).unwrap();
+        let error = source.parse_as::<Assignment>(line).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
 }
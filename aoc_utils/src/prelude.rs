@@ -1,4 +1,5 @@
 // utils/src/prelude.rs
 
-pub use crate::parsing::extract_integers;
+pub use crate::params::Param;
+pub use crate::parsing::{extract_integers, ParseError, Source};
 pub use crate::vec::{Vec2, Vec3};
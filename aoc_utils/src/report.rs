@@ -0,0 +1,327 @@
+//! Timings of every stage of the solvers, saved as JSON or CSV and compared
+//! against a baseline.
+//!
+//! A report is produced by `--report`: every generator and solver of the
+//! selected days is run repeatedly and summarised by its median. Saving a
+//! report as JSON gives a baseline that later runs can be compared with, so the
+//! impact of a change to shared code shows up on every day at once.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::AllocStats;
+
+/// The timings of a set of days.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub days: Vec<DayReport>,
+}
+
+/// The timings of each stage of one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    pub stages: Vec<StageTiming>,
+}
+
+impl DayReport {
+    /// Sum of the medians of every stage.
+    pub fn total(&self) -> Duration {
+        self.stages.iter().map(StageTiming::median).sum()
+    }
+}
+
+/// Summary of the samples taken for a generator or solver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub name: String,
+    pub samples: usize,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+    /// Allocations of a single run, with the `alloc-stats` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    /// The answer of a solver, as printed by the runner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+}
+
+impl StageTiming {
+    /// Summarises the durations measured for the stage called `name`.
+    pub fn from_samples(name: &str, samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples for {name}");
+        let mut nanos: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        nanos.sort_unstable();
+
+        let mid = nanos.len() / 2;
+        let median_ns = if nanos.len().is_multiple_of(2) {
+            (nanos[mid - 1] + nanos[mid]) / 2
+        } else {
+            nanos[mid]
+        };
+
+        StageTiming {
+            name: name.to_string(),
+            samples: nanos.len(),
+            median_ns,
+            mean_ns: nanos.iter().sum::<u64>() / nanos.len() as u64,
+            min_ns: nanos[0],
+            max_ns: nanos[nanos.len() - 1],
+            alloc: None,
+            answer: None,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// An error raised while reading or writing a report.
+#[derive(Debug)]
+pub enum ReportError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// Reports are written as `.json` or `.csv` and read back from `.json`.
+    UnknownFormat(PathBuf),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReportError::Io(err) => write!(f, "could not access report: {err}"),
+            ReportError::Json(err) => write!(f, "invalid report: {err}"),
+            ReportError::UnknownFormat(path) => {
+                write!(f, "{}: reports must be .json or .csv files", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+/// How the median of a stage moved compared to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// Slower by more than the threshold, as a fraction of the baseline.
+    Regressed(f64),
+    /// Faster by more than the threshold.
+    Improved(f64),
+    /// Within the threshold.
+    Unchanged(f64),
+    /// The stage is not in the baseline.
+    New,
+}
+
+/// A stage of the report next to its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub stage: &'a StageTiming,
+    pub baseline: Option<&'a StageTiming>,
+    pub change: Change,
+}
+
+impl Report {
+    /// Reads a report saved as JSON.
+    pub fn load(path: &Path) -> Result<Self, ReportError> {
+        let content = std::fs::read_to_string(path).map_err(ReportError::Io)?;
+        serde_json::from_str(&content).map_err(ReportError::Json)
+    }
+
+    /// Writes the report as JSON or CSV, depending on the extension of `path`.
+    pub fn save(&self, path: &Path) -> Result<(), ReportError> {
+        let content = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.to_json(),
+            Some("csv") => self.to_csv(),
+            _ => return Err(ReportError::UnknownFormat(path.to_path_buf())),
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(ReportError::Io)?;
+        }
+        std::fs::write(path, content).map_err(ReportError::Io)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports are always serializable") + "\n"
+    }
+
+    /// Formats the report with one line per stage.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "year,day,stage,samples,median_ns,mean_ns,min_ns,max_ns,allocations,alloc_bytes,peak_bytes\n",
+        );
+        for day in &self.days {
+            for stage in &day.stages {
+                let alloc = stage.alloc.map_or(",,".to_string(), |alloc| {
+                    format!("{},{},{}", alloc.allocations, alloc.bytes, alloc.peak)
+                });
+                csv += &format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    day.year,
+                    day.day,
+                    stage.name,
+                    stage.samples,
+                    stage.median_ns,
+                    stage.mean_ns,
+                    stage.min_ns,
+                    stage.max_ns,
+                    alloc
+                );
+            }
+        }
+        csv
+    }
+
+    fn find(&self, year: u16, day: u8, stage: &str) -> Option<&StageTiming> {
+        self.days
+            .iter()
+            .find(|d| d.year == year && d.day == day)?
+            .stages
+            .iter()
+            .find(|s| s.name == stage)
+    }
+
+    /// Compares the median of every stage with `baseline`. Stages whose median
+    /// moved by more than `threshold` (a fraction, such as `0.1` for 10%) are
+    /// reported as regressed or improved.
+    pub fn compare<'a>(&'a self, baseline: &'a Report, threshold: f64) -> Vec<Comparison<'a>> {
+        self.days
+            .iter()
+            .flat_map(|day| day.stages.iter().map(move |stage| (day, stage)))
+            .map(|(day, stage)| {
+                let base = baseline.find(day.year, day.day, &stage.name);
+                let change = match base {
+                    None => Change::New,
+                    Some(base) => {
+                        let ratio = stage.median_ns as f64 / base.median_ns.max(1) as f64 - 1.;
+                        if ratio > threshold {
+                            Change::Regressed(ratio)
+                        } else if ratio < -threshold {
+                            Change::Improved(ratio)
+                        } else {
+                            Change::Unchanged(ratio)
+                        }
+                    }
+                };
+                Comparison {
+                    year: day.year,
+                    day: day.day,
+                    stage,
+                    baseline: base,
+                    change,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, median_ns: u64) -> StageTiming {
+        StageTiming {
+            name: name.to_string(),
+            samples: 1,
+            median_ns,
+            mean_ns: median_ns,
+            min_ns: median_ns,
+            max_ns: median_ns,
+            alloc: None,
+            answer: None,
+        }
+    }
+
+    fn report(stages: Vec<StageTiming>) -> Report {
+        Report {
+            days: vec![DayReport {
+                year: 2022,
+                day: 1,
+                stages,
+            }],
+        }
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let samples = [5, 1, 3, 9].map(Duration::from_nanos);
+        let timing = StageTiming::from_samples("part_1", &samples);
+        assert_eq!(timing.samples, 4);
+        assert_eq!(timing.median_ns, 4);
+        assert_eq!(timing.mean_ns, 4);
+        assert_eq!((timing.min_ns, timing.max_ns), (1, 9));
+
+        let timing = StageTiming::from_samples("part_1", &samples[..3]);
+        assert_eq!(timing.median_ns, 3);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let mut part_1 = stage("part_1", 20);
+        part_1.answer = Some("##..\n#..#".to_string());
+        let report = report(vec![stage("generator", 10), part_1]);
+        assert_eq!(
+            serde_json::from_str::<Report>(&report.to_json()).unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn csv_has_a_line_per_stage() {
+        let mut part_1 = stage("part_1", 20);
+        part_1.alloc = Some(AllocStats {
+            allocations: 2,
+            bytes: 64,
+            peak: 32,
+        });
+        let report = report(vec![stage("generator", 10), part_1]);
+        assert_eq!(
+            report.to_csv(),
+            "year,day,stage,samples,median_ns,mean_ns,min_ns,max_ns,allocations,alloc_bytes,peak_bytes\n\
+             2022,1,generator,1,10,10,10,10,,,\n\
+             2022,1,part_1,1,20,20,20,20,2,64,32\n"
+        );
+    }
+
+    #[test]
+    fn flags_medians_beyond_threshold() {
+        let baseline = report(vec![stage("generator", 100), stage("part_1", 100)]);
+        let current = report(vec![
+            stage("generator", 105),
+            stage("part_1", 150),
+            stage("part_2", 10),
+        ]);
+
+        let changes: Vec<Change> = current
+            .compare(&baseline, 0.1)
+            .into_iter()
+            .map(|c| c.change)
+            .collect();
+        assert!(matches!(changes[0], Change::Unchanged(_)));
+        assert!(matches!(changes[1], Change::Regressed(r) if (r - 0.5).abs() < 1e-9));
+        assert_eq!(changes[2], Change::New);
+
+        let changes: Vec<Change> = baseline
+            .compare(&current, 0.1)
+            .into_iter()
+            .map(|c| c.change)
+            .collect();
+        assert!(matches!(changes[1], Change::Improved(_)));
+    }
+
+    #[test]
+    fn unknown_extensions_are_rejected() {
+        assert!(matches!(
+            Report::default().save(Path::new("report.txt")),
+            Err(ReportError::UnknownFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,312 @@
+//! Runs every registered day of every year concurrently, behind `aoc run-all`.
+//!
+//! Each year is built in release mode, then every day runs in its own process
+//! (through the year's `--json` flag) on a pool of worker threads. The timings
+//! of each stage are gathered into a [`Summary`] of the slowest days, the total
+//! of each year and the wall time against the time spent in the solvers.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_main::colored::*;
+use aoc_main::utils::Line;
+
+use crate::report::{DayReport, Report};
+
+/// The outcome of running one day.
+#[derive(Debug)]
+pub struct DayResult {
+    pub year: u16,
+    pub day: u8,
+    /// The timings of the day, or why it could not be run.
+    pub report: Result<DayReport, String>,
+}
+
+/// The crate directory holding the solutions of `year`.
+pub fn crate_dir(root: &Path, year: u16) -> PathBuf {
+    root.join(format!("aoc{:02}", year % 100))
+}
+
+/// Lists the years with a crate in the workspace at `root`.
+pub fn years(root: &Path) -> std::io::Result<Vec<u16>> {
+    let mut years: Vec<u16> = std::fs::read_dir(root)?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let year: u16 = name.to_str()?.strip_prefix("aoc")?.parse().ok()?;
+            Some(2000 + year)
+        })
+        .filter(|&year| crate_dir(root, year).join("src/main.rs").is_file())
+        .collect();
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// The release binary of `year`, as built by [`build`].
+pub(crate) fn binary(root: &Path, year: u16) -> PathBuf {
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| root.join("target"), PathBuf::from);
+    target.join(format!("release/aoc{:02}", year % 100))
+}
+
+/// Builds the binaries of `years` in release mode.
+pub fn build(root: &Path, years: &[u16]) -> Result<(), String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(root)
+        .args(["build", "--release", "--quiet"]);
+    for year in years {
+        command.args(["-p", &format!("aoc{:02}", year % 100)]);
+    }
+
+    let status = command
+        .status()
+        .map_err(|err| format!("could not run cargo: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("cargo build failed: {status}"))
+    }
+}
+
+fn run(binary: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(binary)
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .map_err(|err| format!("could not run {}: {err}", binary.display()))?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|err| err.to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty() && !line.starts_with("note:"));
+        Err(reason.unwrap_or("no error output").trim().to_string())
+    }
+}
+
+/// Runs `day` once on its saved input and returns its timings and answers.
+pub(crate) fn run_day(binary: &Path, day: u8) -> Result<DayReport, String> {
+    let stdout = run(binary, &["-d", &day.to_string(), "--json"])?;
+    let report: Report = serde_json::from_str(&stdout).map_err(|err| err.to_string())?;
+    report
+        .days
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("day {day} is not registered"))
+}
+
+/// Runs every registered day of `years` on `jobs` threads.
+pub fn run_all(root: &Path, years: &[u16], jobs: usize) -> Result<Vec<DayResult>, String> {
+    let mut queue = Vec::new();
+    for &year in years {
+        for day in run(&binary(root, year), &["--list"])?.lines() {
+            let day = day.parse().map_err(|_| format!("invalid day {day:?}"))?;
+            queue.push((year, day));
+        }
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(queue.len()));
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                while let Some(&(year, day)) = queue.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let report = run_day(&binary(root, year), day);
+                    results
+                        .lock()
+                        .unwrap()
+                        .push(DayResult { year, day, report });
+                }
+            });
+        }
+    });
+
+    Ok(results.into_inner().unwrap())
+}
+
+/// The timings of a run over several years.
+pub struct Summary {
+    pub results: Vec<DayResult>,
+    /// Time elapsed while running the days.
+    pub wall: Duration,
+    pub jobs: usize,
+}
+
+impl Summary {
+    fn timed(&self) -> impl Iterator<Item = &DayReport> {
+        self.results
+            .iter()
+            .filter_map(|result| result.report.as_ref().ok())
+    }
+
+    /// The days that took the most time, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<&DayReport> {
+        let mut days: Vec<&DayReport> = self.timed().collect();
+        days.sort_by_key(|day| std::cmp::Reverse(day.total()));
+        days.truncate(count);
+        days
+    }
+
+    /// The number of days and time spent in the solvers of each year.
+    pub fn per_year(&self) -> BTreeMap<u16, (usize, Duration)> {
+        let mut years = BTreeMap::new();
+        for day in self.timed() {
+            let (days, total) = years.entry(day.year).or_insert((0, Duration::ZERO));
+            *days += 1;
+            *total += day.total();
+        }
+        years
+    }
+
+    /// The time spent in the solvers, summed over every day.
+    pub fn cpu(&self) -> Duration {
+        self.timed().map(DayReport::total).sum()
+    }
+
+    /// The days that could not be run, sorted by year and day.
+    pub fn failures(&self) -> Vec<&DayResult> {
+        let mut failures: Vec<&DayResult> = self
+            .results
+            .iter()
+            .filter(|result| result.report.is_err())
+            .collect();
+        failures.sort_by_key(|result| (result.year, result.day));
+        failures
+    }
+
+    /// Prints the `top` slowest days, the total of every year and the overall
+    /// wall and solver time.
+    pub fn print(&self, top: usize) {
+        println!(
+            "Ran {} days on {} threads",
+            self.results.len(),
+            self.jobs.max(1)
+        );
+
+        println!();
+        println!("Slowest days");
+        for day in self.slowest(top) {
+            let line = Line::new(format!("{} day{:<2}", day.year, day.day));
+            println!("  {:40}", line.with_state(format!("{:.2?}", day.total())));
+        }
+
+        println!();
+        println!("Per year");
+        for (year, (days, total)) in self.per_year() {
+            let line = Line::new(format!("{year} ({days} days)"));
+            println!("  {:40}", line.with_state(format!("{total:.2?}")));
+        }
+
+        let failures = self.failures();
+        if !failures.is_empty() {
+            println!();
+            println!("Failed");
+            for result in failures {
+                let line = Line::new(format!("{} day{:<2}", result.year, result.day));
+                let reason = result.report.as_ref().unwrap_err();
+                println!("  {:40}", line.with_state(reason.red()));
+            }
+        }
+
+        let cpu = self.cpu();
+        println!();
+        println!(
+            "Wall time {:.2?}, solver time {:.2?} ({:.1}x)",
+            self.wall,
+            cpu,
+            cpu.as_secs_f64() / self.wall.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+}
+
+/// Builds and runs every day of `years`, returning the summary of the run.
+pub fn run_years(root: &Path, years: &[u16], jobs: usize) -> Result<Summary, String> {
+    build(root, years)?;
+    let start = Instant::now();
+    let results = run_all(root, years, jobs)?;
+    Ok(Summary {
+        results,
+        wall: start.elapsed(),
+        jobs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::StageTiming;
+
+    fn timed(year: u16, day: u8, millis: &[u64]) -> DayResult {
+        let stages = millis
+            .iter()
+            .map(|&ms| StageTiming::from_samples("part_1", &[Duration::from_millis(ms)]))
+            .collect();
+        DayResult {
+            year,
+            day,
+            report: Ok(DayReport { year, day, stages }),
+        }
+    }
+
+    fn summary() -> Summary {
+        Summary {
+            results: vec![
+                timed(2022, 1, &[1, 2]),
+                timed(2022, 16, &[400, 900]),
+                timed(2024, 6, &[2, 300]),
+                DayResult {
+                    year: 2024,
+                    day: 10,
+                    report: Err("panicked".to_string()),
+                },
+            ],
+            wall: Duration::from_millis(900),
+            jobs: 4,
+        }
+    }
+
+    #[test]
+    fn sorts_slowest_days() {
+        let summary = summary();
+        let slowest: Vec<(u16, u8)> = summary
+            .slowest(2)
+            .iter()
+            .map(|day| (day.year, day.day))
+            .collect();
+        assert_eq!(slowest, [(2022, 16), (2024, 6)]);
+    }
+
+    #[test]
+    fn totals_per_year() {
+        let summary = summary();
+        let years = summary.per_year();
+        assert_eq!(years[&2022], (2, Duration::from_millis(1303)));
+        assert_eq!(years[&2024], (1, Duration::from_millis(302)));
+        assert_eq!(summary.cpu(), Duration::from_millis(1605));
+    }
+
+    #[test]
+    fn reports_failures() {
+        let summary = summary();
+        let failures = summary.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!((failures[0].year, failures[0].day), (2024, 10));
+    }
+
+    #[test]
+    fn finds_year_crates() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let years = years(root).unwrap();
+        assert!(years.contains(&2015));
+        assert!(years.contains(&2024));
+        assert!(years.windows(2).all(|w| w[0] < w[1]));
+    }
+}
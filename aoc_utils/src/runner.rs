@@ -0,0 +1,974 @@
+//! Command line runner shared by every year.
+//!
+//! Each year registers its solved days with [`main!`](crate::main), using the
+//! same syntax as `aoc_main::main!`:
+//!
+//! ```ignore
+//! aoc_utils::main! {
+//!     year 2022;
+//!     day1  : generator => part_1, part_2;
+//!     day2              => part_1, part_2;
+//! }
+//! ```
+//!
+//! The macro turns every line into a [`Day`] whose stages are driven through
+//! a [`Harness`], so the same registry is used to print answers, benchmark the
+//! solvers, record their timings in a [`Report`] and verify them against the
+//! year's `answers.toml`.
+//!
+//! A part may be registered with several implementations, named after the
+//! part they solve (`part_1`, `part_1_solution_2`, `part_2_slow`, ...).
+//! `--cross-check` runs all of them on the same input, shows their timings side
+//! by side and fails if they disagree.
+//!
+//! Puzzle constants declared as [`Param`](crate::params::Param)s are overridden
+//! with `--param name=value`, to run a day on an example from the puzzle text.
+//! `--animate` draws the [`Frame`](crate::viz::Frame)s shown by the
+//! simulations while they run, and `--render` writes them to images.
+//! `--dot <FILE>` writes the circuits and networks exported by the days as
+//! [`Graph`](crate::dot::Graph)s to a Graphviz file.
+
+use std::hint::black_box;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_main::clap::{value_parser, Arg, ArgAction, ArgGroup, ArgMatches};
+use aoc_main::colored::*;
+use aoc_main::criterion::{measurement::WallTime, BenchmarkGroup, Criterion};
+use aoc_main::utils::Line;
+
+use crate::alloc::{self, AllocStats};
+use crate::answers::{part_of, Answers, Verdict};
+use crate::dot;
+use crate::image::Format;
+use crate::params;
+use crate::parsing::ParseError;
+use crate::report::{Change, DayReport, Report, StageTiming};
+use crate::viz;
+
+/// A registered day and the function running its generator and solvers.
+pub struct Day {
+    /// The module name of the day, such as `day7`.
+    pub name: &'static str,
+    /// The registered solvers, in order.
+    pub solvers: &'static [&'static str],
+    /// Runs the day on an input, handing every stage to the harness.
+    pub solve: fn(&str, &mut dyn Harness),
+}
+
+impl Day {
+    /// Returns the day of the month this day solves.
+    pub fn number(&self) -> u8 {
+        self.name[3..].parse().expect("days must be integers")
+    }
+
+    /// Returns whether a part has several implementations, such as `part_1`
+    /// and `part_1_solution_2`.
+    pub fn has_alternatives(&self) -> bool {
+        let mut parts: Vec<u8> = self.solvers.iter().map(|name| part_of(name)).collect();
+        parts.sort_unstable();
+        parts.windows(2).any(|pair| pair[0] == pair[1])
+    }
+}
+
+/// Receives the stages of a day as it is solved.
+///
+/// Implementations decide how to run each stage (once, timed, repeatedly for
+/// benchmarks, ...) but must call `run` at least once, since the solvers
+/// depend on the output of the generator.
+pub trait Harness {
+    /// Runs the generator.
+    fn generator(&mut self, run: &mut dyn FnMut());
+
+    /// Runs the solver called `name`; `run` returns the formatted answer.
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String);
+
+    /// Reports that the generator rejected the input. The solvers of the day
+    /// are skipped and the runner moves on to the next day.
+    fn parse_error(&mut self, error: &ParseError) {
+        println!(
+            "  - {}",
+            Line::new("generator").with_state(error.to_string().red())
+        );
+    }
+}
+
+/// The days registered for one year and where their files live.
+pub struct Year<'a> {
+    pub year: u16,
+    /// Directory of the year's crate, holding its `answers.toml`.
+    pub manifest_dir: &'static str,
+    /// Directory of the puzzle inputs, relative to `manifest_dir`.
+    pub inputs: Option<&'static str>,
+    pub days: &'a [Day],
+}
+
+impl Year<'_> {
+    fn input_path(&self, day: u8) -> PathBuf {
+        let manifest_dir = Path::new(self.manifest_dir);
+        let dir = match self.inputs {
+            Some(inputs) => manifest_dir.join(inputs),
+            None => manifest_dir.join("../input").join(self.year.to_string()),
+        };
+        dir.join(format!("day{day}.txt"))
+    }
+
+    fn answers_path(&self) -> PathBuf {
+        Path::new(self.manifest_dir).join("answers.toml")
+    }
+
+    fn baseline_path(&self) -> PathBuf {
+        Path::new(self.manifest_dir).join("bench/baseline.json")
+    }
+
+    /// Reads the local input for `day`, if it has been saved. Empty files are
+    /// placeholders left by `aoc new` and count as missing.
+    fn local_input(&self, day: u8) -> Option<String> {
+        let mut input = std::fs::read_to_string(self.input_path(day))
+            .ok()
+            .filter(|input| !input.is_empty())?;
+        if input.ends_with('\n') {
+            input.pop();
+        }
+        Some(input)
+    }
+
+    /// Reads the local input for `day`, downloading it when missing.
+    fn input(&self, day: u8) -> String {
+        self.local_input(day).unwrap_or_else(|| {
+            aoc_main::input::get_input(self.year, day).expect("could not fetch input")
+        })
+    }
+}
+
+/// Registers the days of a year and defines a `main` function running them.
+///
+/// Days are listed as `dayN : generator => part_1, part_2;`, or without the
+/// generator when the solvers take the raw input. Generators return a
+/// `Result<_, ParseError>`, and a rejected input skips the solvers of the day.
+/// Alternative implementations of a part are listed next to it, such as
+/// `part_1, part_1_fast, part_2`. Inputs are read from
+/// `input/<year>/dayN.txt` at the workspace root unless an `inputs "dir";`
+/// line gives a directory relative to the crate.
+#[macro_export]
+macro_rules! main {
+    ( year $year: expr; inputs $inputs: expr; $( $tail: tt )* ) => {
+        $crate::main! { @year $year, Some($inputs); $( $tail )* }
+    };
+    ( year $year: expr; $( $tail: tt )* ) => {
+        $crate::main! { @year $year, None; $( $tail )* }
+    };
+    (
+        @year $year: expr, $inputs: expr;
+        $( $day: ident $( : $generator: ident )? => $( $solver: ident ),+ ; )*
+    ) => {
+        fn main() {
+            let days = [
+                $(
+                    $crate::runner::Day {
+                        name: stringify!($day),
+                        solvers: &[$( stringify!($solver) ),+],
+                        solve: |input, harness| {
+                            $crate::solve_day!(harness, input, $day $( : $generator )? => $( $solver ),+)
+                        },
+                    },
+                )*
+            ];
+
+            $crate::runner::run($crate::runner::Year {
+                year: $year,
+                manifest_dir: env!("CARGO_MANIFEST_DIR"),
+                inputs: $inputs,
+                days: &days,
+            });
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! solve_day {
+    ( $harness: ident, $input: ident, $day: ident => $( $solver: ident ),+ ) => {{
+        $( $harness.solver(stringify!($solver), &mut || $day::$solver($input).to_string()); )+
+    }};
+    ( $harness: ident, $input: ident, $day: ident : $generator: ident => $( $solver: ident ),+ ) => {{
+        let mut parsed = None;
+        $harness.generator(&mut || parsed = Some($day::$generator($input)));
+        let parsed: ::std::result::Result<_, $crate::parsing::ParseError> =
+            parsed.expect("the harness did not run the generator");
+        match parsed {
+            Ok(parsed) => {
+                $( $harness.solver(stringify!($solver), &mut || $day::$solver(&parsed).to_string()); )+
+            }
+            Err(error) => $harness.parse_error(&error),
+        }
+    }};
+}
+
+/// Parses the command line and runs the requested days of `year`.
+pub fn run(year: Year) {
+    let opt = aoc_main::args(year.year)
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["stdin", "file", "bench", "days"])
+                .help("Check every solved day against the answers in answers.toml"),
+        )
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .value_name("FILE")
+                .action(ArgAction::Append)
+                .help("Time every stage and write the medians to a .json or .csv file"),
+        )
+        .arg(
+            Arg::new("save-baseline")
+                .long("save-baseline")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Time every stage and save the report as a baseline [default: bench/baseline.json]"),
+        )
+        .arg(
+            Arg::new("baseline")
+                .long("baseline")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Time every stage and compare the medians with a baseline [default: bench/baseline.json]"),
+        )
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .value_name("PERCENT")
+                .value_parser(value_parser!(f64))
+                .default_value("10")
+                .help("Change of the median flagged when comparing with a baseline"),
+        )
+        .group(
+            ArgGroup::new("timings")
+                .args(["report", "save-baseline", "baseline"])
+                .multiple(true)
+                .conflicts_with_all(["stdin", "file", "bench", "verify"]),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["bench", "verify", "timings"])
+                .help("Time a single run of every stage and print the report as JSON"),
+        )
+        .arg(
+            Arg::new("cross-check")
+                .long("cross-check")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["bench", "verify", "timings", "json"])
+                .help("Time every implementation of each part and fail if their answers differ"),
+        )
+        .arg(
+            Arg::new("param")
+                .long("param")
+                .value_name("NAME=VALUE")
+                .value_parser(params::parse_assignment)
+                .action(ArgAction::Append)
+                .conflicts_with("verify")
+                .help("Override a puzzle parameter, such as the grid size of an example"),
+        )
+        .arg(
+            Arg::new("animate")
+                .long("animate")
+                .value_name("FPS")
+                .value_parser(value_parser!(f64))
+                .num_args(0..=1)
+                .default_missing_value("60")
+                .conflicts_with_all(["bench", "verify", "timings", "json", "cross-check"])
+                .help("Animate the simulations that support it, 0 FPS drawing as fast as possible"),
+        )
+        .arg(
+            Arg::new("render")
+                .long("render")
+                .value_name("DIR")
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(["bench", "verify", "timings", "json", "cross-check", "animate"])
+                .help("Write the frames shown by the simulations to images in DIR"),
+        )
+        .arg(
+            Arg::new("render-format")
+                .long("render-format")
+                .value_name("FORMAT")
+                .value_parser(["gif", "png", "ppm"])
+                .default_value("gif")
+                .requires("render")
+                .help("An animation per solver (gif) or an image per frame (png, ppm)"),
+        )
+        .arg(
+            Arg::new("render-scale")
+                .long("render-scale")
+                .value_name("PIXELS")
+                .value_parser(value_parser!(usize))
+                .default_value("4")
+                .requires("render")
+                .help("Size of the square drawn for each cell of a frame"),
+        )
+        .arg(
+            Arg::new("dot")
+                .long("dot")
+                .value_name("FILE")
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(["bench", "verify", "timings", "json", "cross-check"])
+                .help("Write the circuits and networks of the days that support it to a Graphviz file"),
+        )
+        .arg(
+            Arg::new("list")
+                .long("list")
+                .action(ArgAction::SetTrue)
+                .exclusive(true)
+                .help("List the registered days"),
+        )
+        .get_matches();
+
+    if opt.get_flag("list") {
+        for day in year.days {
+            println!("{}", day.number());
+        }
+        return;
+    }
+
+    for (name, value) in opt
+        .get_many::<(String, String)>("param")
+        .into_iter()
+        .flatten()
+    {
+        params::set(name, value);
+    }
+
+    if opt.get_flag("verify") {
+        let success = verify(&year);
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
+    if opt.get_flag("cross-check") {
+        let days = if opt.contains_id("days") || opt.get_flag("all") {
+            selected_days(&year, &opt)
+        } else {
+            year.days
+                .iter()
+                .filter(|day| day.has_alternatives())
+                .collect()
+        };
+        let success = cross_check(&year, &days, &opt);
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
+    let days = selected_days(&year, &opt);
+    if opt.get_flag("bench") {
+        bench(&year, &days);
+        return;
+    }
+
+    if opt.contains_id("timings") {
+        let success = report(&year, &days, &opt);
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
+    if opt.get_flag("json") {
+        let inputs = days
+            .iter()
+            .map(|day| read_input(&year, day, &opt))
+            .collect();
+        match time_once(&year, &days, inputs) {
+            Ok(report) => print!("{}", report.to_json()),
+            Err((day, error)) => {
+                eprintln!("day {day}: {error}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if days.len() > 1 && (opt.get_flag("stdin") || opt.contains_id("file")) {
+        eprintln!(r"/!\ You are using a personalized output over several days which can");
+        eprintln!(r"    be missleading. If you only intend to run solutions for a");
+        eprintln!(r"    specific day, you can specify it by using the `-d DAY_NUM` flag.");
+    }
+
+    if let Some(&fps) = opt.get_one::<f64>("animate") {
+        viz::start(fps);
+    } else if let Some(dir) = opt.get_one::<PathBuf>("render") {
+        let format: Format = opt
+            .get_one::<String>("render-format")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let scale = *opt.get_one::<usize>("render-scale").unwrap();
+        viz::render(dir.clone(), format, scale);
+    }
+    if let Some(path) = opt.get_one::<PathBuf>("dot") {
+        dot::start(path.clone());
+    }
+
+    let mut printer = Printer::default();
+    for (i, day) in days.iter().enumerate() {
+        if i != 0 {
+            println!();
+        }
+        println!("Day {}", day.number());
+
+        let input = read_input(&year, day, &opt);
+        printer.day = day.number();
+        (day.solve)(&input, &mut printer);
+    }
+    viz::stop();
+    dot::stop();
+    let unused = params::unused();
+    if !unused.is_empty() {
+        eprintln!(
+            r"/!\ Ignoring parameters not used by any day: {}",
+            unused.join(", ")
+        );
+    }
+    if printer.failed {
+        std::process::exit(1);
+    }
+}
+
+/// Reads the input of `day` from stdin, the given file or the saved inputs.
+fn read_input(year: &Year, day: &Day, opt: &ArgMatches) -> String {
+    if opt.get_flag("stdin") {
+        let mut data = String::new();
+        std::io::stdin()
+            .read_to_string(&mut data)
+            .expect("failed to read from stdin");
+        data
+    } else if let Some(path) = opt.get_one::<String>("file") {
+        std::fs::read_to_string(path).expect("failed to read specified file")
+    } else {
+        year.input(day.number())
+    }
+}
+
+/// Returns the days picked by `--day`/`--all`, defaulting to the latest one.
+fn selected_days<'a>(year: &'a Year, opt: &ArgMatches) -> Vec<&'a Day> {
+    if let Some(requested) = opt.get_many::<String>("days") {
+        let requested: Vec<&str> = requested.map(String::as_str).collect();
+        let ignored: Vec<&str> = requested
+            .iter()
+            .filter(|day| !year.days.iter().any(|d| &d.name[3..] == **day))
+            .copied()
+            .collect();
+        if !ignored.is_empty() {
+            eprintln!(r"/!\ Ignoring unimplemented days: {}", ignored.join(", "));
+        }
+
+        year.days
+            .iter()
+            .filter(|day| requested.contains(&&day.name[3..]))
+            .collect()
+    } else if opt.get_flag("all") {
+        year.days.iter().collect()
+    } else {
+        vec![year.days.last().expect("No day implemenations found")]
+    }
+}
+
+/// Runs every stage once and prints its timing and answer, along with its
+/// allocations when they are counted.
+#[derive(Default)]
+struct Printer {
+    /// The day being solved, naming the frames rendered by its solvers.
+    day: u8,
+    /// Whether a generator rejected its input or frames or graphs could not be
+    /// written.
+    failed: bool,
+}
+
+/// Labels a stage with its allocations, if they were measured.
+fn stage_name(name: &str, alloc: Option<AllocStats>) -> String {
+    match alloc {
+        Some(alloc) => format!("{name} [{alloc}]"),
+        None => name.to_string(),
+    }
+}
+
+impl Harness for Printer {
+    fn generator(&mut self, run: &mut dyn FnMut()) {
+        let start = Instant::now();
+        let ((), alloc) = alloc::measure(run);
+        let elapsed = start.elapsed();
+        println!(
+            "  - {}",
+            Line::new(stage_name("generator", alloc)).with_duration(elapsed)
+        );
+    }
+
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String) {
+        viz::begin(&format!("day{}_{name}", self.day));
+        dot::begin(&format!("day{}_{name}", self.day));
+        let start = Instant::now();
+        let (answer, alloc) = alloc::measure(run);
+        let elapsed = start.elapsed();
+        let rendered = viz::finish();
+        let exported = dot::finish();
+        let width = if alloc.is_some() { 70 } else { 30 };
+        println!(
+            "  - {:width$}",
+            Line::new(stage_name(name, alloc))
+                .with_duration(elapsed)
+                .with_state(answer.normal())
+        );
+        match rendered {
+            Ok(Some(rendered)) => println!("    {}", rendered.to_string().dimmed()),
+            Ok(None) => {}
+            Err(err) => {
+                self.failed = true;
+                println!("    {}", format!("could not write frames: {err}").red());
+            }
+        }
+        match exported {
+            Ok(Some(exported)) => println!("    {}", exported.to_string().dimmed()),
+            Ok(None) => {}
+            Err(err) => {
+                self.failed = true;
+                println!("    {}", format!("could not write graphs: {err}").red());
+            }
+        }
+    }
+
+    fn parse_error(&mut self, error: &ParseError) {
+        self.failed = true;
+        println!("    {}", error.to_string().red());
+    }
+}
+
+/// Benchmarks every solver with criterion, running the generator only once.
+struct Bencher<'a> {
+    group: BenchmarkGroup<'a, WallTime>,
+}
+
+impl Harness for Bencher<'_> {
+    fn generator(&mut self, run: &mut dyn FnMut()) {
+        run();
+    }
+
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String) {
+        self.group.bench_function(name, |b| b.iter(&mut *run));
+    }
+}
+
+fn bench(year: &Year, days: &[&Day]) {
+    let mut criterion = Criterion::default().with_output_color(true);
+
+    for day in days {
+        let input = year.input(day.number());
+        let mut bencher = Bencher {
+            group: criterion.benchmark_group(day.name),
+        };
+        (day.solve)(&input, &mut bencher);
+        bencher.group.finish();
+    }
+
+    criterion.final_summary();
+}
+
+/// Minimum time spent sampling a stage for a report.
+const SAMPLE_TIME: Duration = Duration::from_millis(300);
+/// Bounds on the number of samples taken of a stage.
+const MIN_SAMPLES: u128 = 3;
+const MAX_SAMPLES: u128 = 100;
+
+/// Runs every stage repeatedly and summarises its timings.
+#[derive(Default)]
+struct Sampler {
+    stages: Vec<StageTiming>,
+    /// Times a single run of each stage instead.
+    once: bool,
+    error: Option<ParseError>,
+}
+
+impl Sampler {
+    fn sample(&mut self, name: &str, run: &mut dyn FnMut()) {
+        let start = Instant::now();
+        let ((), alloc) = alloc::measure(&mut *run);
+        let mut samples = vec![start.elapsed()];
+
+        let count = SAMPLE_TIME.as_nanos() / samples[0].as_nanos().max(1);
+        let count = if self.once {
+            1
+        } else {
+            count.clamp(MIN_SAMPLES, MAX_SAMPLES)
+        };
+        for _ in 1..count {
+            let start = Instant::now();
+            run();
+            samples.push(start.elapsed());
+        }
+        self.stages.push(StageTiming {
+            alloc,
+            ..StageTiming::from_samples(name, &samples)
+        });
+    }
+}
+
+impl Harness for Sampler {
+    fn generator(&mut self, run: &mut dyn FnMut()) {
+        self.sample("generator", run);
+    }
+
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String) {
+        let mut answer = None;
+        self.sample(name, &mut || {
+            let output = black_box(run());
+            answer.get_or_insert(output);
+        });
+        if let Some(stage) = self.stages.last_mut() {
+            stage.answer = answer;
+        }
+    }
+
+    fn parse_error(&mut self, error: &ParseError) {
+        self.error = Some(error.clone());
+    }
+}
+
+/// Times the stages of `days`, then writes the requested reports and compares
+/// them with the baseline. Returns `false` if a median regressed or a report
+/// could not be read or written.
+fn report(year: &Year, days: &[&Day], opt: &ArgMatches) -> bool {
+    let path_or_default = |id: &str| {
+        opt.get_one::<String>(id).map(|path| match path.as_str() {
+            "" => year.baseline_path(),
+            path => PathBuf::from(path),
+        })
+    };
+    let baseline = match path_or_default("baseline").map(|path| (Report::load(&path), path)) {
+        Some((Ok(baseline), _)) => Some(baseline),
+        Some((Err(err), path)) => {
+            eprintln!("{}: {err}", path.display());
+            return false;
+        }
+        None => None,
+    };
+    let threshold = opt.get_one::<f64>("threshold").unwrap() / 100.;
+
+    let mut success = true;
+    let mut report = Report::default();
+    for day in days {
+        let input = year.input(day.number());
+        let mut sampler = Sampler::default();
+        (day.solve)(&input, &mut sampler);
+        if let Some(error) = sampler.error {
+            success = false;
+            let state = error.to_string().red();
+            println!("  {}", day_line(day.number(), "generator", None, state));
+        }
+        report.days.push(DayReport {
+            year: year.year,
+            day: day.number(),
+            stages: sampler.stages,
+        });
+    }
+
+    let empty = Report::default();
+    let comparisons = report.compare(baseline.as_ref().unwrap_or(&empty), threshold);
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let change = |ratio: f64| format!("{:+.1}%", ratio * 100.);
+        let state = match comparison.change {
+            _ if baseline.is_none() => format!("{} samples", comparison.stage.samples).normal(),
+            Change::New => "new".yellow(),
+            Change::Unchanged(ratio) => change(ratio).normal(),
+            Change::Improved(ratio) => change(ratio).green(),
+            Change::Regressed(ratio) => {
+                regressions += 1;
+                change(ratio).red()
+            }
+        };
+        println!(
+            "  {}",
+            day_line(
+                comparison.day,
+                &stage_name(&comparison.stage.name, comparison.stage.alloc),
+                Some(comparison.stage.median()),
+                state
+            )
+        );
+    }
+
+    let save_baseline = path_or_default("save-baseline");
+    let outputs = opt.get_many::<String>("report").into_iter().flatten();
+    for path in outputs.map(PathBuf::from).chain(save_baseline) {
+        match report.save(&path) {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(err) => {
+                eprintln!("{}: {err}", path.display());
+                success = false;
+            }
+        }
+    }
+
+    if baseline.is_some() {
+        println!();
+        println!(
+            "{} stages regressed by more than {}%",
+            regressions.to_string().red(),
+            threshold * 100.
+        );
+    }
+    success && regressions == 0
+}
+
+/// Samples every implementation of each part, keeping their answers.
+#[derive(Default)]
+struct CrossChecker {
+    sampler: Sampler,
+    answers: Vec<String>,
+    failed: bool,
+}
+
+impl Harness for CrossChecker {
+    fn generator(&mut self, run: &mut dyn FnMut()) {
+        run();
+    }
+
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String) {
+        let mut answer = String::new();
+        self.sampler.sample(name, &mut || answer = black_box(run()));
+        self.answers.push(answer);
+    }
+
+    fn parse_error(&mut self, error: &ParseError) {
+        self.failed = true;
+        println!(
+            "  - {:40}",
+            Line::new("generator").with_state(error.to_string().red())
+        );
+    }
+}
+
+/// Runs every implementation of the parts of `days` on the same input and
+/// prints their median timings side by side, relative to the fastest one.
+/// Returns `false` if two implementations of a part gave different answers.
+fn cross_check(year: &Year, days: &[&Day], opt: &ArgMatches) -> bool {
+    let mut success = true;
+    for (i, day) in days.iter().enumerate() {
+        if i != 0 {
+            println!();
+        }
+        println!("Day {}", day.number());
+
+        let input = read_input(year, day, opt);
+        let mut checker = CrossChecker::default();
+        (day.solve)(&input, &mut checker);
+        success &= !checker.failed;
+        let solvers: Vec<(&StageTiming, &String)> = checker
+            .sampler
+            .stages
+            .iter()
+            .zip(&checker.answers)
+            .collect();
+
+        let mut parts: Vec<u8> = solvers.iter().map(|(s, _)| part_of(&s.name)).collect();
+        parts.sort_unstable();
+        parts.dedup();
+        for part in parts {
+            let implementations: Vec<_> = solvers
+                .iter()
+                .filter(|(stage, _)| part_of(&stage.name) == part)
+                .collect();
+            let (_, reference) = implementations[0];
+            let fastest = implementations
+                .iter()
+                .map(|(stage, _)| stage.median_ns)
+                .min()
+                .unwrap()
+                .max(1);
+
+            for (stage, answer) in &implementations {
+                let ratio = format!("{:.2}x", stage.median_ns as f64 / fastest as f64);
+                let state = if answer == reference {
+                    format!("{answer} ({ratio})").normal()
+                } else {
+                    success = false;
+                    format!("MISMATCH: {answer}, expected {reference} ({ratio})").red()
+                };
+                println!(
+                    "  - {:40}",
+                    Line::new(stage.name.as_str())
+                        .with_duration(stage.median())
+                        .with_state(state)
+                );
+            }
+        }
+    }
+    success
+}
+
+/// Times a single run of every stage of `days`, as `aoc run-all` collects
+/// through `--json`. Fails on the first input that could not be parsed.
+fn time_once(year: &Year, days: &[&Day], inputs: Vec<String>) -> Result<Report, (u8, ParseError)> {
+    let mut report = Report::default();
+    for (day, input) in days.iter().zip(inputs) {
+        let mut sampler = Sampler {
+            once: true,
+            ..Default::default()
+        };
+        (day.solve)(&input, &mut sampler);
+        if let Some(error) = sampler.error {
+            return Err((day.number(), error));
+        }
+        report.days.push(DayReport {
+            year: year.year,
+            day: day.number(),
+            stages: sampler.stages,
+        });
+    }
+    Ok(report)
+}
+
+/// Checks every solver against the recorded answers, printing one line each.
+struct Verifier<'a> {
+    day: u8,
+    answers: &'a Answers,
+    summary: &'a mut Summary,
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+impl Harness for Verifier<'_> {
+    fn generator(&mut self, run: &mut dyn FnMut()) {
+        run();
+    }
+
+    fn parse_error(&mut self, error: &ParseError) {
+        self.summary.failed += 1;
+        let state = format!("FAIL: {error}").red();
+        println!("  {}", day_line(self.day, "generator", None, state));
+    }
+
+    fn solver(&mut self, name: &'static str, run: &mut dyn FnMut() -> String) {
+        let start = Instant::now();
+        let answer = run();
+        let elapsed = start.elapsed();
+
+        let verdict = self.answers.check(self.day, part_of(name), &answer);
+        let state = match verdict {
+            Verdict::Pass => {
+                self.summary.passed += 1;
+                "pass".green()
+            }
+            Verdict::Fail { expected, actual } => {
+                self.summary.failed += 1;
+                format!("FAIL: expected {expected}, got {actual}").red()
+            }
+            Verdict::Missing => {
+                self.summary.missing += 1;
+                "missing answer".yellow()
+            }
+        };
+        println!("  {}", day_line(self.day, name, Some(elapsed), state));
+    }
+}
+
+fn day_line(day: u8, name: &str, elapsed: Option<Duration>, state: ColoredString) -> String {
+    let line = Line::new(format!("day{day:<2} {name}"));
+    let line = match elapsed {
+        Some(elapsed) => line.with_duration(elapsed),
+        None => line,
+    };
+    let width = if alloc::enabled() { 80 } else { 40 };
+    format!("{:width$}", line.with_state(state))
+}
+
+/// Runs every registered day on its local input and compares the answers with
+/// `answers.toml`. Returns `false` if any answer differs or a day panicked.
+fn verify(year: &Year) -> bool {
+    let answers = match Answers::load(&year.answers_path(), year.year) {
+        Ok(answers) if answers.year == year.year => answers,
+        Ok(answers) => {
+            eprintln!(
+                "{} lists answers for {} instead of {}",
+                year.answers_path().display(),
+                answers.year,
+                year.year
+            );
+            return false;
+        }
+        Err(err) => {
+            eprintln!("{}: {err}", year.answers_path().display());
+            return false;
+        }
+    };
+
+    println!(
+        "Verifying {} against {}",
+        year.year,
+        year.answers_path().display()
+    );
+    let mut summary = Summary::default();
+    for day in year.days {
+        let Some(input) = year.local_input(day.number()) else {
+            summary.missing += 1;
+            println!(
+                "  {}",
+                day_line(day.number(), "input", None, "missing input".yellow())
+            );
+            continue;
+        };
+
+        let mut verifier = Verifier {
+            day: day.number(),
+            answers: &answers,
+            summary: &mut summary,
+        };
+        if panic::catch_unwind(AssertUnwindSafe(|| (day.solve)(&input, &mut verifier))).is_err() {
+            summary.failed += 1;
+            println!(
+                "  {}",
+                day_line(day.number(), "solver", None, "FAIL: panicked".red())
+            );
+        }
+    }
+
+    let Summary {
+        passed,
+        failed,
+        missing,
+    } = summary;
+    println!();
+    println!(
+        "{} passed, {} failed, {} missing",
+        passed.to_string().green(),
+        failed.to_string().red(),
+        missing.to_string().yellow()
+    );
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(solvers: &'static [&'static str]) -> Day {
+        Day {
+            name: "day3",
+            solvers,
+            solve: |_, _| {},
+        }
+    }
+
+    #[test]
+    fn finds_alternative_implementations() {
+        assert!(!day(&["part_1", "part_2"]).has_alternatives());
+        assert!(day(&["part_1", "part_1_solution_2", "part_2"]).has_alternatives());
+        assert!(day(&["part_1", "part_2", "part_2_slow"]).has_alternatives());
+        assert!(!day(&["solution"]).has_alternatives());
+    }
+}
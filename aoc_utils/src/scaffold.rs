@@ -0,0 +1,409 @@
+//! Creation of new days, behind `aoc new <year> <day>`.
+//!
+//! A new day gets its module from [`DAY_TEMPLATE`], is declared and registered
+//! in the year's `main.rs` and gets an empty input file, which the runner
+//! treats as missing until the real input is pasted in.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The module written for every new day.
+pub const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// An error raised while scaffolding a day.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Io(PathBuf, io::Error),
+    /// No crate exists for the year.
+    UnknownYear(u16),
+    /// The day module already exists.
+    DayExists(PathBuf),
+    /// The day is already declared or registered in `main.rs`.
+    AlreadyRegistered(u8),
+    /// `main.rs` does not follow the expected layout.
+    InvalidRegistry {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            ScaffoldError::UnknownYear(year) => write!(f, "there is no crate for {year}"),
+            ScaffoldError::DayExists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::AlreadyRegistered(day) => {
+                write!(f, "day{day} is already registered in main.rs")
+            }
+            ScaffoldError::InvalidRegistry { line, reason } => {
+                write!(f, "main.rs:{line}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// A registered day in the `main!` block.
+struct Entry {
+    line: usize,
+    day: u8,
+    /// Width the day name is padded to before ` : generator`, if aligned.
+    padding: Option<usize>,
+    has_generator: bool,
+}
+
+/// The module declarations and `main!` registry of a year's `main.rs`.
+pub struct Registry {
+    lines: Vec<String>,
+    mods: Vec<(usize, u8)>,
+    entries: Vec<Entry>,
+    /// Index of the line closing the `main!` block.
+    end: usize,
+    /// Directory given by the `inputs "dir";` line, if any.
+    pub inputs: Option<String>,
+}
+
+fn parse_day(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+impl std::str::FromStr for Registry {
+    type Err = ScaffoldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<String> = s.lines().map(String::from).collect();
+        let invalid = |line: usize, reason: &str| ScaffoldError::InvalidRegistry {
+            line: line + 1,
+            reason: reason.to_string(),
+        };
+
+        let mods = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let name = line.trim().strip_prefix("mod ")?.strip_suffix(';')?;
+                Some((i, parse_day(name)?))
+            })
+            .collect();
+
+        let start = lines
+            .iter()
+            .position(|line| line.starts_with("aoc_utils::main!") && line.ends_with('{'))
+            .ok_or_else(|| invalid(lines.len(), "no `aoc_utils::main! {` block"))?;
+        let end = (start + 1..lines.len())
+            .find(|&i| lines[i] == "}")
+            .ok_or_else(|| invalid(start, "unclosed `main!` block"))?;
+
+        let mut entries = Vec::new();
+        let mut inputs = None;
+        for (i, line) in lines.iter().enumerate().take(end).skip(start + 1) {
+            let statement = line.trim();
+            if statement.is_empty() || statement.starts_with("//") {
+                continue;
+            }
+            let statement = statement
+                .strip_suffix(';')
+                .ok_or_else(|| invalid(i, "expected `;` at the end of the line"))?;
+
+            if statement.starts_with("year ") {
+                continue;
+            }
+            if let Some(dir) = statement.strip_prefix("inputs ") {
+                let dir = dir
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|dir| dir.strip_suffix('"'))
+                    .ok_or_else(|| invalid(i, "expected a quoted inputs directory"))?;
+                inputs = Some(dir.to_string());
+                continue;
+            }
+
+            let (stages, _) = statement
+                .split_once("=>")
+                .ok_or_else(|| invalid(i, "expected `dayN : generator => solvers`"))?;
+            let (name, generator) = match stages.split_once(':') {
+                Some((name, generator)) => (name, Some(generator)),
+                None => (stages, None),
+            };
+            let day = parse_day(name.trim()).ok_or_else(|| invalid(i, "expected a day name"))?;
+            let padded = generator.is_some() && name.trim_start().ends_with("  ");
+            entries.push(Entry {
+                line: i,
+                day,
+                padding: padded.then(|| name.trim_start().len() - 1),
+                has_generator: generator.is_some(),
+            });
+        }
+
+        Ok(Registry {
+            lines,
+            mods,
+            entries,
+            end,
+            inputs,
+        })
+    }
+}
+
+impl Registry {
+    /// Returns where the runner reads the input of `day`, below the
+    /// workspace `root`.
+    pub fn input_path(&self, root: &Path, year: u16, day: u8) -> PathBuf {
+        let dir = match &self.inputs {
+            Some(dir) => root.join(format!("aoc{:02}", year % 100)).join(dir),
+            None => root.join("input").join(year.to_string()),
+        };
+        dir.join(format!("day{day}.txt"))
+    }
+
+    /// Returns whether `day` is declared or registered.
+    pub fn contains(&self, day: u8) -> bool {
+        self.mods.iter().any(|&(_, d)| d == day) || self.entries.iter().any(|e| e.day == day)
+    }
+
+    /// Declares `dayN` and registers it with a generator and both parts,
+    /// keeping both lists sorted and following the alignment of the registry.
+    pub fn add_day(&mut self, day: u8) -> Result<(), ScaffoldError> {
+        if self.contains(day) {
+            return Err(ScaffoldError::AlreadyRegistered(day));
+        }
+
+        let name = format!("day{day}");
+        let indent = self
+            .entries
+            .first()
+            .map(|entry| {
+                let line = &self.lines[entry.line];
+                &line[..line.len() - line.trim_start().len()]
+            })
+            .unwrap_or("    ");
+        let padding = self
+            .entries
+            .iter()
+            .filter(|entry| entry.has_generator)
+            .find_map(|entry| entry.padding)
+            .unwrap_or(0);
+        let entry = format!("{indent}{name:<padding$} : generator => part_1, part_2;");
+        let entry_line = self
+            .entries
+            .iter()
+            .find(|entry| entry.day > day)
+            .map_or(self.end, |entry| entry.line);
+
+        // The registry follows the declarations, so insert it first to keep
+        // the declaration indices valid.
+        self.lines.insert(entry_line, entry);
+        let declaration = format!("mod {name};");
+        match self.mods.iter().find(|&&(_, d)| d > day) {
+            Some(&(line, _)) => self.lines.insert(line, declaration),
+            None => match self.mods.last() {
+                Some(&(line, _)) => self.lines.insert(line + 1, declaration),
+                None => {
+                    self.lines.insert(0, declaration);
+                    self.lines.insert(1, String::new());
+                }
+            },
+        }
+
+        // Reparsing is cheap and keeps the line indices in sync.
+        *self = self.to_string().parse()?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.lines.iter().try_for_each(|line| writeln!(f, "{line}"))
+    }
+}
+
+/// The files written by [`new_day`].
+#[derive(Debug)]
+pub struct Scaffold {
+    pub module: PathBuf,
+    pub main: PathBuf,
+    /// The input placeholder, unless an input was already saved.
+    pub input: Option<PathBuf>,
+}
+
+/// Returns the module of a day below the workspace `root`.
+pub fn module_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(format!("aoc{:02}/src/day{day}.rs", year % 100))
+}
+
+/// Creates `dayN.rs` in the crate of `year` below the workspace `root`,
+/// registers it in `main.rs` and adds an empty input file.
+///
+/// Nothing is written if the module exists or the day is already registered.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Scaffold, ScaffoldError> {
+    let crate_dir = root.join(format!("aoc{:02}", year % 100));
+    let main = crate_dir.join("src/main.rs");
+    let module = module_path(root, year, day);
+    if !main.is_file() {
+        return Err(ScaffoldError::UnknownYear(year));
+    }
+    if module.exists() {
+        return Err(ScaffoldError::DayExists(module));
+    }
+
+    let source = fs::read_to_string(&main).map_err(|err| ScaffoldError::Io(main.clone(), err))?;
+    let mut registry: Registry = source.parse()?;
+    registry.add_day(day)?;
+
+    let input = registry.input_path(root, year, day);
+    let input_dir = input.parent().unwrap().to_path_buf();
+
+    fs::write(&module, DAY_TEMPLATE).map_err(|err| ScaffoldError::Io(module.clone(), err))?;
+    if let Err(err) = fs::write(&main, registry.to_string()) {
+        // Leave no orphaned module behind, so that the day can be added again
+        let _ = fs::remove_file(&module);
+        return Err(ScaffoldError::Io(main, err));
+    }
+    let input = if input.exists() {
+        None
+    } else {
+        fs::create_dir_all(&input_dir).map_err(|err| ScaffoldError::Io(input_dir, err))?;
+        fs::write(&input, "").map_err(|err| ScaffoldError::Io(input.clone(), err))?;
+        Some(input)
+    };
+
+    Ok(Scaffold {
+        module,
+        main,
+        input,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALIGNED: &str = "\
+mod day1;
+mod day2;
+mod day4;
+
+aoc_utils::main! {
+    year 2022;
+    day1  : generator => part_1, part_2;
+    day2              => part_1, part_2;
+    day4  : generator => part_1, part_2;
+}
+";
+
+    const UNALIGNED: &str = "\
+mod day1;
+mod day9;
+
+aoc_utils::main! {
+    year 2021;
+    day1 : generator => part_1, part_2;
+    day9 : generator => part_1, part_2;
+}
+";
+
+    const INPUTS: &str = "\
+mod day1;
+
+aoc_utils::main! {
+    year 2015;
+    inputs \"data\";
+    day1              => part_1, part_2;
+}
+";
+
+    #[test]
+    fn inserts_sorted_and_aligned() {
+        let mut registry: Registry = ALIGNED.parse().unwrap();
+        registry.add_day(3).unwrap();
+        registry.add_day(12).unwrap();
+        assert_eq!(
+            registry.to_string(),
+            "\
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day12;
+
+aoc_utils::main! {
+    year 2022;
+    day1  : generator => part_1, part_2;
+    day2              => part_1, part_2;
+    day3  : generator => part_1, part_2;
+    day4  : generator => part_1, part_2;
+    day12 : generator => part_1, part_2;
+}
+"
+        );
+    }
+
+    #[test]
+    fn keeps_unaligned_registries_unaligned() {
+        let mut registry: Registry = UNALIGNED.parse().unwrap();
+        registry.add_day(10).unwrap();
+        assert!(registry
+            .to_string()
+            .contains("    day9 : generator => part_1, part_2;\n    day10 : generator"));
+    }
+
+    #[test]
+    fn reads_inputs_directory() {
+        let mut registry: Registry = INPUTS.parse().unwrap();
+        assert_eq!(registry.inputs.as_deref(), Some("data"));
+        registry.add_day(2).unwrap();
+        assert!(registry.to_string().ends_with(
+            "    day1              => part_1, part_2;\n    day2 : generator => part_1, part_2;\n}\n"
+        ));
+    }
+
+    #[test]
+    fn refuses_registered_days() {
+        let mut registry: Registry = ALIGNED.parse().unwrap();
+        assert!(matches!(
+            registry.add_day(2),
+            Err(ScaffoldError::AlreadyRegistered(2))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_layouts() {
+        assert!(matches!(
+            "fn main() {}\n".parse::<Registry>(),
+            Err(ScaffoldError::InvalidRegistry { .. })
+        ));
+        assert!(matches!(
+            "aoc_utils::main! {\n    year 2022;\n    day1 => part_1\n}\n".parse::<Registry>(),
+            Err(ScaffoldError::InvalidRegistry { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn creates_day_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc22/src")).unwrap();
+        fs::write(root.join("aoc22/src/main.rs"), ALIGNED).unwrap();
+
+        let scaffold = new_day(&root, 2022, 3).unwrap();
+        assert_eq!(fs::read_to_string(&scaffold.module).unwrap(), DAY_TEMPLATE);
+        assert!(fs::read_to_string(&scaffold.main)
+            .unwrap()
+            .contains("mod day3;"));
+        assert_eq!(scaffold.input, Some(root.join("input/2022/day3.txt")));
+
+        assert!(matches!(
+            new_day(&root, 2022, 3),
+            Err(ScaffoldError::DayExists(_))
+        ));
+        assert!(matches!(
+            new_day(&root, 2023, 1),
+            Err(ScaffoldError::UnknownYear(2023))
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
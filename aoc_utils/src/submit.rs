@@ -0,0 +1,742 @@
+//! Submission of answers, behind `aoc submit <year> <day> <part> [answer]`.
+//!
+//! Answers are posted to the answer form of the puzzle with the session token
+//! used to download the inputs, and the page sent back is read into an
+//! [`Outcome`]. Every verdict is recorded in `input/<year>/attempts.toml`, so
+//! answers already known to be wrong, or to be out of the bounds given by a
+//! previous "too high" or "too low", are refused without being sent again.
+//!
+//! [`MockServer`] answers like the real site from a list of correct answers,
+//! for the tests.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use attohttpc::header::{CONTENT_TYPE, COOKIE, USER_AGENT};
+use serde::{Deserialize, Serialize};
+
+/// The site the answers are posted to by default.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT_VALUE: &str = concat!("aoc_utils/", env!("CARGO_PKG_VERSION"));
+
+/// An answer to a part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub value: String,
+}
+
+/// The verdict of the site on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, this one was not checked.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part is already solved, or the previous part is not.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// The name of the outcome in the attempts file, for the outcomes that
+    /// say something about the answer.
+    fn recorded(self) -> Option<&'static str> {
+        match self {
+            Outcome::Correct => Some("correct"),
+            Outcome::Wrong => Some("wrong"),
+            Outcome::TooHigh => Some("too-high"),
+            Outcome::TooLow => Some("too-low"),
+            Outcome::RateLimited { .. } | Outcome::WrongLevel => None,
+        }
+    }
+
+    fn from_recorded(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Outcome::Correct),
+            "wrong" => Some(Outcome::Wrong),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::Wrong => write!(f, "that's not the right answer"),
+            Outcome::TooHigh => write!(f, "that's not the right answer, it is too high"),
+            Outcome::TooLow => write!(f, "that's not the right answer, it is too low"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "answered too recently, wait {}s", wait.as_secs())
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "answered too recently"),
+            Outcome::WrongLevel => write!(f, "this part is solved or not unlocked yet"),
+        }
+    }
+}
+
+/// An error raised while submitting an answer.
+#[derive(Debug)]
+pub enum SubmitError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, String),
+    Http(attohttpc::Error),
+    /// The site answered with an error status.
+    Status(u16),
+    /// The page sent back holds no known verdict.
+    UnexpectedResponse,
+    /// The attempts file belongs to another year.
+    WrongYear {
+        expected: u16,
+        found: u16,
+    },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            SubmitError::Toml(path, err) => write!(f, "{}: {err}", path.display()),
+            SubmitError::Http(err) => write!(f, "could not submit: {err}"),
+            SubmitError::Status(status) => {
+                write!(
+                    f,
+                    "the site answered with status {status}, is the session valid?"
+                )
+            }
+            SubmitError::UnexpectedResponse => {
+                write!(f, "could not find a verdict in the response")
+            }
+            SubmitError::WrongYear { expected, found } => {
+                write!(f, "the attempts file is for {found}, not {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Reads the verdict from the page sent back by the answer form.
+pub fn parse_response(html: &str) -> Option<Outcome> {
+    let outcome = if html.contains("That's the right answer") {
+        Outcome::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if html.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Outcome::RateLimited {
+            wait: parse_wait(html),
+        }
+    } else if html.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        return None;
+    };
+    Some(outcome)
+}
+
+/// Reads the wait of a rate limited answer, given as `You have 4m 36s left
+/// to wait`.
+fn parse_wait(html: &str) -> Option<Duration> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+    html[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (value, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let unit = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(value.parse::<u64>().ok()? * unit)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Percent-encodes a value of a form body.
+fn encode_form(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn decode_form(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' if rest.len() >= 2 => {
+                let hex = std::str::from_utf8(&rest[..2]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(decoded) => {
+                        bytes.push(decoded);
+                        rest = &rest[2..];
+                    }
+                    Err(_) => bytes.push(b'%'),
+                }
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Returns the session token used to download the inputs, from `AOC_SESSION`
+/// or the token file shared with the runner.
+pub fn session_token() -> Option<String> {
+    let token = std::env::var("AOC_SESSION").ok().or_else(|| {
+        let path = dirs::config_dir()
+            .map(|dir| dir.join("aoc/token.txt"))
+            .unwrap_or_else(|| ".token".into());
+        fs::read_to_string(path).ok()
+    })?;
+    Some(token.trim().to_string()).filter(|token| !token.is_empty())
+}
+
+/// Posts answers to the site at a base URL.
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Posts `answer` and reads the verdict from the page sent back.
+    pub fn submit(&self, answer: &Answer) -> Result<Outcome, SubmitError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url, answer.year, answer.day
+        );
+        let body = format!(
+            "level={}&answer={}",
+            answer.part,
+            encode_form(answer.value.trim())
+        );
+        let response = attohttpc::post(url)
+            .header(COOKIE, format!("session={}", self.session))
+            .header(USER_AGENT, USER_AGENT_VALUE)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .timeout(Duration::from_secs(30))
+            .text(body)
+            .send()
+            .map_err(SubmitError::Http)?;
+        if !response.is_success() {
+            return Err(SubmitError::Status(response.status().as_u16()));
+        }
+        let page = response.text().map_err(SubmitError::Http)?;
+        parse_response(&page).ok_or(SubmitError::UnexpectedResponse)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AttemptsFile {
+    year: u16,
+    #[serde(default)]
+    attempt: Vec<Attempt>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Attempt {
+    day: u8,
+    part: u8,
+    answer: String,
+    outcome: String,
+}
+
+/// The answers submitted for one year and their verdicts.
+pub struct Attempts {
+    path: PathBuf,
+    file: AttemptsFile,
+}
+
+impl Attempts {
+    /// Returns where the attempts of `year` are recorded below the workspace
+    /// `root`, next to the downloaded inputs.
+    pub fn path(root: &Path, year: u16) -> PathBuf {
+        root.join("input")
+            .join(year.to_string())
+            .join("attempts.toml")
+    }
+
+    /// Reads the attempts recorded at `path`, none if it does not exist.
+    pub fn load(path: &Path, year: u16) -> Result<Self, SubmitError> {
+        let file = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|err| SubmitError::Toml(path.to_path_buf(), err.to_string()))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => AttemptsFile {
+                year,
+                attempt: Vec::new(),
+            },
+            Err(err) => return Err(SubmitError::Io(path.to_path_buf(), err)),
+        };
+        if file.year != year {
+            return Err(SubmitError::WrongYear {
+                expected: year,
+                found: file.year,
+            });
+        }
+        Ok(Attempts {
+            path: path.to_path_buf(),
+            file,
+        })
+    }
+
+    /// Returns the verdict the recorded attempts already give on `answer`.
+    ///
+    /// Besides answers submitted before, any other answer than a correct one
+    /// is wrong, and numbers beyond an answer too high or too low are too.
+    pub fn known(&self, answer: &Answer) -> Option<Outcome> {
+        let value = answer.value.trim();
+        let attempts = || {
+            self.file
+                .attempt
+                .iter()
+                .filter(|a| (a.day, a.part) == (answer.day, answer.part))
+                .filter_map(|a| Some((a.answer.as_str(), Outcome::from_recorded(&a.outcome)?)))
+        };
+        if let Some((_, outcome)) = attempts().find(|&(answer, _)| answer == value) {
+            return Some(outcome);
+        }
+        if attempts().any(|(_, outcome)| outcome == Outcome::Correct) {
+            return Some(Outcome::Wrong);
+        }
+
+        let number: i128 = value.parse().ok()?;
+        attempts().find_map(|(answer, outcome)| {
+            let bound: i128 = answer.parse().ok()?;
+            match outcome {
+                Outcome::TooHigh if number >= bound => Some(Outcome::TooHigh),
+                Outcome::TooLow if number <= bound => Some(Outcome::TooLow),
+                _ => None,
+            }
+        })
+    }
+
+    /// Records the verdict on `answer`, if it says something about it.
+    pub fn record(&mut self, answer: &Answer, outcome: Outcome) -> Result<(), SubmitError> {
+        let Some(name) = outcome.recorded() else {
+            return Ok(());
+        };
+        self.file.attempt.push(Attempt {
+            day: answer.day,
+            part: answer.part,
+            answer: answer.value.trim().to_string(),
+            outcome: name.to_string(),
+        });
+
+        let content = toml::to_string(&self.file)
+            .map_err(|err| SubmitError::Toml(self.path.clone(), err.to_string()))?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|err| SubmitError::Io(dir.to_path_buf(), err))?;
+        }
+        fs::write(&self.path, content).map_err(|err| SubmitError::Io(self.path.clone(), err))
+    }
+}
+
+/// The verdict on a submitted answer, and whether it was already known.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: Outcome,
+    pub known: bool,
+}
+
+/// Submits `answer` unless `attempts` already tell its verdict, and records
+/// the verdict of the site.
+pub fn submit(
+    client: &Client,
+    attempts: &mut Attempts,
+    answer: &Answer,
+) -> Result<Submission, SubmitError> {
+    if let Some(outcome) = attempts.known(answer) {
+        return Ok(Submission {
+            outcome,
+            known: true,
+        });
+    }
+    let outcome = client.submit(answer)?;
+    attempts.record(answer, outcome)?;
+    Ok(Submission {
+        outcome,
+        known: false,
+    })
+}
+
+/// The state of a [`MockServer`], shared with its thread.
+#[derive(Default)]
+struct MockState {
+    answers: HashMap<(u16, u8, u8), String>,
+    solved: Vec<(u16, u8, u8)>,
+    /// Whether the last answer was wrong, rate limiting the next one.
+    penalty: bool,
+    requests: usize,
+}
+
+impl MockState {
+    /// Answers a request like the site, with a status and a page.
+    fn respond(&mut self, path: &str, cookie: Option<&str>, body: &str) -> (u16, String) {
+        self.requests += 1;
+        let route = path
+            .strip_prefix('/')
+            .and_then(|path| path.strip_suffix("/answer"))
+            .and_then(|path| path.split_once("/day/"))
+            .and_then(|(year, day)| Some((year.parse::<u16>().ok()?, day.parse::<u8>().ok()?)));
+        let Some((year, day)) = route else {
+            return (404, "404 Not Found".to_string());
+        };
+        if !cookie.is_some_and(|cookie| cookie.starts_with("session=") && cookie.len() > 8) {
+            return (
+                400,
+                "Puzzle inputs differ by user.  Please log in.".to_string(),
+            );
+        }
+        let form: HashMap<&str, String> = body
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key, decode_form(value)))
+            .collect();
+        let (Some(part), Some(value)) = (
+            form.get("level").and_then(|level| level.parse().ok()),
+            form.get("answer"),
+        ) else {
+            return (400, "Bad Request".to_string());
+        };
+
+        let key = (year, day, part);
+        let message = if self.penalty {
+            self.penalty = false;
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 59s left to wait."
+                .to_string()
+        } else if self.solved.contains(&key) || !self.answers.contains_key(&key) {
+            "You don't seem to be solving the right level.  Did you already complete it?"
+                .to_string()
+        } else if &self.answers[&key] == value {
+            self.solved.push(key);
+            "That's the right answer!  You are one gold star closer to saving Christmas."
+                .to_string()
+        } else {
+            self.penalty = true;
+            let hint = match (value.parse::<i128>(), self.answers[&key].parse::<i128>()) {
+                (Ok(value), Ok(expected)) if value > expected => "your answer is too high.  ",
+                (Ok(value), Ok(expected)) if value < expected => "your answer is too low.  ",
+                _ => "",
+            };
+            format!(
+                "That's not the right answer; {hint}If you're stuck, make sure you're \
+                 using the full input data."
+            )
+        };
+        (
+            200,
+            format!(
+                "<!DOCTYPE html>\n<html><body><main>\n\
+                 <article><p>{message}</p></article>\n</main></body></html>\n"
+            ),
+        )
+    }
+}
+
+/// A local stand-in for the answer form of the site, checking answers against
+/// a list of correct ones.
+///
+/// A wrong answer rate limits the next submission, and a solved part answers
+/// that it is not the right level, as the site does.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts serving the correct `answers`, given as `(year, day, part,
+    /// answer)`, on a free local port.
+    pub fn start(answers: &[(u16, u8, u8, &str)]) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState {
+            answers: answers
+                .iter()
+                .map(|&(year, day, part, answer)| ((year, day, part), answer.to_string()))
+                .collect(),
+            ..Default::default()
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let state = Arc::clone(&state);
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A broken request only fails the client sending it.
+                        let _ = serve(stream, &state);
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer {
+            address,
+            state,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// The base URL to give to the [`Client`].
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// The number of requests received so far.
+    pub fn requests(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wakes the thread up from waiting for a connection.
+        let _ = TcpStream::connect(self.address);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Reads a request from `stream` and writes the response of the mock.
+fn serve(mut stream: TcpStream, state: &Mutex<MockState>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut length = 0;
+    let mut cookie = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => length = value.trim().parse().unwrap_or(0),
+                "cookie" => cookie = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (status, page) =
+        state
+            .lock()
+            .unwrap()
+            .respond(&path, cookie.as_deref(), &String::from_utf8_lossy(&body));
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{page}",
+        page.len()
+    )?;
+    stream.flush()?;
+    stream.shutdown(Shutdown::Write)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(day: u8, part: u8, value: &str) -> Answer {
+        Answer {
+            year: 2022,
+            day,
+            part,
+            value: value.to_string(),
+        }
+    }
+
+    fn attempts(name: &str) -> Attempts {
+        let path =
+            std::env::temp_dir().join(format!("aoc-attempts-{name}-{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        Attempts::load(&path, 2022).unwrap()
+    }
+
+    #[test]
+    fn parses_responses() {
+        let page = |message: &str| format!("<main><article><p>{message}</p></article></main>");
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are one gold star closer."
+            )),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low.  If you're stuck..."
+            )),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck...")),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 4m 36s left to wait."
+            )),
+            Some(Outcome::RateLimited {
+                wait: Some(Duration::from_secs(276))
+            })
+        );
+        assert_eq!(parse_response("<html></html>"), None);
+    }
+
+    #[test]
+    fn encodes_forms() {
+        let value = "##..#\n#..# a&b=c%";
+        assert_eq!(encode_form("6,1"), "6%2C1");
+        assert_eq!(decode_form(&encode_form(value)), value);
+    }
+
+    #[test]
+    fn submits_to_the_mock_server() {
+        let server =
+            MockServer::start(&[(2022, 10, 1, "13140"), (2022, 10, 2, "EHZFZHCZ")]).unwrap();
+        let client = Client::new(&server.url(), "secret");
+
+        assert_eq!(
+            client.submit(&answer(10, 1, "14000")).unwrap(),
+            Outcome::TooHigh
+        );
+        assert!(matches!(
+            client.submit(&answer(10, 1, "13140")).unwrap(),
+            Outcome::RateLimited { wait: Some(_) }
+        ));
+        assert_eq!(
+            client.submit(&answer(10, 1, "13140")).unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            client.submit(&answer(10, 1, "13140")).unwrap(),
+            Outcome::WrongLevel
+        );
+        assert_eq!(
+            client.submit(&answer(10, 2, "EHZFZHCZ\n")).unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(server.requests(), 5);
+
+        let anonymous = Client::new(&server.url(), "");
+        assert!(matches!(
+            anonymous.submit(&answer(10, 1, "1")),
+            Err(SubmitError::Status(400))
+        ));
+    }
+
+    #[test]
+    fn does_not_resubmit_known_answers() {
+        let server = MockServer::start(&[(2022, 1, 1, "24000")]).unwrap();
+        let client = Client::new(&server.url(), "secret");
+        let mut attempts = attempts("known");
+
+        let submitted = submit(&client, &mut attempts, &answer(1, 1, "30000")).unwrap();
+        assert_eq!(
+            submitted,
+            Submission {
+                outcome: Outcome::TooHigh,
+                known: false
+            }
+        );
+        for value in ["30000", "31000"] {
+            let known = submit(&client, &mut attempts, &answer(1, 1, value)).unwrap();
+            assert_eq!(
+                known,
+                Submission {
+                    outcome: Outcome::TooHigh,
+                    known: true
+                }
+            );
+        }
+        assert_eq!(server.requests(), 1);
+
+        // The rate limit is not recorded, so the answer is sent again.
+        let limited = submit(&client, &mut attempts, &answer(1, 1, "24000")).unwrap();
+        assert!(matches!(limited.outcome, Outcome::RateLimited { .. }));
+        let correct = submit(&client, &mut attempts, &answer(1, 1, "24000")).unwrap();
+        assert_eq!(correct.outcome, Outcome::Correct);
+
+        let reloaded = Attempts::load(&attempts.path, 2022).unwrap();
+        assert_eq!(
+            reloaded.known(&answer(1, 1, "24000")),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(reloaded.known(&answer(1, 1, "100")), Some(Outcome::Wrong));
+        assert_eq!(reloaded.known(&answer(1, 2, "100")), None);
+        assert!(matches!(
+            Attempts::load(&attempts.path, 2023),
+            Err(SubmitError::WrongYear {
+                expected: 2023,
+                found: 2022
+            })
+        ));
+        fs::remove_file(&attempts.path).unwrap();
+    }
+}
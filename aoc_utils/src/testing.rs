@@ -0,0 +1,257 @@
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Generates one test per example and part of a day.
+///
+/// Each example is written as `name: (input, part_1 = expected, ...)`. Only
+/// the listed parts are checked, so examples given for a single part in the
+/// puzzle text can list just that part. Mirroring [`main!`](crate::main), the
+/// input is passed through `generator` when the list starts with
+/// `generator =>`, failing the tests if it is rejected, and handed to the
+/// solvers as a `&str` otherwise.
+///
+/// The macro is meant to be invoked inside a day's test module, next to the
+/// example inputs, and expands to a module per example with a test per part:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+///
+///     aoc_utils::aoc_test! {
+///         generator =>
+///         example: (INPUT, part_1 = 11, part_2 = 31),
+///         small: ("1   1\n", part_2 = 1),
+///     }
+/// }
+/// ```
+///
+/// This defines the tests `tests::example::part_1`, `tests::example::part_2`
+/// and `tests::small::part_2`.
+///
+/// Examples using smaller [`Param`](crate::params::Param)s than the real input
+/// give their values between braces, such as
+/// `example { width = 11, height = 7 }: (INPUT, part_1 = 12)`.
+#[macro_export]
+macro_rules! aoc_test {
+    (
+        $generator: ident =>
+        $(
+            $name: ident $( { $( $param: ident = $value: expr ),* $(,)? } )?
+            : ( $input: expr $( , $part: ident = $expected: expr )+ $(,)? )
+        ),+ $(,)?
+    ) => {
+        $(
+            mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                fn params() -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                    ::std::vec![$( $( (stringify!($param), ($value).to_string()) ),* )?]
+                }
+
+                $(
+                    #[test]
+                    fn $part() {
+                        $crate::params::with_params(&params(), || {
+                            let generator_output = super::$generator($input)
+                                .expect("the example input should parse");
+                            let result = super::$part(&generator_output);
+                            assert_eq!(result, $expected);
+                        });
+                    }
+                )+
+            }
+        )+
+    };
+    (
+        $(
+            $name: ident $( { $( $param: ident = $value: expr ),* $(,)? } )?
+            : ( $input: expr $( , $part: ident = $expected: expr )+ $(,)? )
+        ),+ $(,)?
+    ) => {
+        $(
+            mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                fn params() -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                    ::std::vec![$( $( (stringify!($param), ($value).to_string()) ),* )?]
+                }
+
+                $(
+                    #[test]
+                    fn $part() {
+                        $crate::params::with_params(&params(), || {
+                            let result = super::$part($input);
+                            assert_eq!(result, $expected);
+                        });
+                    }
+                )+
+            }
+        )+
+    };
+}
+
+/// A small seeded random generator for the inputs of property tests.
+///
+/// Days compare their solvers with naive reference implementations on many
+/// random inputs, drawn from this generator by [`check`] so that a failure can
+/// be replayed from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    /// Returns the next value of the SplitMix64 sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value of `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        let span = end.abs_diff(start).wrapping_add(1);
+        let offset = if span == 0 {
+            self.next_u64()
+        } else {
+            self.next_u64() % span
+        };
+        start.wrapping_add(offset as i64)
+    }
+
+    /// Returns `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64().is_multiple_of(n)
+    }
+
+    /// Returns one of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() as i64 - 1) as usize]
+    }
+
+    /// Returns a vector of a length in `len`, with elements drawn by `element`.
+    pub fn vec<T>(
+        &mut self,
+        len: RangeInclusive<usize>,
+        mut element: impl FnMut(&mut Rng) -> T,
+    ) -> Vec<T> {
+        let len = self.range(*len.start() as i64..=*len.end() as i64) as usize;
+        (0..len).map(|_| element(self)).collect()
+    }
+}
+
+/// Checks `property` on `cases` random inputs, each drawn from an [`Rng`]
+/// with its own seed.
+///
+/// A failing case prints its seed before the panic goes on. Setting
+/// `AOC_PROPTEST_SEED` to it reruns that case alone, and `AOC_PROPTEST_CASES`
+/// changes the number of cases of every property.
+pub fn check(cases: usize, property: impl Fn(&mut Rng)) {
+    let env = |name| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v: String| v.parse().ok())
+    };
+    let seeds: Vec<u64> = match env("AOC_PROPTEST_SEED") {
+        Some(seed) => vec![seed],
+        None => {
+            let cases = env("AOC_PROPTEST_CASES").map_or(cases as u64, |cases| cases);
+            let mut seeds = Rng::new(0x5EED);
+            (0..cases).map(|_| seeds.next_u64()).collect()
+        }
+    };
+
+    for seed in seeds {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| property(&mut Rng::new(seed))));
+        if let Err(panic) = outcome {
+            eprintln!("property failed for seed {seed}, rerun it with AOC_PROPTEST_SEED={seed}");
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const INPUT: &str = "1,2,3\n4,5,6\n";
+
+    fn generator(input: &str) -> Result<Vec<u32>, crate::ParseError> {
+        Ok(crate::extract_integers(input))
+    }
+
+    static FACTOR: crate::Param<u32> = crate::Param::new("factor", 1);
+
+    fn part_1(input: &[u32]) -> u32 {
+        input.iter().sum::<u32>() * FACTOR.get()
+    }
+
+    fn part_2(input: &[u32]) -> u32 {
+        input.iter().product()
+    }
+
+    aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 21, part_2 = 720),
+        part_1_only: ("7", part_1 = 7),
+        scaled { factor = 10 }: (INPUT, part_1 = 210),
+        trailing_comma: (
+            "2,5",
+            part_1 = 7,
+            part_2 = 10,
+        ),
+    }
+
+    mod raw_input {
+        use super::INPUT;
+
+        fn part_1(input: &str) -> usize {
+            input.lines().count()
+        }
+
+        fn part_2(input: &str) -> String {
+            input.lines().rev().collect::<Vec<_>>().join(",")
+        }
+
+        aoc_test! {
+            example: (INPUT, part_1 = 2, part_2 = "4,5,6,1,2,3"),
+        }
+    }
+
+    #[test]
+    fn draws_within_ranges() {
+        let mut rng = crate::testing::Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert!((2..=4).contains(&rng.vec(2..=4, |rng| rng.one_in(2)).len()));
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        let full = rng.range(i64::MIN..=i64::MAX);
+        assert_ne!(full, rng.range(i64::MIN..=i64::MAX));
+    }
+
+    #[test]
+    fn replays_the_same_inputs() {
+        let draw = |seed| {
+            let mut rng = crate::testing::Rng::new(seed);
+            rng.vec(10..=10, |rng| *rng.choose(&[1, 2, 3]))
+        };
+        assert_eq!(draw(1), draw(1));
+        assert_ne!(draw(1), draw(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "odd")]
+    fn reports_failing_cases() {
+        crate::testing::check(100, |rng| assert!(rng.range(0..=9) % 2 == 0, "odd"));
+    }
+}
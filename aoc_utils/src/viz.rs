@@ -0,0 +1,593 @@
+//! Terminal animations of the simulations.
+//!
+//! A simulation describes its state as a [`Frame`], a grid of coloured
+//! [`Cell`]s, and hands it to [`show`] after every step. Nothing is drawn
+//! unless the runner was started with `--animate`, in which case each frame is
+//! drawn on the alternate screen of the terminal, redrawing only the cells that
+//! changed since the previous one. While a day is animated:
+//!
+//! - `space` pauses and resumes,
+//! - `n` steps a single frame while paused,
+//! - `+` and `-` double and halve the frame rate,
+//! - `q` stops animating and lets the solver run to the end.
+//!
+//! With `--render`, the frames are written to images instead (see
+//! [`image`](crate::image)), and tests record them with [`record`].
+
+use std::cell::RefCell;
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::image::{Format, Palette, Rendered, Renderer};
+
+/// The colours of the terminal palette.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    #[default]
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    /// The SGR parameter selecting the colour as foreground.
+    fn ansi(self) -> u8 {
+        match self {
+            Color::Default => 39,
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+/// A character drawn in a colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+    pub bold: bool,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell::new(' ');
+
+    pub const fn new(symbol: char) -> Self {
+        Cell {
+            symbol,
+            color: Color::Default,
+            bold: false,
+        }
+    }
+
+    pub const fn color(self, color: Color) -> Self {
+        Cell { color, ..self }
+    }
+
+    pub const fn bold(self) -> Self {
+        Cell { bold: true, ..self }
+    }
+
+    fn style(&self) -> (Color, bool) {
+        (self.color, self.bold)
+    }
+}
+
+/// The state of a simulation, as a grid of cells.
+pub trait Frame {
+    /// Returns the width and height of the grid.
+    fn size(&self) -> (usize, usize);
+
+    /// Returns the cell in column `x` of row `y`, both within [`size`](Frame::size).
+    fn cell(&self, x: usize, y: usize) -> Cell;
+}
+
+/// The cells of a frame, captured to be diffed, recorded or printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Canvas {
+    pub fn capture(frame: &dyn Frame) -> Self {
+        let (width, height) = frame.size();
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| frame.cell(x, y))
+            .collect();
+        Canvas {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Returns the cells that differ from `previous`, which has the same size.
+    fn changes<'a>(
+        &'a self,
+        previous: &'a Canvas,
+    ) -> impl Iterator<Item = (usize, usize, Cell)> + 'a {
+        self.cells
+            .iter()
+            .zip(&previous.cells)
+            .enumerate()
+            .filter(|(_, (cell, old))| cell != old)
+            .map(|(i, (&cell, _))| (i % self.width, i / self.width, cell))
+    }
+}
+
+impl Frame for Canvas {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        self.cells[y * self.width + x]
+    }
+}
+
+/// Prints the symbols of the cells, without their colours.
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y != 0 {
+                f.write_char('\n')?;
+            }
+            for cell in row {
+                f.write_char(cell.symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the escapes drawing `cells` into `out`, moving the cursor and
+/// switching styles only when needed.
+fn draw(out: &mut String, cells: impl Iterator<Item = (usize, usize, Cell)>) {
+    let mut cursor = None;
+    let mut style = None;
+    for (x, y, cell) in cells {
+        if cursor != Some((x, y)) {
+            write!(out, "\x1B[{};{}H", y + 1, x + 1).unwrap();
+        }
+        if style != Some(cell.style()) {
+            let bold = if cell.bold { "1;" } else { "" };
+            write!(out, "\x1B[0;{bold}{}m", cell.color.ansi()).unwrap();
+            style = Some(cell.style());
+        }
+        out.push(cell.symbol);
+        cursor = Some((x + 1, y));
+    }
+    if style.is_some() {
+        out.push_str("\x1B[0m");
+    }
+}
+
+/// Draws the frames of a day on the terminal.
+struct Terminal {
+    /// Frames per second, or `0` to draw them as fast as possible.
+    fps: f64,
+    keys: Option<keys::Keys>,
+    /// The frame on screen, if the alternate screen is in use.
+    previous: Option<Canvas>,
+    last: Instant,
+    paused: bool,
+    /// Whether `q` was pressed during the current stage.
+    stopped: bool,
+}
+
+impl Terminal {
+    fn show(&mut self, canvas: Canvas) {
+        let mut out = String::new();
+        match &self.previous {
+            Some(previous) if previous.size() == canvas.size() => {
+                draw(&mut out, canvas.changes(previous));
+            }
+            Some(_) => {
+                out.push_str("\x1B[2J");
+                draw(&mut out, all_cells(&canvas));
+            }
+            None => {
+                out.push_str("\x1B[?1049h\x1B[?25l\x1B[2J");
+                ALTERNATE_SCREEN.store(true, Relaxed);
+                draw(&mut out, all_cells(&canvas));
+            }
+        }
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(out.as_bytes()).unwrap();
+        stdout.flush().unwrap();
+        self.previous = Some(canvas);
+        self.wait();
+    }
+
+    /// Waits until the next frame is due, handling the keys pressed meanwhile.
+    fn wait(&mut self) {
+        loop {
+            let remaining = if self.fps > 0. {
+                Duration::from_secs_f64(1. / self.fps).saturating_sub(self.last.elapsed())
+            } else {
+                Duration::ZERO
+            };
+            let timeout = if self.paused { None } else { Some(remaining) };
+            let key = match &self.keys {
+                Some(keys) => keys.read(timeout),
+                None => {
+                    thread::sleep(remaining);
+                    None
+                }
+            };
+            match key {
+                Some(b' ') => self.paused = !self.paused,
+                Some(b'n') if self.paused => break,
+                // An unlimited frame rate is left as is.
+                Some(b'+') => self.fps *= 2.,
+                Some(b'-') => self.fps /= 2.,
+                Some(b'q') => {
+                    self.stopped = true;
+                    break;
+                }
+                Some(_) => {}
+                None if !self.paused => break,
+                None => {}
+            }
+        }
+        self.last = Instant::now();
+    }
+
+    /// Leaves the alternate screen, going back to the output of the runner.
+    fn finish(&mut self) {
+        if self.previous.take().is_some() {
+            leave_alternate_screen();
+        }
+        self.paused = false;
+        self.stopped = false;
+    }
+}
+
+fn all_cells(canvas: &Canvas) -> impl Iterator<Item = (usize, usize, Cell)> + '_ {
+    (0..canvas.height).flat_map(move |y| (0..canvas.width).map(move |x| (x, y, canvas.cell(x, y))))
+}
+
+/// Where the frames shown by the simulations go.
+enum Output {
+    Terminal(Terminal),
+    Files(Renderer),
+}
+
+/// Whether frames are drawn or written, checked before taking the lock.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static OUTPUT: Mutex<Option<Output>> = Mutex::new(None);
+/// Whether the alternate screen is in use, for the panic hook which cannot
+/// rely on taking the lock.
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Goes back to the main screen, resetting the colours and showing the cursor.
+fn leave_alternate_screen() {
+    if ALTERNATE_SCREEN.swap(false, Relaxed) {
+        print!("\x1B[0m\x1B[?25h\x1B[?1049l");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<Vec<Canvas>>> = const { RefCell::new(None) };
+}
+
+fn set_output(output: Output) {
+    *OUTPUT.lock().unwrap() = Some(output);
+    ACTIVE.store(true, Relaxed);
+}
+
+/// Draws the frames shown from now on at `fps` frames per second, or as fast
+/// as possible for `0`.
+pub fn start(fps: f64) {
+    // Statics are never dropped, so a panicking solver would otherwise leave
+    // the terminal in raw mode on the alternate screen
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            leave_alternate_screen();
+            keys::restore();
+            previous(info);
+        }));
+    });
+    set_output(Output::Terminal(Terminal {
+        fps,
+        keys: keys::Keys::new(),
+        previous: None,
+        last: Instant::now(),
+        paused: false,
+        stopped: false,
+    }));
+}
+
+/// Writes the frames shown from now on to images in `dir`, painting each cell
+/// as a square of `scale` pixels.
+pub fn render(dir: PathBuf, format: Format, scale: usize) {
+    set_output(Output::Files(Renderer::new(
+        dir,
+        format,
+        scale,
+        Palette::default(),
+    )));
+}
+
+/// Returns whether frames are drawn, written or recorded, for simulations
+/// whose frames are costly to build.
+pub fn is_active() -> bool {
+    ACTIVE.load(Relaxed) || RECORDING.with_borrow(Option::is_some)
+}
+
+/// Shows the next frame of the running simulation.
+pub fn show(frame: &dyn Frame) {
+    let recorded = RECORDING.with_borrow_mut(|recording| {
+        recording
+            .as_mut()
+            .map(|frames| frames.push(Canvas::capture(frame)))
+            .is_some()
+    });
+    if recorded || !ACTIVE.load(Relaxed) {
+        return;
+    }
+
+    match OUTPUT.lock().unwrap().as_mut() {
+        Some(Output::Terminal(terminal)) if !terminal.stopped => {
+            terminal.show(Canvas::capture(frame));
+        }
+        Some(Output::Files(renderer)) => renderer.show(&Canvas::capture(frame)),
+        _ => {}
+    }
+}
+
+/// Starts a stage of a day, such as `day15_part_1`, naming the files its
+/// frames are written to.
+pub fn begin(stage: &str) {
+    if let Some(Output::Files(renderer)) = OUTPUT.lock().unwrap().as_mut() {
+        renderer.begin(stage);
+    }
+}
+
+/// Ends the current stage, restoring the screen or writing its animation.
+/// Returns the frames written, if any.
+pub fn finish() -> io::Result<Option<Rendered>> {
+    match OUTPUT.lock().unwrap().as_mut() {
+        Some(Output::Terminal(terminal)) => {
+            terminal.finish();
+            Ok(None)
+        }
+        Some(Output::Files(renderer)) => renderer.finish(),
+        None => Ok(None),
+    }
+}
+
+/// Stops showing frames and gives the keyboard back to the terminal.
+pub fn stop() {
+    ACTIVE.store(false, Relaxed);
+    if let Some(Output::Terminal(mut terminal)) = OUTPUT.lock().unwrap().take() {
+        terminal.finish();
+    }
+}
+
+/// Runs `f`, returning the frames it showed on the current thread instead of
+/// drawing them.
+pub fn record<R>(f: impl FnOnce() -> R) -> (R, Vec<Canvas>) {
+    /// Puts back the recording of the caller, even if `f` panics.
+    struct Restore(Option<Vec<Canvas>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RECORDING.set(self.0.take());
+        }
+    }
+
+    let restore = Restore(RECORDING.replace(Some(Vec::new())));
+    let result = f();
+    let frames = RECORDING.take().unwrap_or_default();
+    drop(restore);
+    (result, frames)
+}
+
+#[cfg(unix)]
+mod keys {
+    use std::time::Duration;
+
+    use std::sync::Mutex;
+
+    /// The settings of the terminal before raw mode, while it is enabled.
+    static ORIGINAL: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+    /// Reads single key presses from a terminal on stdin, restoring its
+    /// settings when dropped.
+    pub struct Keys(());
+
+    impl Keys {
+        /// Disables line buffering and echo, if stdin is a terminal.
+        pub fn new() -> Option<Self> {
+            unsafe {
+                if libc::isatty(libc::STDIN_FILENO) != 1 {
+                    return None;
+                }
+                let mut original = std::mem::zeroed();
+                if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                    return None;
+                }
+                let mut raw = original;
+                raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+                raw.c_cc[libc::VMIN] = 1;
+                raw.c_cc[libc::VTIME] = 0;
+                if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                    return None;
+                }
+                *ORIGINAL.lock().unwrap_or_else(|e| e.into_inner()) = Some(original);
+                Some(Keys(()))
+            }
+        }
+
+        /// Waits up to `timeout`, or indefinitely for `None`, for a key.
+        pub fn read(&self, timeout: Option<Duration>) -> Option<u8> {
+            let timeout = timeout.map_or(-1, |timeout| {
+                timeout.as_millis().min(i32::MAX as u128) as i32
+            });
+            let mut fd = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            unsafe {
+                if libc::poll(&mut fd, 1, timeout) != 1 {
+                    return None;
+                }
+                let mut key = 0u8;
+                let read = libc::read(libc::STDIN_FILENO, (&mut key as *mut u8).cast(), 1);
+                (read == 1).then_some(key)
+            }
+        }
+    }
+
+    impl Drop for Keys {
+        fn drop(&mut self) {
+            restore();
+        }
+    }
+
+    /// Restores the settings of the terminal, if raw mode is enabled.
+    pub fn restore() {
+        let Ok(mut original) = ORIGINAL.try_lock() else {
+            return;
+        };
+        if let Some(original) = original.take() {
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod keys {
+    use std::time::Duration;
+
+    /// Key presses are only read from Unix terminals.
+    pub struct Keys;
+
+    impl Keys {
+        pub fn new() -> Option<Self> {
+            None
+        }
+
+        pub fn read(&self, _timeout: Option<Duration>) -> Option<u8> {
+            None
+        }
+    }
+
+    pub fn restore() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Blinker(bool);
+
+    impl Frame for Blinker {
+        fn size(&self) -> (usize, usize) {
+            (3, 2)
+        }
+
+        fn cell(&self, x: usize, y: usize) -> Cell {
+            if (x + y) % 2 == usize::from(self.0) {
+                Cell::new('#').color(Color::Green).bold()
+            } else {
+                Cell::new('.')
+            }
+        }
+    }
+
+    #[test]
+    fn captures_frames() {
+        let canvas = Canvas::capture(&Blinker(false));
+        assert_eq!(canvas.size(), (3, 2));
+        assert_eq!(canvas.to_string(), "#.#\n.#.");
+        assert_eq!(canvas.cell(1, 1).color, Color::Green);
+        assert_eq!(canvas.cell(1, 0), Cell::new('.'));
+    }
+
+    #[test]
+    fn diffs_frames() {
+        let before = Canvas::capture(&Blinker(false));
+        let after = Canvas::capture(&Blinker(true));
+        assert_eq!(after.changes(&before).count(), 6);
+        assert_eq!(after.changes(&after).count(), 0);
+
+        let mut moved = before.clone();
+        moved.cells[5] = Cell::new('@');
+        let changes: Vec<_> = moved.changes(&before).collect();
+        assert_eq!(changes, [(2, 1, Cell::new('@'))]);
+    }
+
+    #[test]
+    fn draws_only_needed_escapes() {
+        let canvas = Canvas::capture(&Blinker(false));
+        let mut out = String::new();
+        draw(&mut out, all_cells(&canvas).take(3));
+        assert_eq!(out, "\x1B[1;1H\x1B[0;1;32m#\x1B[0;39m.\x1B[0;1;32m#\x1B[0m");
+
+        let mut out = String::new();
+        draw(&mut out, [(4, 2, Cell::new('@'))].into_iter());
+        assert_eq!(out, "\x1B[3;5H\x1B[0;39m@\x1B[0m");
+
+        let mut out = String::new();
+        draw(&mut out, canvas.changes(&canvas));
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn records_frames_headlessly() {
+        assert!(!is_active());
+        let (steps, frames) = record(|| {
+            assert!(is_active());
+            for step in 0..4 {
+                show(&Blinker(step % 2 == 1));
+            }
+            4
+        });
+        assert_eq!(steps, 4);
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[1].to_string(), ".#.\n#.#");
+        assert!(!is_active());
+    }
+
+    #[test]
+    fn stops_recording_after_a_panic() {
+        let panicked = std::panic::catch_unwind(|| {
+            record(|| {
+                show(&Blinker(false));
+                panic!("the simulation failed");
+            })
+        });
+        assert!(panicked.is_err());
+        assert!(!is_active());
+    }
+}
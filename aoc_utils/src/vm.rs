@@ -0,0 +1,364 @@
+//! A register machine for the puzzles that run small assembly programs.
+//!
+//! Each puzzle describes its opcodes as an [`InstructionSet`], whose
+//! instructions read and write a fixed list of named registers, and runs the
+//! parsed program on a [`Machine`]. The machine owns the program counter and
+//! can stop after a number of steps, at breakpoints, or when it comes back to
+//! a state it has already been in, and records a trace of every step when
+//! asked to:
+//!
+//! ```ignore
+//! let mut machine = Machine::new(program).with_trace();
+//! machine.set("a", 1);
+//! assert_eq!(machine.run(), Stop::Halted);
+//! for step in machine.trace() {
+//!     println!("{step}");
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use crate::parsing::{ParseError, Source};
+
+/// The opcodes of one machine.
+pub trait InstructionSet: Clone + Debug {
+    /// The values held by the registers.
+    type Value: Copy + Debug + Default + Eq + Hash;
+
+    /// The names of the registers, in the order of their [`Register`] indices.
+    const REGISTERS: &'static [&'static str];
+
+    /// Executes the instruction on `registers` and tells where to go next.
+    fn execute(&self, registers: &mut [Self::Value]) -> Flow;
+}
+
+/// The index of a register of an [`InstructionSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Register(pub usize);
+
+impl Register {
+    /// Parses the name of one of the `I::REGISTERS`.
+    pub fn parse<I: InstructionSet>(source: &Source, name: &str) -> Result<Self, ParseError> {
+        I::REGISTERS
+            .iter()
+            .position(|r| *r == name)
+            .map(Register)
+            .ok_or_else(|| source.error(name, "a register"))
+    }
+}
+
+/// Where the program counter goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// On to the next instruction.
+    Next,
+    /// By an offset from the current instruction.
+    Jump(isize),
+    /// Nowhere, the program stops.
+    Halt,
+}
+
+/// Why [`Machine::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The program counter left the program, or an instruction halted.
+    Halted,
+    /// The next instruction is a breakpoint. Running again executes it.
+    Breakpoint(usize),
+    /// The step limit was reached.
+    StepLimit,
+    /// The machine is back in the state it was in after `first_seen` steps,
+    /// so it would run forever.
+    Loop { first_seen: usize },
+}
+
+/// One executed instruction, with the registers it left behind.
+#[derive(Debug, Clone)]
+pub struct TraceStep<I: InstructionSet> {
+    pub step: usize,
+    pub pc: usize,
+    pub instruction: I,
+    pub registers: Vec<I::Value>,
+}
+
+impl<I> Display for TraceStep<I>
+where
+    I: InstructionSet + Display,
+    I::Value: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6}  {:>4}  {:<16}",
+            self.step,
+            self.pc,
+            self.instruction.to_string()
+        )?;
+        for (name, value) in I::REGISTERS.iter().zip(&self.registers) {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The program counter and registers, which determine everything that follows.
+type State<V> = (isize, Vec<V>);
+
+/// Runs a program of an [`InstructionSet`].
+#[derive(Debug, Clone)]
+pub struct Machine<I: InstructionSet> {
+    program: Vec<I>,
+    registers: Vec<I::Value>,
+    pc: isize,
+    steps: usize,
+    step_limit: Option<usize>,
+    breakpoints: HashSet<usize>,
+    paused_at: Option<usize>,
+    trace: Option<Vec<TraceStep<I>>>,
+    seen: Option<HashMap<State<I::Value>, usize>>,
+}
+
+impl<I: InstructionSet> Machine<I> {
+    /// A machine at the start of `program`, with every register at its
+    /// default value.
+    pub fn new(program: Vec<I>) -> Self {
+        Machine {
+            program,
+            registers: vec![I::Value::default(); I::REGISTERS.len()],
+            pc: 0,
+            steps: 0,
+            step_limit: None,
+            breakpoints: HashSet::new(),
+            paused_at: None,
+            trace: None,
+            seen: None,
+        }
+    }
+
+    /// Stops running after `limit` steps in total.
+    pub fn with_step_limit(mut self, limit: usize) -> Self {
+        self.step_limit = Some(limit);
+        self
+    }
+
+    /// Stops running before executing the instruction at `pc`.
+    pub fn with_breakpoint(mut self, pc: usize) -> Self {
+        self.breakpoints.insert(pc);
+        self
+    }
+
+    /// Records every executed instruction, see [`Machine::trace`].
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Stops running when the machine repeats a state. This remembers every
+    /// state it went through, so it is only meant for short programs.
+    pub fn with_loop_detection(mut self) -> Self {
+        self.seen = Some(HashMap::new());
+        self
+    }
+
+    fn index(name: &str) -> usize {
+        I::REGISTERS
+            .iter()
+            .position(|r| *r == name)
+            .unwrap_or_else(|| panic!("No register named {name}"))
+    }
+
+    /// Returns the value of the register called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instruction set has no such register.
+    pub fn get(&self, name: &str) -> I::Value {
+        self.registers[Self::index(name)]
+    }
+
+    /// Sets the register called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instruction set has no such register.
+    pub fn set(&mut self, name: &str, value: I::Value) {
+        self.registers[Self::index(name)] = value;
+    }
+
+    pub fn registers(&self) -> &[I::Value] {
+        &self.registers
+    }
+
+    /// The index of the next instruction, if it is inside the program.
+    pub fn pc(&self) -> Option<usize> {
+        usize::try_from(self.pc)
+            .ok()
+            .filter(|&pc| pc < self.program.len())
+    }
+
+    /// The number of instructions executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The executed instructions, oldest first, if tracing is enabled.
+    pub fn trace(&self) -> &[TraceStep<I>] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Executes the next instruction, unless the program has halted.
+    pub fn step(&mut self) -> Option<Stop> {
+        let Some(pc) = self.pc() else {
+            return Some(Stop::Halted);
+        };
+        let instruction = &self.program[pc];
+        let flow = instruction.execute(&mut self.registers);
+        self.steps += 1;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceStep {
+                step: self.steps,
+                pc,
+                instruction: instruction.clone(),
+                registers: self.registers.clone(),
+            });
+        }
+        match flow {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => self.pc += offset,
+            Flow::Halt => {
+                self.pc = -1;
+                return Some(Stop::Halted);
+            }
+        }
+        None
+    }
+
+    /// Runs until the program halts or one of the configured stops is hit.
+    pub fn run(&mut self) -> Stop {
+        loop {
+            let Some(pc) = self.pc() else {
+                return Stop::Halted;
+            };
+            if self.breakpoints.contains(&pc) && self.paused_at.take() != Some(self.steps) {
+                self.paused_at = Some(self.steps);
+                return Stop::Breakpoint(pc);
+            }
+            if self.step_limit.is_some_and(|limit| self.steps >= limit) {
+                return Stop::StepLimit;
+            }
+            if let Some(seen) = &mut self.seen {
+                let state = (self.pc, self.registers.clone());
+                if let Some(&first_seen) = seen.get(&state) {
+                    return Stop::Loop { first_seen };
+                }
+                seen.insert(state, self.steps);
+            }
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A counter that counts down to zero and then loops forever.
+    #[derive(Debug, Clone)]
+    enum Op {
+        Dec(Register),
+        Jnz(Register, isize),
+        Jmp(isize),
+        Hlt,
+    }
+
+    impl InstructionSet for Op {
+        type Value = i32;
+
+        const REGISTERS: &'static [&'static str] = &["x", "y"];
+
+        fn execute(&self, registers: &mut [i32]) -> Flow {
+            match *self {
+                Op::Dec(r) => registers[r.0] -= 1,
+                Op::Jnz(r, offset) if registers[r.0] != 0 => return Flow::Jump(offset),
+                Op::Jnz(..) => (),
+                Op::Jmp(offset) => return Flow::Jump(offset),
+                Op::Hlt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+    }
+
+    fn countdown(tail: Op) -> Machine<Op> {
+        let x = Register(0);
+        let mut machine = Machine::new(vec![Op::Dec(x), Op::Jnz(x, -1), tail]);
+        machine.set("x", 3);
+        machine
+    }
+
+    #[test]
+    fn runs_until_halted() {
+        let mut machine = countdown(Op::Hlt);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.get("x"), 0);
+        assert_eq!(machine.steps(), 7);
+        assert_eq!(machine.pc(), None);
+
+        let mut machine = countdown(Op::Dec(Register(1)));
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers(), &[0, -1]);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let mut machine = countdown(Op::Hlt).with_breakpoint(1);
+        for x in [2, 1, 0] {
+            assert_eq!(machine.run(), Stop::Breakpoint(1));
+            assert_eq!(machine.get("x"), x);
+        }
+        assert_eq!(machine.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn stops_at_the_step_limit() {
+        let mut machine = countdown(Op::Hlt).with_step_limit(3);
+        assert_eq!(machine.run(), Stop::StepLimit);
+        assert_eq!((machine.steps(), machine.get("x")), (3, 1));
+        assert_eq!(machine.run(), Stop::StepLimit);
+    }
+
+    #[test]
+    fn detects_loops() {
+        let mut machine = countdown(Op::Jmp(0)).with_loop_detection();
+        assert_eq!(machine.run(), Stop::Loop { first_seen: 6 });
+
+        let mut machine = countdown(Op::Hlt).with_loop_detection();
+        assert_eq!(machine.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn traces_steps() {
+        let mut machine = countdown(Op::Hlt).with_trace();
+        machine.run();
+        let trace = machine.trace();
+        assert_eq!(trace.len(), 7);
+        let pcs = trace.iter().map(|s| s.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, [0, 1, 0, 1, 0, 1, 2]);
+        assert_eq!(trace[2].registers, [1, 0]);
+        assert!(countdown(Op::Hlt).trace().is_empty());
+    }
+
+    #[test]
+    fn parses_registers() {
+        let text = "x y z";
+        let source = Source::new(text);
+        assert_eq!(
+            Register::parse::<Op>(&source, &text[2..3]).unwrap(),
+            Register(1)
+        );
+        assert!(Register::parse::<Op>(&source, &text[4..]).is_err());
+    }
+}
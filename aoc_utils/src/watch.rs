@@ -0,0 +1,468 @@
+//! Re-runs a day whenever its files change, behind `aoc watch <year> <day>`.
+//!
+//! The day module, its input and the sources of `aoc_utils` are polled for
+//! changes. After each change the example tests of the day run, then the day
+//! runs on its real input through the `--json` flag of the release binary, and
+//! its answers and timings are printed next to those of the previous run.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_main::colored::*;
+use aoc_main::utils::Line;
+
+use crate::report::{Change, DayReport, Report};
+use crate::run_all;
+use crate::scaffold::{self, Registry, ScaffoldError};
+
+/// How often the watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Change of a median reported as faster or slower.
+const THRESHOLD: f64 = 0.1;
+
+/// The number of lines of compiler errors shown when the tests do not build.
+const ERROR_LINES: usize = 30;
+
+/// The modification times of the watched files.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl Snapshot {
+    /// Records the files of `paths`, walking directories. Files that do not
+    /// exist are recorded too, so creating them counts as a change.
+    pub fn take(paths: &[PathBuf]) -> Self {
+        fn visit(path: &Path, files: &mut BTreeMap<PathBuf, Option<SystemTime>>) {
+            match fs::read_dir(path) {
+                Ok(entries) => {
+                    for entry in entries.flatten() {
+                        visit(&entry.path(), files);
+                    }
+                }
+                Err(_) => {
+                    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                    files.insert(path.to_path_buf(), modified);
+                }
+            }
+        }
+
+        let mut files = BTreeMap::new();
+        for path in paths {
+            visit(path, &mut files);
+        }
+        Snapshot(files)
+    }
+
+    /// The files added, removed or modified since `self`.
+    pub fn changes<'a>(&'a self, newer: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = newer
+            .0
+            .iter()
+            .filter(|&(path, modified)| self.0.get(path) != Some(modified))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        changed.extend(
+            self.0
+                .keys()
+                .filter(|path| !newer.0.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+        changed
+    }
+}
+
+/// The files watched for a day: its module, its input, the `main.rs` of its
+/// year and the sources of `aoc_utils`.
+pub fn watched_paths(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, String> {
+    let main = run_all::crate_dir(root, year).join("src/main.rs");
+    let source = fs::read_to_string(&main).map_err(|err| format!("{}: {err}", main.display()))?;
+    let registry: Registry = source
+        .parse()
+        .map_err(|err: ScaffoldError| err.to_string())?;
+    if !registry.contains(day) {
+        return Err(format!("day{day} is not registered for {year}"));
+    }
+    Ok(vec![
+        scaffold::module_path(root, year, day),
+        registry.input_path(root, year, day),
+        main,
+        root.join("aoc_utils/src"),
+    ])
+}
+
+/// The outcome of the example tests of a day.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tests {
+    Passed(usize),
+    /// The failed tests, with the output of each one.
+    Failed {
+        passed: usize,
+        failures: Vec<(String, String)>,
+    },
+    /// The tests did not build, with the first errors.
+    BuildFailed(String),
+}
+
+/// Reads the outcome of `cargo test` from its output.
+pub fn parse_tests(stdout: &str, stderr: &str) -> Tests {
+    let Some(result) = stdout.lines().find(|line| line.starts_with("test result:")) else {
+        let errors: Vec<&str> = stderr
+            .lines()
+            .skip_while(|line| !line.starts_with("error"))
+            .take(ERROR_LINES)
+            .collect();
+        return Tests::BuildFailed(errors.join("\n"));
+    };
+    let count = |label: &str| {
+        result
+            .split([';', '.'])
+            .find_map(|part| part.trim().strip_suffix(label)?.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    let passed = count("passed");
+    if count("failed") == 0 {
+        return Tests::Passed(passed);
+    }
+
+    // Each failure prints its output below a `---- name stdout ----` line.
+    let mut failures = Vec::new();
+    for block in stdout.split("\n---- ").skip(1) {
+        let Some((name, output)) = block.split_once(" stdout ----\n") else {
+            continue;
+        };
+        let output = output
+            .split("\n\n")
+            .next()
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        failures.push((name.to_string(), output));
+    }
+    Tests::Failed { passed, failures }
+}
+
+/// Runs the tests of `day` in the crate of `year`.
+pub fn run_tests(root: &Path, year: u16, day: u8) -> Result<Tests, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .current_dir(root)
+        .args(["test", "-p", &format!("aoc{:02}", year % 100)])
+        .args(["--color", "never", "--", &format!("day{day}::")])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .map_err(|err| format!("could not run cargo: {err}"))?;
+    Ok(parse_tests(
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+    ))
+}
+
+/// A stage of the latest run next to the same stage of the previous one.
+#[derive(Debug, PartialEq)]
+pub struct StageDiff {
+    pub name: String,
+    pub median: Duration,
+    pub answer: Option<String>,
+    /// The answer of the previous run, if it was different.
+    pub previous_answer: Option<String>,
+    pub change: Change,
+}
+
+/// Compares every stage of `current` with the `previous` run.
+pub fn diff(previous: Option<&DayReport>, current: &DayReport) -> Vec<StageDiff> {
+    let current = Report {
+        days: vec![current.clone()],
+    };
+    let previous = Report {
+        days: previous.into_iter().cloned().collect(),
+    };
+    current
+        .compare(&previous, THRESHOLD)
+        .into_iter()
+        .map(|comparison| {
+            let answer = comparison.stage.answer.clone();
+            let previous_answer = comparison
+                .baseline
+                .and_then(|stage| stage.answer.clone())
+                .filter(|previous| Some(previous) != answer.as_ref());
+            StageDiff {
+                name: comparison.stage.name.clone(),
+                median: comparison.stage.median(),
+                answer,
+                previous_answer,
+                change: comparison.change,
+            }
+        })
+        .collect()
+}
+
+fn print_tests(tests: &Tests) {
+    match tests {
+        Tests::Passed(0) => println!("  - {}", Line::new("tests").with_state("none".yellow())),
+        Tests::Passed(passed) => println!(
+            "  - {}",
+            Line::new("tests").with_state(format!("{passed} passed").green())
+        ),
+        Tests::Failed { passed, failures } => {
+            let state = format!("{} failed, {passed} passed", failures.len());
+            println!("  - {}", Line::new("tests").with_state(state.red()));
+            for (name, output) in failures {
+                println!("    {}", name.bold());
+                for line in output.lines() {
+                    println!("      {line}");
+                }
+            }
+        }
+        Tests::BuildFailed(errors) => {
+            println!("  - {}", Line::new("build").with_state("failed".red()));
+            for line in errors.lines() {
+                println!("    {line}");
+            }
+        }
+    }
+}
+
+fn print_diff(diffs: &[StageDiff]) {
+    for diff in diffs {
+        let timing = match diff.change {
+            Change::New => String::new(),
+            Change::Unchanged(ratio) | Change::Improved(ratio) | Change::Regressed(ratio) => {
+                format!(" {:+.0}%", ratio * 100.)
+            }
+        };
+        let timing = match diff.change {
+            Change::Improved(_) => timing.green(),
+            Change::Regressed(_) => timing.red(),
+            _ => timing.dimmed(),
+        };
+        let line = Line::new(&diff.name).with_duration(diff.median);
+        let state = match (&diff.answer, &diff.previous_answer) {
+            (Some(answer), Some(previous)) => format!("{answer} (was {previous})").yellow(),
+            (Some(answer), None) => answer.normal(),
+            (None, _) => "".normal(),
+        };
+        println!("  - {:30}{timing}", line.with_state(state));
+    }
+}
+
+/// Runs the tests of `day`, then the day on its input if they build, and
+/// prints how it changed since `previous`. Returns the timings of the run.
+fn run_once(
+    root: &Path,
+    year: u16,
+    day: u8,
+    input: &Path,
+    previous: Option<&DayReport>,
+) -> Option<DayReport> {
+    let tests = match run_tests(root, year, day) {
+        Ok(tests) => tests,
+        Err(err) => {
+            println!("  - {}", Line::new("tests").with_state(err.red()));
+            return None;
+        }
+    };
+    print_tests(&tests);
+    if matches!(tests, Tests::BuildFailed(_)) {
+        return None;
+    }
+
+    let saved = fs::metadata(input).is_ok_and(|metadata| metadata.len() > 0);
+    if !saved {
+        let state = format!("no input saved in {}", input.display());
+        println!("  - {}", Line::new("input").with_state(state.yellow()));
+        return None;
+    }
+    let report = run_all::build(root, &[year])
+        .and_then(|()| run_all::run_day(&run_all::binary(root, year), day));
+    match report {
+        Ok(report) => {
+            print_diff(&diff(previous, &report));
+            Some(report)
+        }
+        Err(err) => {
+            println!("  - {}", Line::new("input").with_state(err.red()));
+            None
+        }
+    }
+}
+
+/// Runs `day` of `year` now and after every change to its files, until
+/// interrupted.
+pub fn watch(root: &Path, year: u16, day: u8) -> Result<(), String> {
+    let paths = watched_paths(root, year, day)?;
+    let input = &paths[1];
+    let mut snapshot = Snapshot::take(&paths);
+    let mut previous = None;
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for run in 1.. {
+        if run > 1 {
+            println!();
+        }
+        println!("{} (run {run})", format!("Day {day} of {year}").bold());
+        for path in &changed {
+            let path = path.strip_prefix(root).unwrap_or(path);
+            println!("  {}", path.display().to_string().dimmed());
+        }
+        if let Some(report) = run_once(root, year, day, input, previous.as_ref()) {
+            previous = Some(report);
+        }
+
+        // Editors may write a file several times when saving it, so wait for
+        // the files to settle before running again.
+        let mut latest = loop {
+            thread::sleep(POLL_INTERVAL);
+            let latest = Snapshot::take(&paths);
+            if latest != snapshot {
+                break latest;
+            }
+        };
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let settled = Snapshot::take(&paths);
+            if settled == latest {
+                break;
+            }
+            latest = settled;
+        }
+        changed = snapshot
+            .changes(&latest)
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect();
+        snapshot = latest;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::StageTiming;
+
+    const FAILED: &str = "
+running 3 tests
+test day16::tests::example::part_1 ... ok
+test day16::tests::example::part_2 ... FAILED
+test day16::tests::small::part_1 ... FAILED
+
+failures:
+
+---- day16::tests::example::part_2 stdout ----
+
+thread 'day16::tests::example::part_2' panicked at aoc24/src/day16.rs:12:5:
+assertion `left == right` failed
+  left: 40
+ right: 45
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- day16::tests::small::part_1 stdout ----
+thread 'day16::tests::small::part_1' panicked at aoc24/src/day16.rs:20:9:
+not yet implemented
+
+
+failures:
+    day16::tests::example::part_2
+    day16::tests::small::part_1
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 51 filtered out; finished in 0.01s
+";
+
+    fn stage(name: &str, median_ns: u64, answer: Option<&str>) -> StageTiming {
+        StageTiming {
+            answer: answer.map(str::to_string),
+            ..StageTiming::from_samples(name, &[Duration::from_nanos(median_ns)])
+        }
+    }
+
+    #[test]
+    fn reads_passed_tests() {
+        let stdout = "running 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored; \
+                      0 measured; 52 filtered out; finished in 0.00s\n";
+        assert_eq!(parse_tests(stdout, ""), Tests::Passed(2));
+    }
+
+    #[test]
+    fn reads_failed_tests() {
+        let Tests::Failed { passed, failures } = parse_tests(FAILED, "") else {
+            panic!("the tests failed");
+        };
+        assert_eq!(passed, 1);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].0, "day16::tests::example::part_2");
+        assert!(failures[0].1.ends_with(" right: 45"));
+        assert_eq!(
+            failures[1].1,
+            "thread 'day16::tests::small::part_1' panicked at aoc24/src/day16.rs:20:9:\n\
+             not yet implemented"
+        );
+    }
+
+    #[test]
+    fn reads_build_errors() {
+        let stderr = "   Compiling aoc24 v0.1.0\nerror[E0425]: cannot find value `x`\n  --> \
+                      aoc24/src/day16.rs:3:5\n";
+        assert_eq!(
+            parse_tests("", stderr),
+            Tests::BuildFailed(
+                "error[E0425]: cannot find value `x`\n  --> aoc24/src/day16.rs:3:5".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn compares_with_the_previous_run() {
+        let report = |stages| DayReport {
+            year: 2024,
+            day: 16,
+            stages,
+        };
+        let previous = report(vec![
+            stage("generator", 100, None),
+            stage("part_1", 1000, Some("7036")),
+        ]);
+        let current = report(vec![
+            stage("generator", 102, None),
+            stage("part_1", 500, Some("7036")),
+            stage("part_2", 10, Some("45")),
+        ]);
+
+        let diffs = diff(Some(&previous), &current);
+        assert!(matches!(diffs[0].change, Change::Unchanged(_)));
+        assert!(matches!(diffs[1].change, Change::Improved(_)));
+        assert_eq!(diffs[1].previous_answer, None);
+        assert_eq!(diffs[2].change, Change::New);
+
+        let fixed = report(vec![stage("part_1", 500, Some("7040"))]);
+        let diffs = diff(Some(&current), &fixed);
+        assert_eq!(diffs[0].previous_answer.as_deref(), Some("7036"));
+        assert!(diff(None, &fixed).iter().all(|d| d.change == Change::New));
+    }
+
+    #[test]
+    fn detects_changed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let missing = dir.join("day1.txt");
+        let paths = [dir.join("src"), missing.clone()];
+
+        let before = Snapshot::take(&paths);
+        assert_eq!(before, Snapshot::take(&paths));
+        fs::write(&missing, "1\n").unwrap();
+        let after = Snapshot::take(&paths);
+        assert_eq!(before.changes(&after), [missing.as_path()]);
+        fs::remove_file(dir.join("src/lib.rs")).unwrap();
+        assert_eq!(
+            after.changes(&Snapshot::take(&paths)),
+            [dir.join("src/lib.rs").as_path()]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,33 @@
+use aoc_utils::prelude::*;
+
+pub fn generator(input: &str) -> Result<Vec<Vec2<i32>>, ParseError> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .map(|line| {
+            let [x, y] = source.integers::<i32, 2>(line)?;
+            Ok((x, y).into())
+        })
+        .collect()
+}
+
+pub fn part_1(input: &[Vec2<i32>]) -> u32 {
+    todo!();
+}
+
+pub fn part_2(input: &[Vec2<i32>]) -> u32 {
+    todo!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+"#;
+
+    aoc_utils::aoc_test! {
+        generator =>
+        example: (INPUT, part_1 = 7, part_2 = 5),
+    }
+}